@@ -24,6 +24,10 @@ export enum ClientMethod {
   HAS_CONSENTED = 'has_consented',
   IS_DEFAULT_PRIVATE_CONTEXT = 'is_default_private_context',
   SEARCH_DOCUMENT_BY_EMBEDDING = 'search_document_by_embedding',
+  GET_DOCUMENT_CHUNKS = 'get_document_chunks',
+  RECORD_DOCUMENT_ACCESS = 'record_document_access',
+  GET_MY_PENDING_ACTIONS = 'get_my_pending_actions',
+  RECORD_SIGNATURE_USE = 'record_signature_use',
 }
 
 export interface SignatureRecord {
@@ -55,6 +59,9 @@ export enum DocumentStatus {
   Pending = 'Pending',
   PartiallySigned = 'PartiallySigned',
   FullySigned = 'FullySigned',
+  Expired = 'Expired',
+  Declined = 'Declined',
+  Voided = 'Voided',
 }
 
 export enum PermissionLevel {
@@ -66,6 +73,13 @@ export enum PermissionLevel {
 export interface ParticipantInfo {
   user_id: UserId;
   permission_level: PermissionLevel;
+  deactivation: ParticipantDeactivation | null;
+}
+
+export interface ParticipantDeactivation {
+  deactivated_by: UserId;
+  deactivated_at: number;
+  reason: string | null;
 }
 
 export interface ContextDetails {
@@ -77,6 +91,23 @@ export interface ContextDetails {
   participants: ParticipantInfo[];
   document_count: number;
   created_at: number;
+  archive: ContextArchive | null;
+  profile: ContextProfile;
+}
+
+export interface ContextProfile {
+  description: string | null;
+  avatar_blob_id: string | null;
+  external_reference: string | null;
+  jurisdiction: string | null;
+  tags: string[];
+  updated_by: UserId | null;
+  updated_at: number;
+}
+
+export interface ContextArchive {
+  archived_by: UserId;
+  archived_at: number;
 }
 
 export interface DocumentInfo {
@@ -92,6 +123,29 @@ export interface DocumentInfo {
   extracted_text?: string; // New: Extracted text from PDF
 }
 
+export interface DocumentChunk {
+  text: string;
+  embedding: number[] | { scale: number; values: number[] };
+  start_position: number;
+  end_position: number;
+}
+
+export type PendingActionKind = 'Consent' | 'Sign';
+
+export interface PendingAction {
+  kind: PendingActionKind;
+  document_id: string;
+  document_name: string;
+  requested_by: UserId;
+  created_at: number;
+}
+
+export interface Page<T> {
+  items: T[];
+  total: number;
+  next_cursor: number | null;
+}
+
 export interface Document {
   id: string;
   name: string;
@@ -122,9 +176,16 @@ export interface ClientApi {
     name: string,
     blobIdStr: string,
     dataSize: number,
+    format: string,
   ): ApiResponse<number>;
   deleteSignature(signatureId: number): ApiResponse<void>;
   listSignatures(): ApiResponse<SignatureRecord[]>;
+  // Returns how many documents the signature was applied to in the window
+  recordSignatureUse(
+    signatureId: number,
+    contextId: string,
+    documentId: string,
+  ): ApiResponse<number>;
 
   // Contract expects: shared_identity_str (base58 public key string)
   joinSharedContext(
@@ -203,4 +264,20 @@ export interface ClientApi {
     agreementContextID?: string,
     agreementContextUserID?: string,
   ): ApiResponse<string>;
+  getDocumentChunks(
+    documentId: string,
+    offset?: number,
+    limit?: number,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<Page<DocumentChunk>>;
+  recordDocumentAccess(
+    documentId: string,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<void>;
+  getMyPendingActions(
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<PendingAction[]>;
 }
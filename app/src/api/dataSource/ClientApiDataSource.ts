@@ -12,6 +12,9 @@ import {
   ClientApi,
   ClientMethod,
   ContextDetails,
+  DocumentChunk,
+  Page,
+  PendingAction,
   PermissionLevel,
   UserId,
 } from '../clientApi';
@@ -42,9 +45,40 @@ function getErrorMessage(error: any): string {
   }
   if (typeof error === 'string') return error;
   if (error?.message) return error.message;
+  // Application errors are { kind, message } (MeroDocsError in the logic)
+  if (error?.data?.kind === 'ContextArchived') {
+    return 'This context is archived and read-only';
+  }
+  if (typeof error?.data?.message === 'string') return error.data.message;
   if (error?.data) return JSON.stringify(error.data);
   return 'An unexpected error occurred';
 }
+
+// Page size requested from paginated list methods
+const PAGE_LIMIT = 100;
+
+// List methods return one Page ({ items, total, next_cursor }) per call. Fetch
+// pages until next_cursor runs out and return every item; a response that is
+// not a page is returned as is.
+async function collectPages(
+  fetchPage: (args: { offset: number; limit: number }) => Promise<any>,
+): Promise<any> {
+  const items: any[] = [];
+  let offset: number | null = 0;
+  while (offset !== null) {
+    const data = await fetchPage({ offset, limit: PAGE_LIMIT });
+    const page = Array.isArray(data?.items)
+      ? data
+      : Array.isArray(data?.output?.items)
+        ? data.output
+        : null;
+    if (!page) return items.length > 0 ? items : data;
+    items.push(...page.items);
+    offset = page.next_cursor ?? null;
+  }
+  return items;
+}
+
 function getContextSpecificAuthConfig(
   agreementContextID: string,
   agreementContextUserID: string,
@@ -529,6 +563,7 @@ export class ClientApiDataSource implements ClientApi {
     name: string,
     blobIdStr: string,
     dataSize: number,
+    format: string,
     contextId?: string,
     agreementContextID?: string,
     agreementContextUserID?: string,
@@ -551,6 +586,7 @@ export class ClientApiDataSource implements ClientApi {
           name,
           blob_id_str: blobIdStr,
           data_size: dataSize,
+          format,
         };
 
         const result = await this.app.execute(
@@ -587,6 +623,7 @@ export class ClientApiDataSource implements ClientApi {
           name,
           blob_id_str: blobIdStr,
           data_size: dataSize,
+          format,
         };
 
         // Ensure executorPublicKey is always set - prioritize signatureContextUserID, then authConfig, then getExecutorPublicKey
@@ -703,6 +740,66 @@ export class ClientApiDataSource implements ClientApi {
     }
   }
 
+  async recordSignatureUse(
+    signatureId: number,
+    agreementContextId: string,
+    documentId: string,
+  ): Promise<any> {
+    try {
+      const params = {
+        signature_id: signatureId,
+        context_id: agreementContextId,
+        document_id: documentId,
+      };
+
+      if (this.app) {
+        const defaultContextService = DefaultContextService.getInstance(
+          this.app,
+        );
+        const defaultContext = defaultContextService.getStoredDefaultContext();
+
+        if (!defaultContext) {
+          throw new Error(
+            'Default context not found. Please ensure you are connected to Calimero and have a default context initialized.',
+          );
+        }
+
+        const result = await this.app.execute(
+          defaultContext,
+          ClientMethod.RECORD_SIGNATURE_USE,
+          params,
+        );
+
+        return {
+          data: result.data || result,
+        };
+      } else {
+        // Fallback to old API
+        const authConfig = getAuthConfig();
+
+        const response = await rpcClient.execute(
+          {
+            contextId: authConfig.contextId || getContextId() || '',
+            method: ClientMethod.RECORD_SIGNATURE_USE,
+            argsJson: params,
+            executorPublicKey: (authConfig.executorPublicKey ||
+              getExecutorPublicKey() ||
+              '') as string,
+          },
+          RequestConfig,
+        );
+        return {
+          data: response.result,
+        };
+      }
+    } catch (error: any) {
+      console.error('ClientApiDataSource: Error in recordSignatureUse:', error);
+      return {
+        error: error,
+      };
+    }
+  }
+
   async listSignatures(
     contextId?: string,
     agreementContextID?: string,
@@ -722,12 +819,15 @@ export class ClientApiDataSource implements ClientApi {
           );
         }
 
-        const result = await this.app.execute(
-          defaultContext,
-          ClientMethod.LIST_SIGNATURES,
-          {},
-        );
-        const extractedData = result.data || result;
+        const app = this.app;
+        const extractedData = await collectPages(async (args) => {
+          const result = await app.execute(
+            defaultContext,
+            ClientMethod.LIST_SIGNATURES,
+            args,
+          );
+          return result.data || result;
+        });
 
         if (
           Array.isArray(extractedData) &&
@@ -767,20 +867,22 @@ export class ClientApiDataSource implements ClientApi {
               : getAuthConfig();
         }
 
-        const response = await rpcClient.execute(
-          {
-            contextId:
-              authConfig.contextId || contextId || getContextId() || '',
-            method: ClientMethod.LIST_SIGNATURES,
-            argsJson: {},
-            executorPublicKey: (authConfig.executorPublicKey ||
-              getExecutorPublicKey() ||
-              '') as string,
-          },
-          RequestConfig,
-        );
-
-        const extractedData = response.result?.output || response.result;
+        const extractedData = await collectPages(async (args) => {
+          const response = await rpcClient.execute(
+            {
+              contextId:
+                authConfig.contextId || contextId || getContextId() || '',
+              method: ClientMethod.LIST_SIGNATURES,
+              argsJson: args,
+              executorPublicKey: (authConfig.executorPublicKey ||
+                getExecutorPublicKey() ||
+                '') as string,
+            },
+            RequestConfig,
+          );
+          if (response?.error) throw response.error;
+          return response.result?.output || response.result;
+        });
 
         if (
           Array.isArray(extractedData) &&
@@ -1150,29 +1252,22 @@ export class ClientApiDataSource implements ClientApi {
       if (authConfig.executorPublicKey) {
         setExecutorPublicKey(authConfig.executorPublicKey);
       }
-      const response = await rpcClient.execute(
-        {
-          contextId: authConfig.contextId || contextId || getContextId() || '',
-          method: ClientMethod.LIST_DOCUMENTS,
-          argsJson: {},
-          executorPublicKey: (authConfig.executorPublicKey ||
-            getExecutorPublicKey() ||
-            '') as string,
-        },
-        RequestConfig,
-      );
-
-      if (response?.error) {
-        return {
-          data: null,
-          error: {
-            code: response.error.code ?? 500,
-            message: getErrorMessage(response.error),
+      const data = await collectPages(async (args) => {
+        const response = await rpcClient.execute(
+          {
+            contextId:
+              authConfig.contextId || contextId || getContextId() || '',
+            method: ClientMethod.LIST_DOCUMENTS,
+            argsJson: args,
+            executorPublicKey: (authConfig.executorPublicKey ||
+              getExecutorPublicKey() ||
+              '') as string,
           },
-        };
-      }
-
-      const data = response.result?.output || response.result;
+          RequestConfig,
+        );
+        if (response?.error) throw response.error;
+        return response.result?.output || response.result;
+      });
 
       return {
         data: data,
@@ -1316,4 +1411,177 @@ export class ClientApiDataSource implements ClientApi {
       };
     }
   }
+
+  async getDocumentChunks(
+    documentId: string,
+    offset?: number,
+    limit?: number,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<Page<DocumentChunk>> {
+    try {
+      const authConfig =
+        agreementContextID && agreementContextUserID
+          ? getContextSpecificAuthConfig(
+              agreementContextID,
+              agreementContextUserID,
+            )
+          : getAuthConfig();
+
+      const response = await rpcClient.execute(
+        {
+          contextId: authConfig.contextId || getContextId() || '',
+          method: ClientMethod.GET_DOCUMENT_CHUNKS,
+          argsJson: {
+            document_id: documentId,
+            offset: offset ?? null,
+            limit: limit ?? null,
+          },
+          executorPublicKey: (authConfig.executorPublicKey ||
+            getExecutorPublicKey() ||
+            '') as string,
+        },
+        RequestConfig,
+      );
+
+      if (response?.error) {
+        return {
+          data: undefined,
+          error: {
+            code: response.error.code ?? 500,
+            message: getErrorMessage(response.error),
+          },
+        };
+      }
+
+      const data: any = response.result?.output ?? response.result;
+
+      return {
+        data: data as Page<DocumentChunk>,
+        error: null,
+      };
+    } catch (error: any) {
+      console.error('ClientApiDataSource: Error in getDocumentChunks:', error);
+      return {
+        data: null,
+        error: {
+          code: error.code || 500,
+          message: getErrorMessage(error),
+        },
+      };
+    }
+  }
+
+  async recordDocumentAccess(
+    documentId: string,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<void> {
+    try {
+      const authConfig =
+        agreementContextID && agreementContextUserID
+          ? getContextSpecificAuthConfig(
+              agreementContextID,
+              agreementContextUserID,
+            )
+          : getAuthConfig();
+
+      const response = await rpcClient.execute(
+        {
+          contextId: authConfig.contextId || getContextId() || '',
+          method: ClientMethod.RECORD_DOCUMENT_ACCESS,
+          argsJson: {
+            document_id: documentId,
+          },
+          executorPublicKey: (authConfig.executorPublicKey ||
+            getExecutorPublicKey() ||
+            '') as string,
+        },
+        RequestConfig,
+      );
+
+      if (response?.error) {
+        return {
+          data: undefined,
+          error: {
+            code: response.error.code ?? 500,
+            message: getErrorMessage(response.error),
+          },
+        };
+      }
+
+      return {
+        data: undefined,
+        error: null,
+      };
+    } catch (error: any) {
+      console.error(
+        'ClientApiDataSource: Error in recordDocumentAccess:',
+        error,
+      );
+      return {
+        data: null,
+        error: {
+          code: error.code || 500,
+          message: getErrorMessage(error),
+        },
+      };
+    }
+  }
+
+  async getMyPendingActions(
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): ApiResponse<PendingAction[]> {
+    try {
+      const authConfig =
+        agreementContextID && agreementContextUserID
+          ? getContextSpecificAuthConfig(
+              agreementContextID,
+              agreementContextUserID,
+            )
+          : getAuthConfig();
+
+      const response = await rpcClient.execute(
+        {
+          contextId: authConfig.contextId || getContextId() || '',
+          method: ClientMethod.GET_MY_PENDING_ACTIONS,
+          argsJson: {},
+          executorPublicKey: (authConfig.executorPublicKey ||
+            getExecutorPublicKey() ||
+            '') as string,
+        },
+        RequestConfig,
+      );
+
+      if (response?.error) {
+        return {
+          data: undefined,
+          error: {
+            code: response.error.code ?? 500,
+            message: getErrorMessage(response.error),
+          },
+        };
+      }
+
+      const data: any = response.result?.output ?? response.result;
+
+      return {
+        data: (data ?? []) as PendingAction[],
+        error: null,
+      };
+    } catch (error: any) {
+      console.error(
+        'ClientApiDataSource: Error in getMyPendingActions:',
+        error,
+      );
+      return {
+        data: null,
+        error: {
+          code: error.code || 500,
+          message: getErrorMessage(error),
+        },
+      };
+    }
+  }
 }
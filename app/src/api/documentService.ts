@@ -1,5 +1,11 @@
 import { ClientApiDataSource } from './dataSource/ClientApiDataSource';
-import { DocumentInfo, Document } from './clientApi';
+import {
+  DocumentInfo,
+  Document,
+  DocumentChunk,
+  Page,
+  PendingAction,
+} from './clientApi';
 import { blobClient } from '@calimero-network/calimero-client';
 import bs58 from 'bs58';
 // TODO: Re-enable when AI chatbot is re-implemented
@@ -257,6 +263,73 @@ export class DocumentService {
     }
   }
 
+  // Chunks are not part of DocumentInfo; fetch them a page at a time when needed
+  async getDocumentChunks(
+    documentId: string,
+    offset?: number,
+    limit?: number,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): Promise<{ data?: Page<DocumentChunk>; error?: any }> {
+    try {
+      const response = await this.clientApi.getDocumentChunks(
+        documentId,
+        offset,
+        limit,
+        agreementContextID,
+        agreementContextUserID,
+      );
+
+      return {
+        data: response.data || undefined,
+        error: response.error,
+      };
+    } catch (error) {
+      console.error('DocumentService: Error in getDocumentChunks:', error);
+      return { error: { message: `Failed to load document chunks: ${error}` } };
+    }
+  }
+
+  // Record that the current user retrieved the document's PDF
+  async recordDocumentAccess(
+    documentId: string,
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): Promise<{ error?: any }> {
+    try {
+      const response = await this.clientApi.recordDocumentAccess(
+        documentId,
+        agreementContextID,
+        agreementContextUserID,
+      );
+      return { error: response.error };
+    } catch (error) {
+      console.error('DocumentService: Error in recordDocumentAccess:', error);
+      return { error: { message: `Failed to record document access: ${error}` } };
+    }
+  }
+
+  // What the current user still has to consent to or sign in the context
+  async getMyPendingActions(
+    agreementContextID?: string,
+    agreementContextUserID?: string,
+  ): Promise<{ data?: PendingAction[]; error?: any }> {
+    try {
+      const response = await this.clientApi.getMyPendingActions(
+        agreementContextID,
+        agreementContextUserID,
+      );
+
+      return {
+        data: response.data || undefined,
+        error: response.error,
+      };
+    } catch (error) {
+      console.error('DocumentService: Error in getMyPendingActions:', error);
+      return { error: { message: `Failed to load pending actions: ${error}` } };
+    }
+  }
+
   private formatDocument(documentInfo: DocumentInfo): Document {
     const uploadedAtMs = Math.floor(
       Number(documentInfo.uploaded_at) / 1_000_000,
@@ -124,6 +124,9 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
   const [selectedSavedSignature, setSelectedSavedSignature] = useState<
     string | null
   >(null);
+  const [selectedSavedSignatureId, setSelectedSavedSignatureId] = useState<
+    string | null
+  >(null);
   const [signingMode, setSigningMode] = useState(false);
   const [showMobileActions, setShowMobileActions] = useState(false);
   const [savingToContext, setSavingToContext] = useState(false);
@@ -208,6 +211,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
     setDocumentSignatures((prev) => [...prev, signature]);
     setSigningMode(false);
     setSelectedSavedSignature(null);
+    setSelectedSavedSignatureId(null);
   }, []);
 
   const handleCanvasClick = useCallback(
@@ -239,6 +243,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
           pageNumber: currentPage,
           signatureData: selectedSavedSignature,
           timestamp: Date.now(),
+          savedSignatureId: selectedSavedSignatureId ?? undefined,
         };
 
         handleSignaturePlace(signaturePosition);
@@ -280,6 +285,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
       onSignaturePlace,
       signingMode,
       selectedSavedSignature,
+      selectedSavedSignatureId,
       handleSignaturePlace,
     ],
   );
@@ -314,6 +320,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
           pageNumber: currentPage,
           signatureData: selectedSavedSignature,
           timestamp: Date.now(),
+          savedSignatureId: selectedSavedSignatureId ?? undefined,
         };
 
         handleSignaturePlace(signaturePosition);
@@ -324,6 +331,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
       selectedSignatureId,
       signingMode,
       selectedSavedSignature,
+      selectedSavedSignatureId,
       currentPage,
       handleSignaturePlace,
     ],
@@ -405,7 +413,9 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
               id: sig.id.toString(),
               name: sig.name,
               dataURL,
-              createdAt: new Date(sig.created_at).toLocaleDateString(),
+              createdAt: new Date(
+                Number(sig.created_at) / 1_000_000,
+              ).toLocaleDateString(),
             };
           }),
         );
@@ -424,14 +434,16 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
     setShowSignaturePad(true);
   };
 
-  const handleSelectExistingSignature = (signatureData: string) => {
-    setSelectedSavedSignature(signatureData);
+  const handleSelectExistingSignature = (signature: SavedSignature) => {
+    setSelectedSavedSignature(signature.dataURL);
+    setSelectedSavedSignatureId(signature.id);
     setSigningMode(true);
     setShowSignatureOptions(false);
   };
 
   const handleSignaturePadSave = (signatureData: string) => {
     setSelectedSavedSignature(signatureData);
+    setSelectedSavedSignatureId(null);
     setSigningMode(true);
     setShowSignaturePad(false);
   };
@@ -518,6 +530,24 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
         return;
       }
 
+      // Let the private context track where stored signatures are applied so
+      // it can flag unusual use. A failure here does not undo the signing.
+      const usedSignatureIds = new Set(
+        documentSignatures
+          .map((sig) => sig.savedSignatureId)
+          .filter((id): id is string => !!id),
+      );
+      for (const signatureId of usedSignatureIds) {
+        const useResponse = await api.recordSignatureUse(
+          Number(signatureId),
+          contextId,
+          documentId,
+        );
+        if (useResponse.error) {
+          console.error('Error recording signature use:', useResponse.error);
+        }
+      }
+
       if (onDocumentSaved) {
         onDocumentSaved();
       }
@@ -1239,7 +1269,7 @@ const PDFViewer: React.FC<PDFViewerProps> = ({
                           <button
                             key={signature.id}
                             onClick={() =>
-                              handleSelectExistingSignature(signature.dataURL)
+                              handleSelectExistingSignature(signature)
                             }
                             className="w-full p-3 border border-border rounded-lg hover:bg-muted transition-colors flex items-center gap-3 text-left"
                           >
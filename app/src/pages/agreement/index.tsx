@@ -55,7 +55,11 @@ import AuditTrail from '../../components/AuditTrail';
 import { DocumentService } from '../../api/documentService';
 import { ClientApiDataSource } from '../../api/dataSource/ClientApiDataSource';
 import { ContextApiDataSource } from '../../api/dataSource/nodeApiDataSource';
-import { ContextDetails, PermissionLevel } from '../../api/clientApi';
+import {
+  ContextDetails,
+  DocumentStatus,
+  PermissionLevel,
+} from '../../api/clientApi';
 import bs58 from 'bs58';
 
 /**
@@ -94,6 +98,59 @@ const ANIMATION_VARIANTS = {
   },
 } as const;
 
+interface StatusBadge {
+  label: string;
+  colors: { backgroundColor: string; color: string };
+}
+
+const STATUS_BADGES: Record<string, StatusBadge> = {
+  [DocumentStatus.Pending]: {
+    label: 'Pending',
+    colors: {
+      backgroundColor: 'rgba(163, 163, 163, 0.15)',
+      color: '#737373',
+    },
+  },
+  [DocumentStatus.PartiallySigned]: {
+    label: 'Partially Signed',
+    colors: {
+      backgroundColor: 'rgba(234, 179, 8, 0.15)',
+      color: '#ca8a04',
+    },
+  },
+  [DocumentStatus.FullySigned]: {
+    label: 'Fully Signed',
+    colors: {
+      backgroundColor: 'rgba(22, 163, 74, 0.15)',
+      color: '#16a34a',
+    },
+  },
+  [DocumentStatus.Expired]: {
+    label: 'Expired',
+    colors: {
+      backgroundColor: 'rgba(234, 88, 12, 0.15)',
+      color: '#ea580c',
+    },
+  },
+  [DocumentStatus.Declined]: {
+    label: 'Declined',
+    colors: {
+      backgroundColor: 'rgba(220, 38, 38, 0.15)',
+      color: '#dc2626',
+    },
+  },
+  [DocumentStatus.Voided]: {
+    label: 'Voided',
+    colors: {
+      backgroundColor: 'rgba(82, 82, 82, 0.15)',
+      color: '#525252',
+    },
+  },
+};
+
+const statusBadge = (status: string): StatusBadge =>
+  STATUS_BADGES[status] ?? STATUS_BADGES[DocumentStatus.Pending];
+
 interface UploadedDocument {
   id: string;
   name: string;
@@ -611,6 +668,12 @@ const AgreementPage: React.FC = () => {
           document.pdfBlobId,
           contextID || '',
         );
+        // Access is recorded for compliance; a failure here must not block viewing
+        void documentService.recordDocumentAccess(
+          document.id,
+          contextID || undefined,
+          localStorage.getItem('agreementContextUserID') || undefined,
+        );
         const file = new File([blob], document.name, {
           type: 'application/pdf',
         });
@@ -628,7 +691,7 @@ const AgreementPage: React.FC = () => {
         showNotification(`Failed to load PDF: "${document.name}".`, 'error');
       }
     },
-    [showNotification, app],
+    [showNotification, app, documentService],
   );
 
   const handleClosePDFViewer = useCallback(() => {
@@ -659,6 +722,11 @@ const AgreementPage: React.FC = () => {
           doc.pdfBlobId,
           contextID || ' ',
         );
+        void documentService.recordDocumentAccess(
+          doc.id,
+          contextID || undefined,
+          localStorage.getItem('agreementContextUserID') || undefined,
+        );
 
         const url = URL.createObjectURL(blob);
         const link = document.createElement('a');
@@ -673,7 +741,7 @@ const AgreementPage: React.FC = () => {
         showNotification(`Failed to download "${doc.name}".`, 'error');
       }
     },
-    [showNotification, app],
+    [showNotification, app, documentService],
   );
 
   const handleGenerateOpenInvitation = useCallback(async () => {
@@ -1473,25 +1541,10 @@ const AgreementPage: React.FC = () => {
                           style={{
                             padding: `${spacing[2].value} ${spacing[3].value}`,
                             borderRadius: radius.lg.value,
-                            backgroundColor:
-                              document.status === 'FullySigned'
-                                ? 'rgba(22, 163, 74, 0.15)'
-                                : document.status === 'PartiallySigned'
-                                  ? 'rgba(234, 179, 8, 0.15)'
-                                  : 'rgba(163, 163, 163, 0.15)',
-                            color:
-                              document.status === 'FullySigned'
-                                ? '#16a34a'
-                                : document.status === 'PartiallySigned'
-                                  ? '#ca8a04'
-                                  : '#737373',
+                            ...statusBadge(document.status).colors,
                           }}
                         >
-                          {document.status === 'FullySigned'
-                            ? 'Fully Signed'
-                            : document.status === 'PartiallySigned'
-                              ? 'Partially Signed'
-                              : 'Pending'}
+                          {statusBadge(document.status).label}
                         </Text>
                       </Box>
                       <Flex alignItems="center" gap="sm">
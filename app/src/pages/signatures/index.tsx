@@ -177,7 +177,7 @@ export default function SignaturesPage() {
       const { blobId, size } = await uploadSignatureBlob(blob);
 
       const newSignatureName = `Signature ${signatures.length + 1}`;
-      await api.createSignature(newSignatureName, blobId, size);
+      await api.createSignature(newSignatureName, blobId, size, blob.type);
 
       await fetchSignatures();
     } catch (error) {
@@ -23,6 +23,8 @@ export interface SignaturePosition {
   pageNumber: number;
   signatureData: string;
   timestamp: number;
+  // ID of the stored signature this was placed from, if any
+  savedSignatureId?: string;
 }
 
 class PDFService {
@@ -0,0 +1,62 @@
+//! Blob announcement bookkeeping.
+//!
+//! Every blob announcement goes through the state's `announce_blobs`. It uses
+//! the records kept here to skip blobs that were announced moments ago. The
+//! re-announce repair path uses them to find blobs whose last announcement
+//! failed or is old.
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::Serialize;
+use calimero_storage::collections::Mergeable;
+
+use crate::{BlobId, TimestampNanos};
+
+/// Minimum time between two successful announcements of the same blob (1 minute, in nanoseconds)
+pub const ANNOUNCE_THROTTLE_NANOS: u64 = 60 * 1_000_000_000;
+
+/// Last announcement of a blob to the context - uses LWW based on last_announced_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct BlobAnnouncement {
+    pub blob_id: BlobId,
+    pub last_announced_at: TimestampNanos,
+    pub succeeded: bool,
+    pub announce_count: u64,
+}
+
+impl Mergeable for BlobAnnouncement {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on last_announced_at - newer wins
+        if other.last_announced_at > self.last_announced_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Whether a blob needs announcing, given its last recorded announcement
+pub fn is_due(previous: Option<&BlobAnnouncement>, now: u64) -> bool {
+    match previous {
+        None => true,
+        Some(previous) => {
+            !previous.succeeded
+                || now.saturating_sub(previous.last_announced_at.as_nanos())
+                    >= ANNOUNCE_THROTTLE_NANOS
+        }
+    }
+}
+
+/// Drop repeated blobs from a batch, keeping first-seen order
+pub fn dedupe(blobs: &[BlobId]) -> Vec<BlobId> {
+    let mut unique: Vec<BlobId> = Vec::with_capacity(blobs.len());
+    for blob in blobs {
+        if !unique.contains(blob) {
+            unique.push(*blob);
+        }
+    }
+    unique
+}
@@ -0,0 +1,206 @@
+//! Canonical byte encodings for hashing.
+//!
+//! Any client or canister that hashes MeroSign records has to produce the same
+//! bytes. Every encoding starts with a versioned domain tag, then the fields in
+//! a fixed order:
+//!
+//! - integers are 8-byte big-endian
+//! - strings and byte arrays are prefixed with their length as 4-byte big-endian
+//! - optional values are a `0` byte, or a `1` byte followed by the value
+//! - sets (such as participants) are sorted before encoding
+
+use sha2::{Digest, Sha256};
+
+use crate::{ContextId, DocumentInfo, DocumentSignature, ShareScope, TimestampNanos, UserId};
+
+const DOCUMENT_DOMAIN: &str = "merosign/document/v1";
+const SIGNATURE_DOMAIN: &str = "merosign/signature/v1";
+const AGREEMENT_DOMAIN: &str = "merosign/agreement/v1";
+const SHARE_DESCRIPTOR_DOMAIN: &str = "merosign/share-descriptor/v1";
+const SHARE_DESCRIPTOR_PAYLOAD_DOMAIN: &str = "merosign/share-descriptor-payload/v1";
+const SIGNING_PAYLOAD_DOMAIN: &str = "merosign/signing-payload/v1";
+const PRINCIPAL_LINK_DOMAIN: &str = "merosign/principal-link/v1";
+
+/// Builder for a canonical encoding
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn new(domain: &str) -> Self {
+        let mut encoder = Encoder { buf: Vec::new() };
+        encoder.str(domain);
+        encoder
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.buf
+            .extend_from_slice(&(value.len() as u32).to_be_bytes());
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    fn opt_bytes(&mut self, value: Option<&[u8]>) -> &mut Self {
+        match value {
+            Some(value) => {
+                self.buf.push(1);
+                self.bytes(value)
+            }
+            None => {
+                self.buf.push(0);
+                self
+            }
+        }
+    }
+
+    fn opt_u64(&mut self, value: Option<u64>) -> &mut Self {
+        match value {
+            Some(value) => {
+                self.buf.push(1);
+                self.u64(value)
+            }
+            None => {
+                self.buf.push(0);
+                self
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+/// Encode the identity of a document version: what was uploaded, by whom, and its current PDF.
+/// Mutable metadata such as status, tags or folder is left out.
+pub fn document_bytes(document: &DocumentInfo) -> Vec<u8> {
+    Encoder::new(DOCUMENT_DOMAIN)
+        .str(&document.id)
+        .str(&document.name)
+        .str(&document.hash)
+        .bytes(&document.uploaded_by)
+        .u64(document.uploaded_at.as_nanos())
+        .bytes(&document.pdf_blob_id)
+        .u64(document.size)
+        .opt_u64(document.expires_at.map(TimestampNanos::as_nanos))
+        .finish()
+}
+
+/// Encode one signature on a document
+pub fn signature_bytes(
+    document_id: &str,
+    document_hash: &str,
+    signature: &DocumentSignature,
+) -> Vec<u8> {
+    Encoder::new(SIGNATURE_DOMAIN)
+        .str(document_id)
+        .str(document_hash)
+        .bytes(&signature.signer)
+        .u64(signature.signed_at.as_nanos())
+        .bytes(&signature.signed_by)
+        .opt_bytes(signature.on_behalf_of.as_ref().map(|id| id.as_slice()))
+        .str(&signature.signed_hash)
+        .opt_bytes(signature.signature.as_deref())
+        .finish()
+}
+
+/// Message a signer signs with their ed25519 identity key to attest a document hash
+pub fn signing_payload(context_id: &ContextId, document_id: &str, document_hash: &str) -> Vec<u8> {
+    Encoder::new(SIGNING_PAYLOAD_DOMAIN)
+        .bytes(context_id)
+        .str(document_id)
+        .str(document_hash)
+        .finish()
+}
+
+/// Message the creator of a share descriptor signs with their ed25519 identity key. It
+/// covers the terms the creator chooses; gateways check it against `created_by`.
+pub fn share_descriptor_payload(
+    context_id: &ContextId,
+    document_id: &str,
+    document_hash: &str,
+    scope: &ShareScope,
+    created_by: &UserId,
+    expires_at: u64,
+) -> Vec<u8> {
+    Encoder::new(SHARE_DESCRIPTOR_PAYLOAD_DOMAIN)
+        .bytes(context_id)
+        .str(document_id)
+        .str(document_hash)
+        .str(share_scope_label(scope))
+        .bytes(created_by)
+        .u64(expires_at)
+        .finish()
+}
+
+/// Message the key behind an IC principal signs to let a participant link that principal
+pub fn principal_link_payload(
+    context_id: &ContextId,
+    user_id: &UserId,
+    principal: &str,
+) -> Vec<u8> {
+    Encoder::new(PRINCIPAL_LINK_DOMAIN)
+        .bytes(context_id)
+        .bytes(user_id)
+        .str(principal)
+        .finish()
+}
+
+/// Encode an agreement: the shared context, its name and its participants
+pub fn agreement_bytes(context_id: &ContextId, name: &str, participants: &[UserId]) -> Vec<u8> {
+    let mut participants = participants.to_vec();
+    participants.sort_unstable();
+    participants.dedup();
+
+    let mut encoder = Encoder::new(AGREEMENT_DOMAIN);
+    encoder
+        .bytes(context_id)
+        .str(name)
+        .u64(participants.len() as u64);
+    for participant in &participants {
+        encoder.bytes(participant);
+    }
+    encoder.finish()
+}
+
+/// Encode the terms of an external share descriptor
+pub fn share_descriptor_bytes(
+    context_id: &ContextId,
+    document_id: &str,
+    document_hash: &str,
+    scope: &ShareScope,
+    created_by: &UserId,
+    created_at: TimestampNanos,
+    expires_at: u64,
+) -> Vec<u8> {
+    Encoder::new(SHARE_DESCRIPTOR_DOMAIN)
+        .bytes(context_id)
+        .str(document_id)
+        .str(document_hash)
+        .str(share_scope_label(scope))
+        .bytes(created_by)
+        .u64(created_at.as_nanos())
+        .u64(expires_at)
+        .finish()
+}
+
+fn share_scope_label(scope: &ShareScope) -> &'static str {
+    match scope {
+        ShareScope::Verification => "verification",
+        ShareScope::ReadOnly => "read_only",
+    }
+}
+
+/// Hex-encoded SHA-256 of a canonical encoding
+pub fn digest(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
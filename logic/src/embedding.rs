@@ -0,0 +1,104 @@
+//! Stored embedding vectors.
+//!
+//! Embeddings arrive from clients as `f32` arrays. When the
+//! `quantized_embeddings` feature is on, they are stored as int8 values with
+//! one scale per vector, a quarter of the size. Cosine similarity is
+//! scale-invariant, so quantized vectors are compared without dequantizing them
+//! first.
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::{Deserialize, Serialize};
+
+/// Int8 embedding; component `i` is `values[i] as f32 * scale`
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct QuantizedEmbedding {
+    pub scale: f32,
+    pub values: Vec<i8>,
+}
+
+/// Embedding as stored in state. Serialized untagged, so clients keep sending plain arrays.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde", untagged)]
+pub enum Embedding {
+    Float(Vec<f32>),
+    Quantized(QuantizedEmbedding),
+}
+
+impl Embedding {
+    /// Number of dimensions
+    pub fn len(&self) -> usize {
+        match self {
+            Embedding::Float(values) => values.len(),
+            Embedding::Quantized(quantized) => quantized.values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_quantized(&self) -> bool {
+        matches!(self, Embedding::Quantized(_))
+    }
+
+    /// Convert to int8 with a symmetric per-vector scale; quantized embeddings are returned as-is
+    pub fn quantize(self) -> Embedding {
+        let Embedding::Float(values) = self else {
+            return self;
+        };
+
+        let max_abs = values
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold(0.0f32, |max, v| max.max(v.abs()));
+        if max_abs == 0.0 {
+            return Embedding::Quantized(QuantizedEmbedding {
+                scale: 1.0,
+                values: vec![0; values.len()],
+            });
+        }
+
+        let scale = max_abs / i8::MAX as f32;
+        let values = values
+            .iter()
+            .map(|v| {
+                if v.is_finite() {
+                    (v / scale).round().clamp(-(i8::MAX as f32), i8::MAX as f32) as i8
+                } else {
+                    0
+                }
+            })
+            .collect();
+
+        Embedding::Quantized(QuantizedEmbedding { scale, values })
+    }
+}
+
+/// Cosine similarity between a query and a stored embedding of the same dimension
+pub fn cosine_similarity(query: &[f32], stored: &Embedding) -> f32 {
+    match stored {
+        Embedding::Float(values) => cosine(query.iter().copied(), values.iter().copied()),
+        // The scale cancels out of the cosine, so the raw int8 values are enough
+        Embedding::Quantized(quantized) => cosine(
+            query.iter().copied(),
+            quantized.values.iter().map(|&v| v as f32),
+        ),
+    }
+}
+
+fn cosine(a: impl Iterator<Item = f32>, b: impl Iterator<Item = f32>) -> f32 {
+    let (mut dot_product, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.zip(b) {
+        dot_product += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot_product / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
@@ -0,0 +1,107 @@
+//! Errors returned by the application.
+//!
+//! Every method fails with a `MeroDocsError`. Clients branch on the `kind`
+//! tag instead of matching message text; `message` stays human-readable and
+//! is what `Display` prints.
+
+use std::fmt;
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde", tag = "kind", content = "message")]
+pub enum MeroDocsError {
+    /// The document, user, folder or other entity does not exist
+    NotFound(String),
+    /// The caller lacks the permission, capability or role for the operation
+    Unauthorized(String),
+    /// The entity or relationship being created already exists
+    AlreadyExists(String),
+    /// The user has not consented to the document
+    ConsentRequired(String),
+    /// A blob ID could not be decoded
+    InvalidBlobId(String),
+    /// An argument is malformed or out of range
+    InvalidInput(String),
+    /// The operation is not allowed in the entity's current state
+    InvalidState(String),
+    /// The method is not available in this kind of context (private or shared)
+    WrongContext(String),
+    /// The feature the method needs is switched off in this context
+    FeatureDisabled(String),
+    /// A configured limit would be exceeded
+    LimitExceeded(String),
+    /// The context is archived and read-only
+    ContextArchived,
+    /// Reading or writing state failed
+    StorageError(String),
+}
+
+impl MeroDocsError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        MeroDocsError::NotFound(message.into())
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        MeroDocsError::Unauthorized(message.into())
+    }
+
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        MeroDocsError::AlreadyExists(message.into())
+    }
+
+    pub fn consent_required(message: impl Into<String>) -> Self {
+        MeroDocsError::ConsentRequired(message.into())
+    }
+
+    pub fn invalid_blob_id(message: impl Into<String>) -> Self {
+        MeroDocsError::InvalidBlobId(message.into())
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        MeroDocsError::InvalidInput(message.into())
+    }
+
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        MeroDocsError::InvalidState(message.into())
+    }
+
+    pub fn wrong_context(message: impl Into<String>) -> Self {
+        MeroDocsError::WrongContext(message.into())
+    }
+
+    pub fn feature_disabled(message: impl Into<String>) -> Self {
+        MeroDocsError::FeatureDisabled(message.into())
+    }
+
+    pub fn limit_exceeded(message: impl Into<String>) -> Self {
+        MeroDocsError::LimitExceeded(message.into())
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        MeroDocsError::StorageError(message.into())
+    }
+}
+
+impl fmt::Display for MeroDocsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeroDocsError::NotFound(message)
+            | MeroDocsError::Unauthorized(message)
+            | MeroDocsError::AlreadyExists(message)
+            | MeroDocsError::ConsentRequired(message)
+            | MeroDocsError::InvalidBlobId(message)
+            | MeroDocsError::InvalidInput(message)
+            | MeroDocsError::InvalidState(message)
+            | MeroDocsError::WrongContext(message)
+            | MeroDocsError::FeatureDisabled(message)
+            | MeroDocsError::LimitExceeded(message)
+            | MeroDocsError::StorageError(message) => f.write_str(message),
+            MeroDocsError::ContextArchived => f.write_str("This context is archived and read-only"),
+        }
+    }
+}
+
+impl std::error::Error for MeroDocsError {}
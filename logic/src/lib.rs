@@ -1,14 +1,199 @@
 #![allow(clippy::len_without_is_empty)]
 
+use std::collections::BTreeMap;
+
 use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use calimero_sdk::serde::{Deserialize, Serialize};
 use calimero_sdk::{app, env, PublicKey};
 use calimero_storage::collections::{LwwRegister, Mergeable, UnorderedMap, UnorderedSet, Vector};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+mod announcements;
+mod canonical;
+mod embedding;
+mod error;
+mod lifecycle;
+mod migration;
+mod principal;
+mod roles;
+mod search;
+mod time;
+
+pub use announcements::BlobAnnouncement;
+use embedding::cosine_similarity;
+pub use embedding::{Embedding, QuantizedEmbedding};
+pub use error::MeroDocsError;
+use lifecycle::StatusTrigger;
+use roles::Capabilities;
+pub use roles::{Role, RoleAssignment, RoleInfo};
+pub use time::TimestampNanos;
 
 pub type UserId = [u8; 32];
 pub type BlobId = [u8; 32];
 pub type ContextId = [u8; 32];
 
+/// Default maximum size of a stored signature image (1 MiB)
+const DEFAULT_SIGNATURE_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Default number of documents one signature may be applied to per window before alerting
+const DEFAULT_SIGNATURE_USE_MAX_DOCUMENTS: u64 = 20;
+
+/// Default window for counting signature applications (1 hour, in nanoseconds)
+const DEFAULT_SIGNATURE_USE_WINDOW_NANOS: u64 = 60 * 60 * 1_000_000_000;
+
+/// Time a recovery identity must wait before taking over a private context (7 days, in nanoseconds)
+const RECOVERY_TIMELOCK_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Default number of audit entries returned per export chunk
+const DEFAULT_AUDIT_EXPORT_LIMIT: u64 = 500;
+
+/// Default number of items returned per page by list queries
+const DEFAULT_PAGE_LIMIT: u64 = 100;
+
+/// Default number of documents returned by keyword search
+const DEFAULT_KEYWORD_SEARCH_LIMIT: u64 = 10;
+
+/// Highlighted snippets returned per keyword search hit
+const KEYWORD_SNIPPETS_PER_HIT: usize = 3;
+
+/// Most chunks compared in one cross-document semantic search, to keep gas bounded
+const MAX_CONTEXT_SEARCH_CHUNKS: u64 = 5_000;
+
+/// Most results returned by one cross-document semantic search
+const MAX_CONTEXT_SEARCH_TOP_K: u64 = 50;
+
+/// Default number of neighbouring chunks stitched onto each side of a search hit
+const DEFAULT_CHUNK_CONTEXT_WINDOW: u64 = 1;
+
+/// Largest allowed neighbouring-chunk window
+const MAX_CHUNK_CONTEXT_WINDOW: u64 = 5;
+
+/// Default number of documents converted per `quantize_existing_embeddings` call
+const DEFAULT_QUANTIZATION_BATCH: u64 = 50;
+
+/// Most pages a document's page geometry can describe
+const MAX_DOCUMENT_PAGES: usize = 10_000;
+
+/// Approximate serialized size of each state collection above which admins are warned, in bytes
+const RESOURCE_WARNING_THRESHOLDS: &[(&str, u64)] = &[
+    ("documents", 4 * 1024 * 1024),
+    ("chunks", 32 * 1024 * 1024),
+    ("document_signatures", 4 * 1024 * 1024),
+    ("document_versions", 4 * 1024 * 1024),
+    ("document_access_log", 4 * 1024 * 1024),
+    ("keyword_index", 16 * 1024 * 1024),
+    ("audit_log", 8 * 1024 * 1024),
+    ("signatures", 1024 * 1024),
+    ("agreements", 1024 * 1024),
+];
+
+/// Most documents one recurring schedule generates per run when several periods
+/// were missed
+const MAX_SCHEDULE_CATCH_UP_RUNS: u64 = 12;
+
+/// Longest message allowed on a join request, in characters
+const MAX_JOIN_REQUEST_MESSAGE_CHARS: usize = 500;
+
+/// Longest context description, in characters
+const MAX_CONTEXT_DESCRIPTION_CHARS: usize = 2_000;
+
+/// Longest deal reference or jurisdiction on a context profile, in characters
+const MAX_CONTEXT_PROFILE_FIELD_CHARS: usize = 200;
+
+/// Characters of the issuer's identity appended to a document number
+const DOCUMENT_NUMBER_ISSUER_CHARS: usize = 8;
+
+/// Longest document summary, in characters
+const MAX_DOCUMENT_SUMMARY_CHARS: usize = 4_000;
+
+/// Longest question in a recorded Q&A exchange, in characters
+const MAX_QA_QUESTION_CHARS: usize = 2_000;
+
+/// Longest answer in a recorded Q&A exchange, in characters
+const MAX_QA_ANSWER_CHARS: usize = 8_000;
+
+/// One page of a paginated listing; `next_cursor` is the offset of the next page
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next_cursor: Option<u64>,
+}
+
+/// One batch of the change log; `next_cursor` is the id of the last change scanned
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ChangeLogPage {
+    pub items: Vec<ChangeEntry>,
+    pub total: u64,
+    pub next_cursor: Option<String>,
+}
+
+/// Features that can be switched per context, with their default state
+const FEATURES: &[(&str, bool)] = &[
+    ("sequential_signing", true),
+    ("signing_delegation", true),
+    ("signature_fields", true),
+    ("document_expiry", true),
+    ("require_signature_payloads", false),
+    ("quantized_embeddings", false),
+    ("discoverable", false),
+];
+
+/// Approximate size of one state collection
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct CollectionUsage {
+    pub name: String,
+    pub entries: u64,
+    pub approx_bytes: u64,
+    pub warning_bytes: u64,
+    pub exceeded: bool,
+}
+
+/// Approximate state size per collection, to spot contexts growing too heavy to sync
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ResourceReport {
+    pub collections: Vec<CollectionUsage>,
+    pub total_bytes: u64,
+    pub generated_at: TimestampNanos,
+}
+
+/// Whether a feature is enabled in a context
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct FeatureStatus {
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// How a stored signature was produced
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum SignatureKind {
+    Drawn,
+    Typed,
+    Uploaded,
+    Initials,
+}
+
+/// Sort order for listing stored signatures
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum SignatureSortField {
+    CreatedAt,
+    Name,
+}
+
 /// Signature record - uses LWW based on created_at timestamp
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
@@ -18,7 +203,9 @@ pub struct SignatureRecord {
     pub name: String,
     pub blob_id: BlobId,
     pub size: u64,
-    pub created_at: u64,
+    pub format: Option<String>,
+    pub kind: SignatureKind,
+    pub created_at: TimestampNanos,
 }
 
 impl Mergeable for SignatureRecord {
@@ -34,13 +221,138 @@ impl Mergeable for SignatureRecord {
     }
 }
 
+/// Record kept after a signature is deleted so historical references stay resolvable
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureTombstone {
+    pub id: u64,
+    pub name_hash: String,
+    pub created_at: TimestampNanos,
+    pub deleted_at: TimestampNanos,
+}
+
+impl Mergeable for SignatureTombstone {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on deleted_at - newer wins
+        if other.deleted_at > self.deleted_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Limits applied to signature uploads in the private context
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureLimits {
+    pub max_bytes: u64,
+    pub allowed_formats: Vec<String>,
+}
+
+impl Default for SignatureLimits {
+    fn default() -> Self {
+        SignatureLimits {
+            max_bytes: DEFAULT_SIGNATURE_MAX_BYTES,
+            allowed_formats: vec![
+                "image/png".to_string(),
+                "image/jpeg".to_string(),
+                "image/svg+xml".to_string(),
+                "image/webp".to_string(),
+            ],
+        }
+    }
+}
+
+/// Threshold for how many documents a signature may be applied to within a sliding window
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureUseLimits {
+    pub max_documents: u64,
+    pub window_nanos: u64,
+}
+
+impl Default for SignatureUseLimits {
+    fn default() -> Self {
+        SignatureUseLimits {
+            max_documents: DEFAULT_SIGNATURE_USE_MAX_DOCUMENTS,
+            window_nanos: DEFAULT_SIGNATURE_USE_WINDOW_NANOS,
+        }
+    }
+}
+
+/// Document seen in a joined shared context, indexed by hash in the private context -
+/// uses LWW based on indexed_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct IndexedDocument {
+    pub context_id: String,
+    pub document_id: String,
+    pub name: String,
+    pub indexed_at: TimestampNanos,
+}
+
+impl Mergeable for IndexedDocument {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.indexed_at > self.indexed_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A joined context holding a document with a given hash
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentContextMatch {
+    pub context_id: String,
+    pub context_name: String,
+    pub document_id: String,
+    pub name: String,
+    pub indexed_at: TimestampNanos,
+}
+
+/// One application of a stored signature to a document - uses LWW based on applied_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureUse {
+    pub signature_id: u64,
+    pub context_id: String,
+    pub document_id: String,
+    pub applied_at: TimestampNanos,
+}
+
+impl Mergeable for SignatureUse {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on applied_at - newer wins
+        if other.applied_at > self.applied_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
 pub struct ContextAgreement {
     pub context_id: ContextId,
     pub agreement_name: String,
-    pub joined_at: u64,
+    pub joined_at: TimestampNanos,
 }
 
 /// Participant roles in shared contexts
@@ -85,1049 +397,10677 @@ impl Mergeable for ParticipantRole {
 #[serde(crate = "calimero_sdk::serde")]
 pub struct DocumentChunk {
     pub text: String,
-    pub embedding: Vec<f32>,
+    pub embedding: Embedding,
     pub start_position: usize,
     pub end_position: usize,
 }
 
-/// Document information - uses LWW based on uploaded_at timestamp
+impl Mergeable for DocumentChunk {
+    fn merge(
+        &mut self,
+        _other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Chunks are written once per upload or re-index and replaced as a whole
+        Ok(())
+    }
+}
+
+/// Placement of a signature field as supplied by the client
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureFieldSpec {
+    pub page: u32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub assigned_signer: Option<String>,
+}
+
+/// Optional settings for `upload_document`; anything left out is not applied
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde", default)]
+pub struct UploadOptions {
+    /// Signers who must sign one after another, in this order
+    pub signing_order: Option<Vec<String>>,
+    /// Signers who must sign before the document is fully signed, instead of every
+    /// participant allowed to sign
+    pub required_signers: Option<Vec<String>>,
+    /// Time after which the document can no longer be signed, in nanoseconds
+    pub expires_at: Option<TimestampNanos>,
+    pub signature_fields: Option<Vec<SignatureFieldSpec>>,
+    pub page_count: Option<u32>,
+    pub page_sizes: Option<Vec<PageSize>>,
+    /// Witness signatures needed, in addition to the parties, to complete the document
+    pub required_witnesses: Option<u32>,
+}
+
+/// Named placeholder in a template, filled in when the template is instantiated
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MergeField {
+    pub name: String,
+    pub required: bool,
+}
+
+/// Value given to a merge field
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MergeFieldValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// Reusable document with merge fields - uses LWW based on updated_at
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct DocumentInfo {
+pub struct DocumentTemplate {
     pub id: String,
     pub name: String,
-    pub hash: String,
-    pub uploaded_by: UserId,
-    pub uploaded_at: u64,
-    pub status: DocumentStatus,
     pub pdf_blob_id: BlobId,
+    pub hash: String,
     pub size: u64,
-    pub embeddings: Option<Vec<f32>>,
-    pub extracted_text: Option<String>,
-    pub chunks: Option<Vec<DocumentChunk>>,
+    pub page_count: Option<u32>,
+    pub merge_fields: Vec<MergeField>,
+    pub created_by: UserId,
+    pub updated_at: TimestampNanos,
 }
 
-impl Mergeable for DocumentInfo {
+impl Mergeable for DocumentTemplate {
     fn merge(
         &mut self,
         other: &Self,
     ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
-        // LWW based on uploaded_at - newer wins
-        if other.uploaded_at > self.uploaded_at {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
             *self = other.clone();
         }
         Ok(())
     }
 }
 
-/// Document status tracking
-#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
+/// How often a recurring schedule fires, parsed from a cron-style macro
+/// (`@daily`, `@weekly`, `@monthly`)
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub enum DocumentStatus {
-    Pending,
-    PartiallySigned,
-    FullySigned,
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    /// Same day of the month as the first run, clamped to shorter months' last day
+    Monthly,
 }
 
-/// Signature record for documents - uses LWW based on signed_at timestamp
+impl Recurrence {
+    fn parse(spec: &str) -> Result<Self, MeroDocsError> {
+        match spec.trim().trim_start_matches('@').to_lowercase().as_str() {
+            "daily" => Ok(Recurrence::Daily),
+            "weekly" => Ok(Recurrence::Weekly),
+            "monthly" => Ok(Recurrence::Monthly),
+            _ => Err(MeroDocsError::invalid_input(format!(
+                "Unsupported schedule '{}': use @daily, @weekly or @monthly",
+                spec
+            ))),
+        }
+    }
+
+    /// The run `periods` periods after the first one at `starts_at`. Each run is computed
+    /// from the first, so a monthly schedule starting on the 31st returns to the 31st
+    /// after a shorter month.
+    fn nth_run(self, starts_at: TimestampNanos, periods: u64) -> TimestampNanos {
+        const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+        match self {
+            Recurrence::Daily => starts_at.plus_nanos(periods.saturating_mul(NANOS_PER_DAY)),
+            Recurrence::Weekly => starts_at.plus_nanos(periods.saturating_mul(7 * NANOS_PER_DAY)),
+            Recurrence::Monthly => starts_at.plus_months(periods),
+        }
+    }
+}
+
+/// Template instantiated for the same recipients on every period - uses LWW based on
+/// updated_at
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct DocumentSignature {
-    pub signer: UserId,
-    pub signed_at: u64,
+pub struct RecurringSchedule {
+    pub id: String,
+    pub template_id: String,
+    pub recurrence: Recurrence,
+    /// Required signers of each generated document
+    pub recipients: Vec<UserId>,
+    pub field_values: Vec<MergeFieldValue>,
+    /// First run; every later run is computed from it
+    pub starts_at: TimestampNanos,
+    /// Periods since `starts_at` that have come due, whether generated or skipped
+    pub periods_elapsed: u64,
+    pub next_run_at: TimestampNanos,
+    pub last_run_at: Option<TimestampNanos>,
+    pub documents_generated: u64,
+    pub created_by: UserId,
+    pub updated_at: TimestampNanos,
 }
 
-impl Mergeable for DocumentSignature {
+impl Mergeable for RecurringSchedule {
     fn merge(
         &mut self,
         other: &Self,
     ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
-        // LWW based on signed_at - newer wins
-        if other.signed_at > self.signed_at {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
             *self = other.clone();
         }
         Ok(())
     }
 }
 
-/// Permission levels for participants
+/// Template a document was created from and the values its merge fields were given.
+/// The template hash identifies the template across contexts.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct TemplateProvenance {
+    pub template_id: String,
+    pub template_hash: String,
+    pub field_values: Vec<MergeFieldValue>,
+}
+
+/// Size of one PDF page in points, as reported by the client's PDF renderer
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub enum PermissionLevel {
-    Read,
-    Sign,
-    Admin,
+pub struct PageSize {
+    pub width: f32,
+    pub height: f32,
 }
 
-impl Mergeable for PermissionLevel {
-    fn merge(
-        &mut self,
-        other: &Self,
-    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
-        // Take higher permission (Admin > Sign > Read)
-        let self_priority = match self {
-            PermissionLevel::Admin => 2,
-            PermissionLevel::Sign => 1,
-            PermissionLevel::Read => 0,
-        };
-        let other_priority = match other {
-            PermissionLevel::Admin => 2,
-            PermissionLevel::Sign => 1,
-            PermissionLevel::Read => 0,
-        };
-        if other_priority > self_priority {
-            *self = other.clone();
-        }
-        Ok(())
-    }
+/// Named area of a PDF page where a signature goes
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignatureField {
+    pub id: String,
+    pub page: u32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub assigned_signer: Option<UserId>,
+    pub filled_by: Option<UserId>,
+    pub filled_at: Option<TimestampNanos>,
 }
 
-/// Metadata for tracking joined shared contexts
+/// Embedding model that a context's chunks are produced with
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct ContextMetadata {
-    pub context_id: ContextId,
-    pub context_name: String,
-    pub role: ParticipantRole,
-    pub joined_at: u64,
-    pub private_identity: UserId,
-    pub shared_identity: UserId,
+pub struct EmbeddingModel {
+    pub name: String,
+    pub dimension: u64,
+    pub set_by: UserId,
+    pub set_at: TimestampNanos,
 }
 
-impl Mergeable for ContextMetadata {
-    fn merge(
-        &mut self,
-        other: &Self,
-    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
-        // LWW based on joined_at - newer wins
-        if other.joined_at > self.joined_at {
-            *self = other.clone();
-        }
-        Ok(())
-    }
+/// In-progress migration of a context's chunks to a new embedding model
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ReindexJob {
+    pub target_model: String,
+    pub target_dimension: u64,
+    pub previous_model: Option<EmbeddingModel>,
+    pub started_by: UserId,
+    pub started_at: TimestampNanos,
 }
 
-/// Identity mapping for tracking user identities across contexts
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+/// Per-document progress of a re-index
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct IdentityMapping {
-    pub private_identity: UserId,
-    pub shared_identity: UserId,
-    pub context_id: ContextId,
-    pub created_at: u64,
+pub enum ReindexStatus {
+    Stale,
+    Reindexed,
 }
 
-impl Mergeable for IdentityMapping {
+impl Mergeable for ReindexStatus {
     fn merge(
         &mut self,
         other: &Self,
     ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
-        // LWW based on created_at - newer wins
-        if other.created_at > self.created_at {
-            *self = other.clone();
+        // Once re-indexed on any node, the document stays re-indexed
+        if *other == ReindexStatus::Reindexed {
+            *self = ReindexStatus::Reindexed;
         }
         Ok(())
     }
 }
 
-/// Participant information with permission level
+/// Summary of re-index progress across the context's documents
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct ParticipantInfo {
-    pub user_id: UserId,
-    pub permission_level: PermissionLevel,
+pub struct ReindexProgress {
+    pub job: ReindexJob,
+    pub total_documents: u64,
+    pub reindexed_documents: u64,
+    pub stale_document_ids: Vec<String>,
 }
 
-/// Detailed information about a shared context
+/// LLM-produced document summary shared with all participants
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
 #[serde(crate = "calimero_sdk::serde")]
-pub struct ContextDetails {
-    pub context_id: ContextId,
-    pub context_name: String,
-    pub owner: UserId,
-    pub is_private: bool,
-    pub participant_count: u64,
-    pub participants: Vec<ParticipantInfo>,
-    pub document_count: u64,
-    pub created_at: u64,
+pub struct DocumentSummary {
+    pub text: String,
+    pub generated_by: String,
+    pub set_by: UserId,
+    pub set_at: TimestampNanos,
 }
 
-#[app::state(emits = MeroSignEvent)]
-#[derive(BorshDeserialize, BorshSerialize)]
+/// Per-context scheme for human-readable document numbers, e.g. "ACME-NDA-0042-7Hq3kP2x".
+/// The suffix is the start of the issuing member's identity.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[borsh(crate = "calimero_sdk::borsh")]
-pub struct MeroSignState {
-    // Context type flag
-    pub is_private: LwwRegister<bool>,
+#[serde(crate = "calimero_sdk::serde")]
+pub struct NumberingScheme {
+    pub prefix: String,
+    pub padding: u32,
+}
 
-    pub owner: LwwRegister<UserId>,
-    pub context_name: LwwRegister<String>,
+/// Ordered chain of required signers for a document
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SigningOrder {
+    pub signers: Vec<UserId>,
+}
 
-    // Private context data
-    pub signatures: UnorderedMap<String, SignatureRecord>,
-    pub joined_contexts: UnorderedMap<String, ContextMetadata>,
-    pub identity_mappings: UnorderedMap<String, IdentityMapping>,
-    pub signature_count: LwwRegister<u64>,
+/// Deadline by which a required signer has to sign
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SignerDueDate {
+    pub signer: UserId,
+    pub due_at: TimestampNanos,
+}
 
-    // Shared context data
-    pub participants: UnorderedSet<UserId>,
-    pub documents: UnorderedMap<String, DocumentInfo>,
-    pub document_signatures: UnorderedMap<String, Vector<DocumentSignature>>,
-    pub permissions: UnorderedMap<UserId, PermissionLevel>,
-    pub consents: UnorderedMap<String, LwwRegister<bool>>,
+/// Per-document permission for one user, overriding their context permission
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentAccess {
+    pub user_id: UserId,
+    pub permission: PermissionLevel,
+    pub granted_by: UserId,
+    pub granted_at: TimestampNanos,
 }
 
-#[app::event]
-pub enum MeroSignEvent {
-    // Private context events
-    SignatureCreated {
-        id: u64,
-        name: String,
-        size: u64,
-    },
-    SignatureDeleted {
-        id: u64,
-    },
-    ContextJoined {
-        context_id: String,
-        context_name: String,
-    },
-    ContextLeft {
-        context_id: String,
-    },
+/// Document information - uses LWW based on uploaded_at timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentInfo {
+    pub id: String,
+    pub name: String,
+    pub hash: String,
+    pub uploaded_by: UserId,
+    pub uploaded_at: TimestampNanos,
+    pub status: DocumentStatus,
+    pub pdf_blob_id: BlobId,
+    pub size: u64,
+    pub embeddings: Option<Embedding>,
+    pub extracted_text: Option<String>,
+    pub summary: Option<DocumentSummary>,
+    pub first_signed_at: Option<TimestampNanos>,
+    pub completed_at: Option<TimestampNanos>,
+    pub document_number: Option<String>,
+    pub requires_fully_signed: Option<String>,
+    pub signing_order: Option<SigningOrder>,
+    pub required_signers: Option<Vec<UserId>>,
+    pub expires_at: Option<TimestampNanos>,
+    pub signature_fields: Vec<SignatureField>,
+    pub tags: Vec<String>,
+    pub folder_id: Option<String>,
+    pub signer_due_dates: Vec<SignerDueDate>,
+    pub access_list: Vec<DocumentAccess>,
+    pub page_count: Option<u32>,
+    /// Size of each page, first page first; empty when unknown
+    pub page_sizes: Vec<PageSize>,
+    /// Organization the document is sent on behalf of
+    pub organization_id: Option<String>,
+    /// Key epoch the current PDF blob was encrypted under
+    pub key_epoch: u64,
+    /// Set when the document was created from a template
+    pub template: Option<TemplateProvenance>,
+    /// Witness signatures needed on top of the required signers before the document is
+    /// fully signed
+    pub required_witnesses: u32,
+}
 
-    // Shared context events
-    DocumentUploaded {
-        id: String,
-        name: String,
-        uploaded_by: UserId,
-    },
-    DocumentDeleted {
-        id: String,
-    },
-    DocumentSigned {
-        document_id: String,
-        signer: UserId,
-    },
-    ParticipantInvited {
-        user_id: UserId,
-        role: ParticipantRole,
-    },
-    ParticipantJoined {
-        user_id: UserId,
-    },
-    ParticipantLeft {
-        user_id: UserId,
-    },
+impl Mergeable for DocumentInfo {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on uploaded_at - newer wins
+        if other.uploaded_at > self.uploaded_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
 }
 
-/// Helper to decode base58 blob_id from API input
-fn parse_blob_id_base58(blob_id_str: &str) -> Result<BlobId, String> {
-    match bs58::decode(blob_id_str).into_vec() {
-        Ok(bytes) => {
-            if bytes.len() != 32 {
-                return Err(format!(
-                    "Invalid blob ID length: expected 32 bytes, got {}",
-                    bytes.len()
-                ));
-            }
-            let mut blob_id = [0u8; 32];
-            blob_id.copy_from_slice(&bytes);
-            Ok(blob_id)
+/// Document status tracking
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum DocumentStatus {
+    Pending,
+    PartiallySigned,
+    FullySigned,
+    Expired,
+    Declined,
+    /// Withdrawn by the uploader or an admin before completion; kept for the record
+    Voided,
+}
+
+/// Capacity a signature was given in
+#[derive(
+    Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum SignatureCapacity {
+    /// A party to the document
+    Party,
+    /// Attests that a party signed
+    Witness,
+    /// Notarizes the execution
+    Notary,
+}
+
+/// Signature record for documents - uses LWW based on signed_at timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentSignature {
+    /// Identity the signature counts for
+    pub signer: UserId,
+    pub signed_at: TimestampNanos,
+    /// Identity that actually signed; differs from `signer` under a delegation
+    pub signed_by: UserId,
+    pub on_behalf_of: Option<UserId>,
+    /// Document hash the signature was made over
+    pub signed_hash: String,
+    /// ed25519 signature by `signed_by` (an identity public key) over the canonical signing payload
+    pub signature: Option<Vec<u8>>,
+    /// Organization `signer` signed for
+    pub organization_id: Option<String>,
+    pub capacity: SignatureCapacity,
+}
+
+impl Mergeable for DocumentSignature {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on signed_at - newer wins
+        if other.signed_at > self.signed_at {
+            *self = other.clone();
         }
-        Err(e) => Err(format!("Failed to decode blob ID '{}': {}", blob_id_str, e)),
+        Ok(())
     }
 }
 
-/// Helper to decode base58 public key from API input
-fn parse_public_key_base58(key_str: &str) -> Result<UserId, String> {
-    key_str
-        .parse::<PublicKey>()
-        .map(|pk| *pk.as_ref())
-        .map_err(|e| format!("Failed to parse public key '{}': {}", key_str, e))
+/// Authority granted by a required signer to another participant to sign a document for them
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SigningDelegation {
+    pub document_id: String,
+    pub delegator: UserId,
+    pub delegate: UserId,
+    pub created_at: TimestampNanos,
+    pub expires_at: TimestampNanos,
+    pub revoked_at: Option<TimestampNanos>,
 }
 
-/// Helper to decode base58 context ID from API input
-fn parse_context_id_base58(context_id_str: &str) -> Result<ContextId, String> {
-    match bs58::decode(context_id_str).into_vec() {
-        Ok(bytes) => {
-            if bytes.len() != 32 {
-                return Err(format!(
-                    "Invalid context ID length: expected 32 bytes, got {}",
-                    bytes.len()
+impl SigningDelegation {
+    fn is_active(&self, now: u64) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at.as_nanos()
+    }
+}
+
+impl Mergeable for SigningDelegation {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Revocation of the same grant wins; otherwise the newer grant wins
+        if other.created_at > self.created_at
+            || (other.created_at == self.created_at
+                && self.revoked_at.is_none()
+                && other.revoked_at.is_some())
+        {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Documents that must be executed together as one signing packet - uses LWW based
+/// on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Envelope {
+    pub id: String,
+    pub name: String,
+    pub document_ids: Vec<String>,
+    pub created_by: UserId,
+    pub created_at: TimestampNanos,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for Envelope {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Signed PDF for one document of an envelope, as supplied by the client
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct EnvelopeDocumentSignature {
+    pub document_id: String,
+    pub pdf_blob_id_str: String,
+    pub file_size: u64,
+    pub new_hash: String,
+    pub field_ids: Option<Vec<String>>,
+    pub signature_hex: Option<String>,
+    pub page_count: Option<u32>,
+}
+
+/// Signed PDF submitted along with a signature
+struct SignedPdf {
+    pdf_blob_id_str: String,
+    file_size: u64,
+    new_hash: String,
+    field_ids: Option<Vec<String>>,
+    signature_hex: Option<String>,
+    page_count: Option<u32>,
+    organization_id: Option<String>,
+    capacity: SignatureCapacity,
+}
+
+/// One consent decision by a participant on a document - uses LWW based on recorded_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ConsentRecord {
+    pub user_id: UserId,
+    pub document_id: String,
+    pub given: bool,
+    /// Hash of the consent/disclosure text version shown to the user
+    pub consent_text_hash: Option<String>,
+    pub recorded_at: TimestampNanos,
+}
+
+impl Mergeable for ConsentRecord {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on recorded_at - newer wins
+        if other.recorded_at > self.recorded_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Result of verifying a document hash without access to the document itself
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PublicVerification {
+    pub matches: bool,
+    pub status: DocumentStatus,
+    pub completed_at: Option<TimestampNanos>,
+}
+
+/// A signature in an evidence package, with what a verifier needs to check it offline
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct EvidenceSignature {
+    pub signature: DocumentSignature,
+    /// Canonical digest of the signature record
+    pub digest: String,
+    /// Hex of the message the attestation signs
+    pub signing_payload_hex: String,
+    /// Hex of the ed25519 attestation, if the signer supplied one
+    pub signature_hex: Option<String>,
+}
+
+/// Everything recorded about one document, gathered for export as a dispute bundle.
+///
+/// The registry anchor receipt is held by the registry canister, not this context;
+/// `document_digest` is the value anchored there, so clients look the receipt up by it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct EvidencePackage {
+    pub context_id: ContextId,
+    pub agreement_digest: String,
+    pub document_id: String,
+    pub name: String,
+    pub document_number: Option<String>,
+    pub status: DocumentStatus,
+    pub completed_at: Option<TimestampNanos>,
+    pub final_blob_id: String,
+    pub final_hash: String,
+    pub document_digest: String,
+    /// Version history, oldest first
+    pub versions: Vec<DocumentVersion>,
+    pub signatures: Vec<EvidenceSignature>,
+    pub consents: Vec<ConsentRecord>,
+    /// Audit entries about this document, in sequence order
+    pub audit_entries: Vec<AuditEntry>,
+    pub access_events: Vec<DocumentAccessEvent>,
+    pub generated_by: UserId,
+    pub generated_at: TimestampNanos,
+}
+
+/// Snapshot of a document's PDF at one point in its history - uses LWW based on created_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentVersion {
+    pub version: u64,
+    pub pdf_blob_id: BlobId,
+    pub hash: String,
+    pub size: u64,
+    pub author: UserId,
+    pub created_at: TimestampNanos,
+    pub reason: String,
+    pub page_count: Option<u32>,
+    pub chunk_hashes: Vec<String>,
+}
+
+/// A participant muting reminders for a document until a point in time - uses LWW based
+/// on set_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentSnooze {
+    pub document_id: String,
+    pub user_id: UserId,
+    pub until: u64,
+    pub set_at: TimestampNanos,
+}
+
+impl Mergeable for DocumentSnooze {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.set_at > self.set_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// What a participant still has to do on a document
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum PendingActionKind {
+    /// Give consent before signing
+    Consent,
+    /// Sign the document
+    Sign,
+}
+
+/// An entry in a participant's to-do queue - uses LWW based on created_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PendingAction {
+    pub kind: PendingActionKind,
+    pub document_id: String,
+    pub document_name: String,
+    pub requested_by: UserId,
+    pub created_at: TimestampNanos,
+}
+
+impl Mergeable for PendingAction {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.created_at > self.created_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A participant retrieving a document's PDF - uses LWW based on accessed_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentAccessEvent {
+    pub accessed_by: UserId,
+    pub accessed_at: TimestampNanos,
+    /// Blob that was retrieved and the version it belongs to
+    pub pdf_blob_id: BlobId,
+    pub hash: String,
+    pub version: u64,
+}
+
+impl Mergeable for DocumentAccessEvent {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on accessed_at - newer wins
+        if other.accessed_at > self.accessed_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Supporting file linked to a document (exhibit, ID, evidence) - uses LWW based on added_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentAttachment {
+    pub id: String,
+    pub document_id: String,
+    pub name: String,
+    pub blob_id: BlobId,
+    pub mime_type: String,
+    pub added_by: UserId,
+    pub added_at: TimestampNanos,
+}
+
+impl Mergeable for DocumentAttachment {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.added_at > self.added_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// What changed between two versions of a document
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct VersionComparison {
+    pub document_id: String,
+    pub from_version: u64,
+    pub to_version: u64,
+    pub from_hash: String,
+    pub to_hash: String,
+    pub hash_changed: bool,
+    pub size_delta: i64,
+    pub page_count_delta: Option<i64>,
+    pub text_compared: bool,
+    pub changed_chunks: Vec<u64>,
+    pub added_chunks: Vec<u64>,
+    pub removed_chunks: Vec<u64>,
+}
+
+impl Mergeable for DocumentVersion {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on created_at - newer wins
+        if other.created_at > self.created_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Passage of a document cited by an answer
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct QaCitation {
+    pub text: String,
+    pub start_position: usize,
+    pub end_position: usize,
+}
+
+/// Question/answer exchange about a document - uses LWW based on asked_at timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct QaExchange {
+    pub question: String,
+    pub answer: String,
+    pub citations: Vec<QaCitation>,
+    pub asked_by: UserId,
+    pub asked_at: TimestampNanos,
+}
+
+impl Mergeable for QaExchange {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on asked_at - newer wins
+        if other.asked_at > self.asked_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// What an external share descriptor allows a gateway to serve
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum ShareScope {
+    Verification,
+    ReadOnly,
+}
+
+/// Record of a document shared outside the context - revocation wins on merge
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ShareDescriptor {
+    pub id: String,
+    pub document_id: String,
+    pub document_hash: String,
+    pub scope: ShareScope,
+    pub created_by: UserId,
+    pub created_at: TimestampNanos,
+    pub expires_at: TimestampNanos,
+    pub revoked_at: Option<TimestampNanos>,
+    pub digest: String,
+    /// ed25519 signature by `created_by` over the canonical share descriptor payload
+    pub signature: Vec<u8>,
+}
+
+impl Mergeable for ShareDescriptor {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Revocation is permanent - a revoked copy always wins
+        if self.revoked_at.is_none() && other.revoked_at.is_some() {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Folder for organizing documents in a shared context - uses LWW based on created_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentFolder {
+    pub id: String,
+    pub name: String,
+    pub parent: Option<String>,
+    pub created_by: UserId,
+    pub created_at: TimestampNanos,
+}
+
+impl Mergeable for DocumentFolder {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on created_at - newer wins
+        if other.created_at > self.created_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Named set of participants that can be added to documents in one step - uses LWW based
+/// on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Group {
+    pub id: String,
+    pub name: String,
+    pub members: Vec<UserId>,
+    pub created_by: UserId,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for Group {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Legal entity that participants act for, so signatures can name the company behind
+/// the signer - uses LWW based on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+    pub registration_number: Option<String>,
+    pub members: Vec<UserId>,
+    pub created_by: UserId,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for Organization {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A signature together with the organization it was made for
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SigningParty {
+    pub signer: UserId,
+    pub signed_by: UserId,
+    pub signed_at: TimestampNanos,
+    pub organization: Option<Organization>,
+}
+
+/// Entity-level parties to a document, for completion certificates
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentParties {
+    pub document_id: String,
+    /// Organization the document was sent on behalf of
+    pub sender: Option<Organization>,
+    pub signers: Vec<SigningParty>,
+}
+
+/// Rotation of the context's content-encryption key. Keys are generated and wrapped for
+/// each participant client-side; the context only records the epochs - uses LWW based on
+/// rotated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct KeyEpoch {
+    pub epoch: u64,
+    pub rotated_by: UserId,
+    pub rotated_at: TimestampNanos,
+    pub reason: Option<String>,
+    /// Participants at the time of rotation, who should each get a wrapped copy of the key
+    pub recipients: Vec<UserId>,
+}
+
+impl Mergeable for KeyEpoch {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.rotated_at > self.rotated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Current key epoch and the epochs whose keys the caller needs
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct KeyEpochStatus {
+    /// 0 until the key is first rotated
+    pub epoch: u64,
+    pub rotated_at: Option<TimestampNanos>,
+    /// A participant was removed since the last rotation
+    pub rotation_due: bool,
+    /// Epochs of the documents visible to the caller, oldest first
+    pub needed_epochs: Vec<u64>,
+}
+
+/// Who archived a shared context and when
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextArchive {
+    pub archived_by: UserId,
+    pub archived_at: TimestampNanos,
+}
+
+/// Descriptive metadata about a shared context beyond its name
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextProfile {
+    pub description: Option<String>,
+    pub avatar_blob_id: Option<BlobId>,
+    /// Identifier of the deal or matter in an external system
+    pub external_reference: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub tags: Vec<String>,
+    pub updated_by: Option<UserId>,
+    pub updated_at: TimestampNanos,
+}
+
+/// Direct children of a folder (or of the root when no folder is given). Subfolders are
+/// listed in full; documents are paginated.
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct FolderContents {
+    pub folders: Vec<DocumentFolder>,
+    pub documents: Page<DocumentInfo>,
+}
+
+/// Document matching a keyword search, with highlighted passages
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct KeywordSearchHit {
+    pub document_id: String,
+    pub name: String,
+    pub score: f32,
+    pub matched_terms: Vec<String>,
+    pub snippets: Vec<String>,
+}
+
+/// Chunk of a document matching a cross-document semantic search
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ChunkSearchHit {
+    pub document_id: String,
+    pub document_name: String,
+    pub text: String,
+    pub passage: String,
+    pub start_position: usize,
+    pub end_position: usize,
+    pub score: f32,
+}
+
+/// Best matching chunks across a context; `truncated` is set when the scan stopped early
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextSearchResult {
+    pub hits: Vec<ChunkSearchHit>,
+    pub scanned_chunks: u64,
+    pub truncated: bool,
+}
+
+/// Outcome of one embedding quantization batch
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct QuantizationProgress {
+    pub converted: u64,
+    pub remaining: u64,
+}
+
+/// One executed document in a download bundle
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ManifestEntry {
+    pub document_id: String,
+    pub filename: String,
+    pub blob_id: String,
+    pub hash: String,
+    pub size: u64,
+}
+
+/// Ordered list of blobs a client fetches to assemble a bundle of executed documents
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DownloadManifest {
+    pub folder_id: Option<String>,
+    pub entries: Vec<ManifestEntry>,
+    pub total_size: u64,
+}
+
+/// Claim that a participant controls an Internet Computer principal - uses LWW based on linked_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PrincipalLink {
+    pub user_id: UserId,
+    pub principal: String,
+    /// Hex-encoded ed25519 key of the principal followed by its signature over the link payload
+    pub proof: String,
+    pub linked_at: TimestampNanos,
+}
+
+impl Mergeable for PrincipalLink {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on linked_at - newer wins
+        if other.linked_at > self.linked_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A signer's formal rejection of a document - uses LWW based on declined_at timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentDecline {
+    pub document_id: String,
+    pub declined_by: UserId,
+    pub reason: String,
+    pub declined_at: TimestampNanos,
+}
+
+/// Why and by whom a document was voided - uses LWW based on voided_at timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentVoid {
+    pub document_id: String,
+    pub voided_by: UserId,
+    pub reason: String,
+    pub voided_at: TimestampNanos,
+}
+
+impl Mergeable for DocumentVoid {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.voided_at > self.voided_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Mergeable for DocumentDecline {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on declined_at - newer wins
+        if other.declined_at > self.declined_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Kind of mutating operation recorded in the audit log
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum AuditAction {
+    DocumentUploaded,
+    DocumentDeleted,
+    DocumentSigned,
+    ConsentGiven,
+    ConsentWithdrawn,
+    ParticipantAdded,
+    ParticipantJoined,
+    ParticipantRemoved,
+    ParticipantDeactivated,
+    ParticipantReactivated,
+    PrincipalLinked,
+    PrincipalUnlinked,
+    ShareDescriptorCreated,
+    ShareDescriptorRevoked,
+    OversightRoleChanged,
+    DocumentDeclined,
+    DocumentVoided,
+    AttachmentAdded,
+    AttachmentRemoved,
+    DocumentReopened,
+    DocumentAccessChanged,
+    DocumentTagsChanged,
+    FolderCreated,
+    DocumentMoved,
+    VisibilityConditionChanged,
+    SignatureFieldAdded,
+    SignatureFieldRemoved,
+    SigningDelegated,
+    DocumentSnoozed,
+    SigningDelegationRevoked,
+    SignerDueDateChanged,
+    DocumentExpired,
+    FeatureToggled,
+    RoleDefined,
+    RoleAssigned,
+    PermissionChanged,
+    OwnershipTransferred,
+    JoinRequested,
+    JoinRequestDenied,
+    GroupChanged,
+    GroupAppliedToDocument,
+    OrganizationChanged,
+    DocumentOrganizationChanged,
+    ContextArchived,
+    ContextUnarchived,
+    ContextProfileUpdated,
+    ContextKeyRotated,
+    TemplateCreated,
+    TemplateDeleted,
+    EnvelopeCreated,
+    EnvelopeSigned,
+    RecurringScheduleCreated,
+    RecurringScheduleDeleted,
+    DocumentSummarySet,
+    NumberingSchemeChanged,
+    PageGeometryChanged,
+    EmbeddingModelSet,
+    ReindexStarted,
+    DocumentReindexed,
+    EmbeddingsQuantized,
+    ChunkContextWindowChanged,
+    QaExchangeRecorded,
+}
+
+impl AuditAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::DocumentUploaded => "DocumentUploaded",
+            AuditAction::DocumentDeleted => "DocumentDeleted",
+            AuditAction::DocumentSigned => "DocumentSigned",
+            AuditAction::ConsentGiven => "ConsentGiven",
+            AuditAction::ConsentWithdrawn => "ConsentWithdrawn",
+            AuditAction::ParticipantAdded => "ParticipantAdded",
+            AuditAction::ParticipantJoined => "ParticipantJoined",
+            AuditAction::ParticipantRemoved => "ParticipantRemoved",
+            AuditAction::ParticipantDeactivated => "ParticipantDeactivated",
+            AuditAction::ParticipantReactivated => "ParticipantReactivated",
+            AuditAction::PrincipalLinked => "PrincipalLinked",
+            AuditAction::PrincipalUnlinked => "PrincipalUnlinked",
+            AuditAction::ShareDescriptorCreated => "ShareDescriptorCreated",
+            AuditAction::ShareDescriptorRevoked => "ShareDescriptorRevoked",
+            AuditAction::OversightRoleChanged => "OversightRoleChanged",
+            AuditAction::DocumentDeclined => "DocumentDeclined",
+            AuditAction::DocumentVoided => "DocumentVoided",
+            AuditAction::AttachmentAdded => "AttachmentAdded",
+            AuditAction::AttachmentRemoved => "AttachmentRemoved",
+            AuditAction::DocumentReopened => "DocumentReopened",
+            AuditAction::DocumentAccessChanged => "DocumentAccessChanged",
+            AuditAction::DocumentTagsChanged => "DocumentTagsChanged",
+            AuditAction::FolderCreated => "FolderCreated",
+            AuditAction::DocumentMoved => "DocumentMoved",
+            AuditAction::VisibilityConditionChanged => "VisibilityConditionChanged",
+            AuditAction::SignatureFieldAdded => "SignatureFieldAdded",
+            AuditAction::SignatureFieldRemoved => "SignatureFieldRemoved",
+            AuditAction::SigningDelegated => "SigningDelegated",
+            AuditAction::SigningDelegationRevoked => "SigningDelegationRevoked",
+            AuditAction::DocumentSnoozed => "DocumentSnoozed",
+            AuditAction::SignerDueDateChanged => "SignerDueDateChanged",
+            AuditAction::DocumentExpired => "DocumentExpired",
+            AuditAction::FeatureToggled => "FeatureToggled",
+            AuditAction::RoleDefined => "RoleDefined",
+            AuditAction::RoleAssigned => "RoleAssigned",
+            AuditAction::PermissionChanged => "PermissionChanged",
+            AuditAction::OwnershipTransferred => "OwnershipTransferred",
+            AuditAction::JoinRequested => "JoinRequested",
+            AuditAction::JoinRequestDenied => "JoinRequestDenied",
+            AuditAction::GroupChanged => "GroupChanged",
+            AuditAction::GroupAppliedToDocument => "GroupAppliedToDocument",
+            AuditAction::OrganizationChanged => "OrganizationChanged",
+            AuditAction::DocumentOrganizationChanged => "DocumentOrganizationChanged",
+            AuditAction::ContextArchived => "ContextArchived",
+            AuditAction::ContextUnarchived => "ContextUnarchived",
+            AuditAction::ContextProfileUpdated => "ContextProfileUpdated",
+            AuditAction::ContextKeyRotated => "ContextKeyRotated",
+            AuditAction::TemplateCreated => "TemplateCreated",
+            AuditAction::TemplateDeleted => "TemplateDeleted",
+            AuditAction::EnvelopeCreated => "EnvelopeCreated",
+            AuditAction::EnvelopeSigned => "EnvelopeSigned",
+            AuditAction::RecurringScheduleCreated => "RecurringScheduleCreated",
+            AuditAction::RecurringScheduleDeleted => "RecurringScheduleDeleted",
+            AuditAction::DocumentSummarySet => "DocumentSummarySet",
+            AuditAction::NumberingSchemeChanged => "NumberingSchemeChanged",
+            AuditAction::PageGeometryChanged => "PageGeometryChanged",
+            AuditAction::EmbeddingModelSet => "EmbeddingModelSet",
+            AuditAction::ReindexStarted => "ReindexStarted",
+            AuditAction::DocumentReindexed => "DocumentReindexed",
+            AuditAction::EmbeddingsQuantized => "EmbeddingsQuantized",
+            AuditAction::ChunkContextWindowChanged => "ChunkContextWindowChanged",
+            AuditAction::QaExchangeRecorded => "QaExchangeRecorded",
+        }
+    }
+}
+
+/// Audit log entry for a mutating operation in a shared context - uses LWW based on timestamp
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: TimestampNanos,
+    pub actor: UserId,
+    pub action: AuditAction,
+    pub entity_id: String,
+    pub details: String,
+}
+
+impl Mergeable for AuditEntry {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.timestamp > self.timestamp {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Kind of record a change log entry refers to
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum ChangeEntity {
+    Document,
+    Signature,
+    Participant,
+    Folder,
+}
+
+/// How a record changed
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Compact descriptor of one mutation, for clients syncing deltas - uses LWW based on changed_at.
+/// `id` orders entries by time, then author, then `seq`; `seq` only breaks ties between
+/// changes one author made at the same instant, so it is not unique across members.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ChangeEntry {
+    pub id: String,
+    pub seq: u64,
+    pub author: UserId,
+    pub entity: ChangeEntity,
+    pub entity_id: String,
+    pub kind: ChangeKind,
+    pub changed_at: TimestampNanos,
+}
+
+impl Mergeable for ChangeEntry {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on changed_at - newer wins
+        if other.changed_at > self.changed_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Output format for audit log exports
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum AuditExportFormat {
+    Csv,
+    Jsonl,
+}
+
+/// One chunk of an audit log export; pass `next_cursor` back to continue
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct AuditExportChunk {
+    pub format: AuditExportFormat,
+    pub payload: String,
+    pub entry_count: u64,
+    pub next_cursor: Option<u64>,
+}
+
+/// Signing turnaround for a single document, in nanoseconds since upload
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct DocumentSigningMetrics {
+    pub document_id: String,
+    pub uploaded_at: TimestampNanos,
+    pub time_to_first_signature: Option<u64>,
+    pub time_to_completion: Option<u64>,
+}
+
+/// Signing turnaround aggregated over a shared context, in nanoseconds
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct SigningMetrics {
+    pub total_documents: u64,
+    pub signed_documents: u64,
+    pub completed_documents: u64,
+    pub average_time_to_first_signature: Option<u64>,
+    pub average_time_to_completion: Option<u64>,
+    pub max_time_to_completion: Option<u64>,
+    pub documents: Vec<DocumentSigningMetrics>,
+}
+
+/// Activity in one calendar month (UTC)
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct MonthlyUsage {
+    /// `YYYY-MM`
+    pub month: String,
+    pub documents_sent: u64,
+    pub documents_completed: u64,
+    pub signatures_applied: u64,
+}
+
+impl MonthlyUsage {
+    fn empty(month: String) -> Self {
+        MonthlyUsage {
+            month,
+            documents_sent: 0,
+            documents_completed: 0,
+            signatures_applied: 0,
+        }
+    }
+}
+
+/// Monthly activity of a shared context, for usage-based billing
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct UsageReport {
+    pub context_id: ContextId,
+    pub period: Option<String>,
+    pub months: Vec<MonthlyUsage>,
+    pub documents_sent: u64,
+    pub documents_completed: u64,
+    pub signatures_applied: u64,
+    pub generated_at: TimestampNanos,
+}
+
+/// Permission levels for participants
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum PermissionLevel {
+    Read,
+    Sign,
+    Admin,
+}
+
+impl Mergeable for PermissionLevel {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Take higher permission (Admin > Sign > Read)
+        let self_priority = match self {
+            PermissionLevel::Admin => 2,
+            PermissionLevel::Sign => 1,
+            PermissionLevel::Read => 0,
+        };
+        let other_priority = match other {
+            PermissionLevel::Admin => 2,
+            PermissionLevel::Sign => 1,
+            PermissionLevel::Read => 0,
+        };
+        if other_priority > self_priority {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Metadata for tracking joined shared contexts
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextMetadata {
+    pub context_id: ContextId,
+    pub context_name: String,
+    pub role: ParticipantRole,
+    pub joined_at: TimestampNanos,
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+}
+
+impl Mergeable for ContextMetadata {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on joined_at - newer wins
+        if other.joined_at > self.joined_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Client-reported sync health of a joined shared context - uses LWW based on reported_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextHealth {
+    pub context_id: ContextId,
+    pub reachable: bool,
+    pub last_sync_at: TimestampNanos,
+    pub reported_at: TimestampNanos,
+}
+
+impl Mergeable for ContextHealth {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on reported_at - newer wins
+        if other.reported_at > self.reported_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Personal note on a shared document, kept only in the private context - uses LWW based on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct PrivateNote {
+    pub context_id: ContextId,
+    pub document_id: String,
+    pub note: String,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for PrivateNote {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on updated_at - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Joined context that hasn't synced recently
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct StaleContext {
+    pub metadata: ContextMetadata,
+    pub health: Option<ContextHealth>,
+}
+
+/// Pending request by a recovery identity to take over a private context
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct RecoveryRequest {
+    pub requested_by: UserId,
+    pub requested_at: TimestampNanos,
+    pub unlocks_at: TimestampNanos,
+}
+
+/// Identity mapping for tracking user identities across contexts
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct IdentityMapping {
+    pub private_identity: UserId,
+    pub shared_identity: UserId,
+    pub context_id: ContextId,
+    pub created_at: TimestampNanos,
+}
+
+impl Mergeable for IdentityMapping {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on created_at - newer wins
+        if other.created_at > self.created_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Oversight roles for non-signing participants, layered on `PermissionLevel::Read`
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum OversightRole {
+    /// Sees documents and statuses, but not blob IDs
+    Observer,
+    /// Sees the audit log and all statuses, but not blob IDs or document content
+    Auditor,
+}
+
+impl Mergeable for OversightRole {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Take broader visibility (Auditor > Observer)
+        if *other == OversightRole::Auditor {
+            *self = OversightRole::Auditor;
+        }
+        Ok(())
+    }
+}
+
+/// Where a join request stands
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// Request from a context member to become a participant - uses LWW based on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct JoinRequest {
+    pub user_id: UserId,
+    pub message: Option<String>,
+    pub requested_at: TimestampNanos,
+    pub status: JoinRequestStatus,
+    pub decided_by: Option<UserId>,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for JoinRequest {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Participant information with permission level
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ParticipantInfo {
+    pub user_id: UserId,
+    pub permission_level: PermissionLevel,
+    pub oversight_role: Option<OversightRole>,
+    pub deactivation: Option<ParticipantDeactivation>,
+}
+
+/// A participant put on hold without being removed - uses LWW based on deactivated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ParticipantDeactivation {
+    pub deactivated_by: UserId,
+    pub deactivated_at: TimestampNanos,
+    pub reason: Option<String>,
+}
+
+impl Mergeable for ParticipantDeactivation {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.deactivated_at > self.deactivated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Detailed information about a shared context
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct ContextDetails {
+    pub context_id: ContextId,
+    pub context_name: String,
+    pub owner: UserId,
+    pub is_private: bool,
+    pub participant_count: u64,
+    pub participants: Vec<ParticipantInfo>,
+    pub document_count: u64,
+    pub created_at: TimestampNanos,
+    pub archive: Option<ContextArchive>,
+    pub profile: ContextProfile,
+}
+
+#[app::state(emits = MeroSignEvent)]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+pub struct MeroSignState {
+    // Context type flag
+    pub is_private: LwwRegister<bool>,
+
+    pub owner: LwwRegister<UserId>,
+    pub context_name: LwwRegister<String>,
+
+    // Private context data
+    pub signatures: UnorderedMap<String, SignatureRecord>,
+    pub joined_contexts: UnorderedMap<String, ContextMetadata>,
+    pub identity_mappings: UnorderedMap<String, IdentityMapping>,
+    pub context_health: UnorderedMap<String, ContextHealth>,
+    pub private_notes: UnorderedMap<String, PrivateNote>,
+    pub signature_count: LwwRegister<u64>,
+    pub signature_limits: LwwRegister<SignatureLimits>,
+    pub signature_tombstones: UnorderedMap<String, SignatureTombstone>,
+    pub signature_uses: UnorderedMap<String, Vector<SignatureUse>>,
+    pub signature_use_limits: LwwRegister<SignatureUseLimits>,
+    pub recovery_identities: UnorderedSet<UserId>,
+    pub recovery_request: LwwRegister<Option<RecoveryRequest>>,
+    pub document_digests: UnorderedMap<String, Vector<IndexedDocument>>,
+
+    // Shared context data
+    pub participants: UnorderedSet<UserId>,
+    pub documents: UnorderedMap<String, DocumentInfo>,
+    pub document_chunks: UnorderedMap<String, Vector<DocumentChunk>>,
+    pub document_signatures: UnorderedMap<String, Vector<DocumentSignature>>,
+    pub document_versions: UnorderedMap<String, Vector<DocumentVersion>>,
+    pub document_access_log: UnorderedMap<String, Vector<DocumentAccessEvent>>,
+    pub document_attachments: UnorderedMap<String, Vector<DocumentAttachment>>,
+    pub document_declines: UnorderedMap<String, DocumentDecline>,
+    pub document_voids: UnorderedMap<String, DocumentVoid>,
+    pub signing_delegations: UnorderedMap<String, SigningDelegation>,
+    pub document_snoozes: UnorderedMap<String, DocumentSnooze>,
+    pub pending_actions: UnorderedMap<String, Vector<PendingAction>>,
+    pub permissions: UnorderedMap<UserId, PermissionLevel>,
+    pub oversight_roles: UnorderedMap<UserId, OversightRole>,
+    pub deactivations: UnorderedMap<UserId, ParticipantDeactivation>,
+    pub roles: UnorderedMap<String, Role>,
+    pub role_assignments: UnorderedMap<UserId, RoleAssignment>,
+    pub join_requests: UnorderedMap<UserId, JoinRequest>,
+    pub principal_links: UnorderedMap<UserId, PrincipalLink>,
+    pub folders: UnorderedMap<String, DocumentFolder>,
+    pub groups: UnorderedMap<String, Group>,
+    pub organizations: UnorderedMap<String, Organization>,
+    pub templates: UnorderedMap<String, DocumentTemplate>,
+    pub recurring_schedules: UnorderedMap<String, RecurringSchedule>,
+    pub envelopes: UnorderedMap<String, Envelope>,
+    pub consents: UnorderedMap<String, LwwRegister<bool>>,
+    pub consent_history: UnorderedMap<String, Vector<ConsentRecord>>,
+    pub feature_flags: UnorderedMap<String, LwwRegister<bool>>,
+    pub qa_history: UnorderedMap<String, Vector<QaExchange>>,
+    pub share_descriptors: UnorderedMap<String, ShareDescriptor>,
+    pub audit_log: Vector<AuditEntry>,
+    pub change_log: Vector<ChangeEntry>,
+    pub numbering_scheme: LwwRegister<Option<NumberingScheme>>,
+    pub document_counters: UnorderedMap<String, LwwRegister<u64>>,
+    pub embedding_model: LwwRegister<Option<EmbeddingModel>>,
+    pub reindex_job: LwwRegister<Option<ReindexJob>>,
+    pub reindex_status: UnorderedMap<String, ReindexStatus>,
+    pub blob_announcements: UnorderedMap<String, BlobAnnouncement>,
+    pub keyword_index: UnorderedMap<String, UnorderedMap<String, LwwRegister<u32>>>,
+    pub chunk_context_window: LwwRegister<u64>,
+    pub archive: LwwRegister<Option<ContextArchive>>,
+    pub profile: LwwRegister<ContextProfile>,
+    pub key_epochs: Vector<KeyEpoch>,
+    pub key_rotation_due: LwwRegister<bool>,
+}
+
+#[app::event]
+pub enum MeroSignEvent {
+    // Private context events
+    SignatureCreated {
+        id: u64,
+        name: String,
+        size: u64,
+    },
+    SignatureDeleted {
+        id: u64,
+    },
+    AnomalousSignatureUse {
+        signature_id: u64,
+        documents_in_window: u64,
+        max_documents: u64,
+        window_nanos: u64,
+    },
+    ContextJoined {
+        context_id: String,
+        context_name: String,
+    },
+    ContextLeft {
+        context_id: String,
+    },
+    RecoveryIdentityAdded {
+        user_id: UserId,
+    },
+    RecoveryIdentityRemoved {
+        user_id: UserId,
+    },
+    RecoveryInitiated {
+        requested_by: UserId,
+        unlocks_at: u64,
+    },
+    RecoveryCancelled {
+        requested_by: UserId,
+    },
+    OwnershipRecovered {
+        previous_owner: UserId,
+        new_owner: UserId,
+    },
+
+    // Shared context events
+    DocumentUploaded {
+        id: String,
+        name: String,
+        uploaded_by: UserId,
+    },
+    DocumentDeleted {
+        id: String,
+    },
+    DocumentExpired {
+        id: String,
+    },
+    OverdueSignatureReminder {
+        document_id: String,
+        signer: UserId,
+        due_at: u64,
+    },
+    DocumentSnoozed {
+        document_id: String,
+        user_id: UserId,
+        until: Option<u64>,
+    },
+    ResourceThresholdExceeded {
+        collection: String,
+        approx_bytes: u64,
+        warning_bytes: u64,
+    },
+    DocumentStatusChanged {
+        document_id: String,
+        from: DocumentStatus,
+        to: DocumentStatus,
+    },
+    DocumentVoided {
+        document_id: String,
+        voided_by: UserId,
+        reason: String,
+    },
+    DocumentDeclined {
+        document_id: String,
+        declined_by: UserId,
+        reason: String,
+    },
+    DocumentReopened {
+        document_id: String,
+    },
+    DocumentSigned {
+        document_id: String,
+        signer: UserId,
+        on_behalf_of: Option<UserId>,
+    },
+    DocumentAccessed {
+        document_id: String,
+        accessed_by: UserId,
+        version: u64,
+    },
+    AttachmentAdded {
+        document_id: String,
+        attachment_id: String,
+        name: String,
+    },
+    AttachmentRemoved {
+        document_id: String,
+        attachment_id: String,
+    },
+    SigningDelegated {
+        document_id: String,
+        delegator: UserId,
+        delegate: UserId,
+        expires_at: u64,
+    },
+    SigningDelegationRevoked {
+        document_id: String,
+        delegator: UserId,
+    },
+    DocumentAccessChanged {
+        document_id: String,
+        user_id: UserId,
+        permission: Option<PermissionLevel>,
+    },
+    ParticipantInvited {
+        user_id: UserId,
+        role: ParticipantRole,
+    },
+    ParticipantJoined {
+        user_id: UserId,
+    },
+    ParticipantLeft {
+        user_id: UserId,
+    },
+    ParticipantDeactivated {
+        user_id: UserId,
+        deactivated_by: UserId,
+    },
+    ParticipantReactivated {
+        user_id: UserId,
+        reactivated_by: UserId,
+    },
+    JoinRequested {
+        user_id: UserId,
+        message: Option<String>,
+    },
+    JoinRequestApproved {
+        user_id: UserId,
+        permission: PermissionLevel,
+    },
+    JoinRequestDenied {
+        user_id: UserId,
+    },
+    GroupChanged {
+        id: String,
+        name: String,
+        members: Vec<UserId>,
+    },
+    OrganizationChanged {
+        id: String,
+        name: String,
+        members: Vec<UserId>,
+    },
+    ContextArchived {
+        archived_by: UserId,
+    },
+    ContextUnarchived {
+        unarchived_by: UserId,
+    },
+    ContextProfileUpdated {
+        updated_by: UserId,
+    },
+    ContextKeyRotated {
+        epoch: u64,
+        rotated_by: UserId,
+    },
+    EnvelopeSigned {
+        envelope_id: String,
+        signer: UserId,
+        document_ids: Vec<String>,
+    },
+    TemplateCreated {
+        id: String,
+        name: String,
+    },
+    KeyRotationDue {
+        removed_user: UserId,
+    },
+    ParticipantPermissionChanged {
+        user_id: UserId,
+        permission: PermissionLevel,
+    },
+    OwnershipTransferred {
+        previous_owner: UserId,
+        new_owner: UserId,
+    },
+    OversightRoleChanged {
+        user_id: UserId,
+        role: Option<OversightRole>,
+    },
+    RoleDefined {
+        name: String,
+        capabilities: Vec<String>,
+    },
+    RoleAssigned {
+        user_id: UserId,
+        role: Option<String>,
+    },
+    PrincipalLinked {
+        user_id: UserId,
+        principal: String,
+    },
+    FeatureToggled {
+        name: String,
+        enabled: bool,
+    },
+    FolderCreated {
+        id: String,
+        name: String,
+        parent: Option<String>,
+    },
+    DocumentMoved {
+        document_id: String,
+        folder_id: Option<String>,
+    },
+    PrincipalUnlinked {
+        user_id: UserId,
+    },
+    VisibilityConditionSet {
+        document_id: String,
+        requires_fully_signed: Option<String>,
+    },
+    QaExchangeRecorded {
+        document_id: String,
+        asked_by: UserId,
+    },
+    EmbeddingModelSet {
+        name: String,
+        dimension: u64,
+    },
+    ReindexStarted {
+        target_model: String,
+        target_dimension: u64,
+        stale_documents: u64,
+    },
+    DocumentReindexed {
+        document_id: String,
+    },
+    ReindexCompleted {
+        model: String,
+        dimension: u64,
+    },
+    DocumentSummarySet {
+        document_id: String,
+        generated_by: String,
+    },
+    ShareDescriptorCreated {
+        id: String,
+        document_id: String,
+        expires_at: u64,
+    },
+    ShareDescriptorRevoked {
+        id: String,
+        document_id: String,
+    },
+}
+
+/// Helper to decode base58 blob_id from API input
+fn parse_blob_id_base58(blob_id_str: &str) -> Result<BlobId, MeroDocsError> {
+    match bs58::decode(blob_id_str).into_vec() {
+        Ok(bytes) => {
+            if bytes.len() != 32 {
+                return Err(MeroDocsError::invalid_blob_id(format!(
+                    "Invalid blob ID length: expected 32 bytes, got {}",
+                    bytes.len()
+                )));
+            }
+            let mut blob_id = [0u8; 32];
+            blob_id.copy_from_slice(&bytes);
+            Ok(blob_id)
+        }
+        Err(e) => Err(MeroDocsError::invalid_blob_id(format!(
+            "Failed to decode blob ID '{}': {}",
+            blob_id_str, e
+        ))),
+    }
+}
+
+/// Length of a value's Borsh encoding, as an estimate of its size in storage
+fn encoded_len<T: BorshSerialize>(value: &T) -> u64 {
+    calimero_sdk::borsh::to_vec(value).map_or(0, |bytes| bytes.len() as u64)
+}
+
+/// Identity executing the current call. Authorization must check this, never `owner`,
+/// which is only the identity that created the context.
+fn current_caller() -> UserId {
+    env::executor_id()
+}
+
+/// Helper to decode base58 public key from API input
+fn parse_public_key_base58(key_str: &str) -> Result<UserId, MeroDocsError> {
+    key_str
+        .parse::<PublicKey>()
+        .map(|pk| *pk.as_ref())
+        .map_err(|e| {
+            MeroDocsError::invalid_input(format!("Failed to parse public key '{}': {}", key_str, e))
+        })
+}
+
+/// Helper to decode base58 context ID from API input
+fn parse_context_id_base58(context_id_str: &str) -> Result<ContextId, MeroDocsError> {
+    match bs58::decode(context_id_str).into_vec() {
+        Ok(bytes) => {
+            if bytes.len() != 32 {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Invalid context ID length: expected 32 bytes, got {}",
+                    bytes.len()
+                )));
+            }
+            let mut context_id = [0u8; 32];
+            context_id.copy_from_slice(&bytes);
+            Ok(context_id)
+        }
+        Err(e) => Err(MeroDocsError::invalid_input(format!(
+            "Failed to decode context ID '{}': {}",
+            context_id_str, e
+        ))),
+    }
+}
+
+/// Helper to encode context ID to base58 string
+fn encode_context_id_base58(context_id: &ContextId) -> String {
+    bs58::encode(context_id).into_string()
+}
+
+/// Validate a field spec and give it an ID not used by the existing fields
+fn build_signature_field(
+    spec: SignatureFieldSpec,
+    existing: &[SignatureField],
+    page_sizes: &[PageSize],
+) -> Result<SignatureField, MeroDocsError> {
+    let dimensions = [spec.x, spec.y, spec.width, spec.height];
+    if dimensions.iter().any(|d| !d.is_finite() || *d < 0.0) {
+        return Err(MeroDocsError::invalid_input(
+            "Signature field coordinates must be non-negative numbers",
+        ));
+    }
+    if spec.width == 0.0 || spec.height == 0.0 {
+        return Err(MeroDocsError::invalid_input(
+            "Signature field must have a non-zero size",
+        ));
+    }
+    if !page_sizes.is_empty() {
+        check_field_on_page(
+            spec.page,
+            spec.x,
+            spec.y,
+            spec.width,
+            spec.height,
+            page_sizes,
+        )?;
+    }
+
+    let assigned_signer = match spec.assigned_signer {
+        Some(signer_str) => Some(parse_public_key_base58(&signer_str)?),
+        None => None,
+    };
+
+    let mut n = existing.len() + 1;
+    let mut id = format!("field_{}", n);
+    while existing.iter().any(|f| f.id == id) {
+        n += 1;
+        id = format!("field_{}", n);
+    }
+
+    Ok(SignatureField {
+        id,
+        page: spec.page,
+        x: spec.x,
+        y: spec.y,
+        width: spec.width,
+        height: spec.height,
+        assigned_signer,
+        filled_by: None,
+        filled_at: None,
+    })
+}
+
+/// Check that a field lies within its page. Pages are numbered from 1.
+fn check_field_on_page(
+    page: u32,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    page_sizes: &[PageSize],
+) -> Result<(), MeroDocsError> {
+    let size = page
+        .checked_sub(1)
+        .and_then(|index| page_sizes.get(index as usize))
+        .ok_or_else(|| {
+            MeroDocsError::invalid_input(format!(
+                "Page {} does not exist; the document has {} pages",
+                page,
+                page_sizes.len()
+            ))
+        })?;
+    if x + width > size.width || y + height > size.height {
+        return Err(MeroDocsError::invalid_input(format!(
+            "Signature field extends outside page {} ({} x {})",
+            page, size.width, size.height
+        )));
+    }
+    Ok(())
+}
+
+/// Check that every page has a positive, finite size
+fn validate_page_sizes(page_sizes: &[PageSize]) -> Result<(), MeroDocsError> {
+    if page_sizes.len() > MAX_DOCUMENT_PAGES {
+        return Err(MeroDocsError::invalid_input(format!(
+            "Page geometry cannot describe more than {} pages",
+            MAX_DOCUMENT_PAGES
+        )));
+    }
+    for (index, size) in page_sizes.iter().enumerate() {
+        let valid = |d: f32| d.is_finite() && d > 0.0;
+        if !valid(size.width) || !valid(size.height) {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Page {} must have a positive width and height",
+                index + 1
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check an ed25519 signature by `signer` over the canonical signing payload of a document hash
+fn verify_signing_payload(
+    signer: &UserId,
+    document_id: &str,
+    document_hash: &str,
+    signature: &[u8],
+) -> Result<(), MeroDocsError> {
+    let key = VerifyingKey::from_bytes(signer).map_err(|_| {
+        MeroDocsError::invalid_input("Signer identity is not a valid ed25519 public key")
+    })?;
+    let signature = Signature::from_slice(signature)
+        .map_err(|_| MeroDocsError::invalid_input("Signature must be 64 bytes"))?;
+    let payload = canonical::signing_payload(&env::context_id(), document_id, document_hash);
+    key.verify_strict(&payload, &signature).map_err(|_| {
+        MeroDocsError::invalid_input("Signature does not verify against the document hash")
+    })
+}
+
+/// Check an ed25519 signature by the creator of a share descriptor over its terms
+fn verify_share_descriptor_payload(
+    created_by: &UserId,
+    document_id: &str,
+    document_hash: &str,
+    scope: &ShareScope,
+    expires_at: u64,
+    signature: &[u8],
+) -> Result<(), MeroDocsError> {
+    let key = VerifyingKey::from_bytes(created_by).map_err(|_| {
+        MeroDocsError::invalid_input("Creator identity is not a valid ed25519 public key")
+    })?;
+    let signature = Signature::from_slice(signature)
+        .map_err(|_| MeroDocsError::invalid_input("Signature must be 64 bytes"))?;
+    let payload = canonical::share_descriptor_payload(
+        &env::context_id(),
+        document_id,
+        document_hash,
+        scope,
+        created_by,
+        expires_at,
+    );
+    key.verify_strict(&payload, &signature).map_err(|_| {
+        MeroDocsError::invalid_input("Signature does not verify against the share descriptor")
+    })
+}
+
+/// Check a principal link proof: the hex-encoded ed25519 public key the principal was derived
+/// from, followed by its signature over the link payload for `user_id`
+fn verify_principal_proof(
+    user_id: &UserId,
+    principal: &str,
+    proof: &str,
+) -> Result<(), MeroDocsError> {
+    let bytes = hex::decode(proof)
+        .map_err(|_| MeroDocsError::invalid_input("Proof must be hex-encoded"))?;
+    if bytes.len() != 32 + 64 {
+        return Err(MeroDocsError::invalid_input(
+            "Proof must be a 32-byte public key followed by a 64-byte signature",
+        ));
+    }
+    let (public_key, signature) = bytes.split_at(32);
+    let public_key: [u8; 32] = public_key
+        .try_into()
+        .map_err(|_| MeroDocsError::invalid_input("Public key must be 32 bytes"))?;
+
+    if principal::self_authenticating(&public_key) != principal {
+        return Err(MeroDocsError::invalid_input(
+            "Proof key does not derive the principal",
+        ));
+    }
+
+    let key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|_| MeroDocsError::invalid_input("Proof key is not a valid ed25519 public key"))?;
+    let signature = Signature::from_slice(signature)
+        .map_err(|_| MeroDocsError::invalid_input("Signature must be 64 bytes"))?;
+    let payload = canonical::principal_link_payload(&env::context_id(), user_id, principal);
+    key.verify_strict(&payload, &signature).map_err(|_| {
+        MeroDocsError::invalid_input("Proof signature does not verify against the link payload")
+    })
+}
+
+/// Key of a delegator's signing delegation on a document
+fn delegation_key(document_id: &str, delegator: &UserId) -> String {
+    format!("{}:{}", document_id, bs58::encode(delegator).into_string())
+}
+
+/// Id of a change-log entry. Fixed-width fields make ids sort by time, then author,
+/// then the author's local sequence number.
+fn change_id(changed_at: u64, author: &UserId, seq: u64) -> String {
+    format!("{:020}-{}-{:020}", changed_at, hex::encode(author), seq)
+}
+
+/// Key of a participant's reminder snooze on a document
+fn snooze_key(document_id: &str, user_id: &UserId) -> String {
+    format!("{}:{}", document_id, bs58::encode(user_id).into_string())
+}
+
+/// Check merge field values against a template: no unknown or repeated fields, and every
+/// required field filled in
+fn validate_merge_values(
+    template: &DocumentTemplate,
+    field_values: Vec<MergeFieldValue>,
+) -> Result<Vec<MergeFieldValue>, MeroDocsError> {
+    let mut values: Vec<MergeFieldValue> = Vec::with_capacity(field_values.len());
+    for value in field_values {
+        let name = value.name.trim().to_string();
+        if !template.merge_fields.iter().any(|f| f.name == name) {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Unknown merge field: {}",
+                name
+            )));
+        }
+        if values.iter().any(|v| v.name == name) {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Duplicate merge field value: {}",
+                name
+            )));
+        }
+        values.push(MergeFieldValue {
+            name,
+            value: value.value,
+        });
+    }
+    for field in template.merge_fields.iter().filter(|f| f.required) {
+        let filled = values
+            .iter()
+            .any(|v| v.name == field.name && !v.value.trim().is_empty());
+        if !filled {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Missing value for required merge field: {}",
+                field.name
+            )));
+        }
+    }
+    Ok(values)
+}
+
+/// Trim an optional profile field, treating blank as unset, and enforce its length
+fn normalize_profile_field(
+    value: Option<String>,
+    field: &str,
+    max_chars: usize,
+) -> Result<Option<String>, MeroDocsError> {
+    let value = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    if value
+        .as_ref()
+        .is_some_and(|v| v.chars().count() > max_chars)
+    {
+        return Err(MeroDocsError::limit_exceeded(format!(
+            "{} cannot exceed {} characters",
+            field, max_chars
+        )));
+    }
+    Ok(value)
+}
+
+/// Trim and lowercase a tag so matching is case-insensitive
+fn normalize_tag(tag: &str) -> Result<String, MeroDocsError> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err(MeroDocsError::invalid_input("Tag cannot be empty"));
+    }
+    if tag.chars().count() > 64 {
+        return Err(MeroDocsError::invalid_input(
+            "Tag cannot be longer than 64 characters",
+        ));
+    }
+    Ok(tag)
+}
+
+/// Slice an in-memory listing into a page
+fn paginate<T>(items: Vec<T>, offset: Option<u64>, limit: Option<u64>) -> Page<T> {
+    let total = items.len() as u64;
+    let start = offset.unwrap_or(0);
+    let end = start.saturating_add(limit.unwrap_or(DEFAULT_PAGE_LIMIT));
+
+    Page {
+        items: items
+            .into_iter()
+            .skip(usize::try_from(start).unwrap_or(usize::MAX))
+            .take(usize::try_from(end - start).unwrap_or(usize::MAX))
+            .collect(),
+        total,
+        next_cursor: (end < total).then_some(end),
+    }
+}
+
+/// Check that a string is a textual IC principal: lowercase base32 groups of five
+/// separated by dashes, with a shorter final group
+fn validate_icp_principal(principal: &str) -> Result<(), MeroDocsError> {
+    let groups: Vec<&str> = principal.split('-').collect();
+    let (last, rest) = groups
+        .split_last()
+        .ok_or_else(|| MeroDocsError::invalid_input("Principal cannot be empty"))?;
+    let valid_group = |g: &str| {
+        g.chars()
+            .all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+    };
+
+    if principal.len() > 63
+        || rest.is_empty()
+        || rest.iter().any(|g| g.len() != 5 || !valid_group(g))
+        || last.is_empty()
+        || last.len() > 5
+        || !valid_group(last)
+    {
+        return Err(MeroDocsError::invalid_input(format!(
+            "Invalid IC principal: {}",
+            principal
+        )));
+    }
+    Ok(())
+}
+
+/// Helper to compute a hex-encoded SHA-256 digest
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+#[app::logic]
+impl MeroSignState {
+    #[app::init]
+    pub fn init(is_private: bool, context_name: String) -> MeroSignState {
+        let owner_raw = current_caller();
+        let mut state = MeroSignState::empty(is_private, context_name, owner_raw);
+
+        // For shared contexts, add the creator as a participant with admin permissions
+        if !is_private {
+            let _ = state.participants.insert(owner_raw);
+            let _ = state.permissions.insert(owner_raw, PermissionLevel::Admin);
+        }
+
+        state
+    }
+
+    /// State with every collection empty and every setting at its default
+    fn empty(is_private: bool, context_name: String, owner: UserId) -> MeroSignState {
+        MeroSignState {
+            is_private: is_private.into(),
+            owner: owner.into(),
+            context_name: context_name.into(),
+
+            signatures: UnorderedMap::new(),
+            joined_contexts: UnorderedMap::new(),
+            identity_mappings: UnorderedMap::new(),
+            context_health: UnorderedMap::new(),
+            private_notes: UnorderedMap::new(),
+            signature_count: 0u64.into(),
+            signature_limits: SignatureLimits::default().into(),
+            signature_tombstones: UnorderedMap::new(),
+            signature_uses: UnorderedMap::new(),
+            signature_use_limits: SignatureUseLimits::default().into(),
+            recovery_identities: UnorderedSet::new(),
+            recovery_request: None.into(),
+            document_digests: UnorderedMap::new(),
+            participants: UnorderedSet::new(),
+            documents: UnorderedMap::new(),
+            document_chunks: UnorderedMap::new(),
+            document_signatures: UnorderedMap::new(),
+            document_versions: UnorderedMap::new(),
+            document_access_log: UnorderedMap::new(),
+            document_attachments: UnorderedMap::new(),
+            document_declines: UnorderedMap::new(),
+            document_voids: UnorderedMap::new(),
+            signing_delegations: UnorderedMap::new(),
+            document_snoozes: UnorderedMap::new(),
+            pending_actions: UnorderedMap::new(),
+            permissions: UnorderedMap::new(),
+            oversight_roles: UnorderedMap::new(),
+            deactivations: UnorderedMap::new(),
+            roles: UnorderedMap::new(),
+            role_assignments: UnorderedMap::new(),
+            join_requests: UnorderedMap::new(),
+            principal_links: UnorderedMap::new(),
+            folders: UnorderedMap::new(),
+            groups: UnorderedMap::new(),
+            organizations: UnorderedMap::new(),
+            templates: UnorderedMap::new(),
+            recurring_schedules: UnorderedMap::new(),
+            envelopes: UnorderedMap::new(),
+            consents: UnorderedMap::new(),
+            consent_history: UnorderedMap::new(),
+            feature_flags: UnorderedMap::new(),
+            qa_history: UnorderedMap::new(),
+            share_descriptors: UnorderedMap::new(),
+            audit_log: Vector::new(),
+            change_log: Vector::new(),
+            numbering_scheme: None.into(),
+            document_counters: UnorderedMap::new(),
+            embedding_model: None.into(),
+            reindex_job: None.into(),
+            reindex_status: UnorderedMap::new(),
+            blob_announcements: UnorderedMap::new(),
+            keyword_index: UnorderedMap::new(),
+            chunk_context_window: DEFAULT_CHUNK_CONTEXT_WINDOW.into(),
+            archive: None.into(),
+            profile: ContextProfile::default().into(),
+            key_epochs: Vector::new(),
+            key_rotation_due: false.into(),
+        }
+    }
+
+    pub fn is_default_private_context(&self) -> bool {
+        *self.is_private.get() && self.context_name.get() == "default"
+    }
+
+    /// Create a new signature and store its blob ID
+    pub fn create_signature(
+        &mut self,
+        name: String,
+        blob_id_str: String,
+        data_size: u64,
+        format: Option<String>,
+        kind: Option<SignatureKind>,
+    ) -> Result<u64, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signatures can only be created in private context",
+            ));
+        }
+
+        let format = format.map(|f| f.trim().to_ascii_lowercase());
+        self.validate_signature_upload(data_size, format.as_deref())?;
+
+        let signature_id = *self.signature_count.get();
+        self.signature_count.set(signature_id + 1);
+
+        let blob_id = parse_blob_id_base58(&blob_id_str)?;
+
+        // Announce the signature blob to the network for discovery
+        self.announce_blobs(&[blob_id])?;
+
+        let signature = SignatureRecord {
+            id: signature_id,
+            name: name.clone(),
+            blob_id,
+            size: data_size,
+            format,
+            kind: kind.unwrap_or(SignatureKind::Drawn),
+            created_at: TimestampNanos::now(),
+        };
+
+        self.signatures
+            .insert(signature_id.to_string(), signature)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store signature: {:?}", e)))?;
+
+        app::emit!(MeroSignEvent::SignatureCreated {
+            id: signature_id,
+            name,
+            size: data_size,
+        });
+
+        Ok(signature_id)
+    }
+
+    /// Check a signature upload against the configured size and format limits
+    fn validate_signature_upload(
+        &self,
+        data_size: u64,
+        format: Option<&str>,
+    ) -> Result<(), MeroDocsError> {
+        let limits = self.signature_limits.get();
+
+        if data_size == 0 {
+            return Err(MeroDocsError::invalid_input("Signature is empty"));
+        }
+
+        if data_size > limits.max_bytes {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Signature too large: {} bytes exceeds the limit of {} bytes",
+                data_size, limits.max_bytes
+            )));
+        }
+
+        if !limits.allowed_formats.is_empty() {
+            // An omitted format would otherwise slip past the allow-list
+            let Some(format) = format else {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Signature format is required (allowed: {})",
+                    limits.allowed_formats.join(", ")
+                )));
+            };
+            if !limits
+                .allowed_formats
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(format))
+            {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Signature format not allowed: '{}' (allowed: {})",
+                    format,
+                    limits.allowed_formats.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Configure the size and format limits for new signatures
+    pub fn set_signature_limits(
+        &mut self,
+        max_bytes: u64,
+        allowed_formats: Vec<String>,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signature limits can only be set in private context",
+            ));
+        }
+
+        if max_bytes == 0 {
+            return Err(MeroDocsError::invalid_input(
+                "Maximum signature size must be greater than zero",
+            ));
+        }
+
+        self.signature_limits.set(SignatureLimits {
+            max_bytes,
+            allowed_formats: allowed_formats
+                .into_iter()
+                .map(|f| f.trim().to_ascii_lowercase())
+                .filter(|f| !f.is_empty())
+                .collect(),
+        });
+
+        Ok(())
+    }
+
+    /// Get the size and format limits for new signatures
+    pub fn get_signature_limits(&self) -> Result<SignatureLimits, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signature limits can only be accessed in private context",
+            ));
+        }
+
+        Ok(self.signature_limits.get().clone())
+    }
+
+    /// Delete a signature by ID
+    pub fn delete_signature(&mut self, signature_id: u64) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signatures can only be deleted in private context",
+            ));
+        }
+
+        let key = signature_id.to_string();
+
+        match self.signatures.remove(&key) {
+            Ok(Some(signature)) => {
+                let tombstone = SignatureTombstone {
+                    id: signature.id,
+                    name_hash: sha256_hex(signature.name.as_bytes()),
+                    created_at: signature.created_at,
+                    deleted_at: TimestampNanos::now(),
+                };
+                let _ = self.signature_uses.remove(&key);
+                self.signature_tombstones
+                    .insert(key, tombstone)
+                    .map_err(|e| {
+                        MeroDocsError::storage(format!(
+                            "Failed to store signature tombstone: {:?}",
+                            e
+                        ))
+                    })?;
+
+                app::emit!(MeroSignEvent::SignatureDeleted { id: signature_id });
+                Ok(())
+            }
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Signature not found: {}",
+                signature_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to delete signature: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Get all signatures
+    ///
+    /// Signatures are ordered by `sort_by` (creation time by default, oldest first unless
+    /// `descending`), optionally filtered by `kind`, and paginated with `offset`/`limit`.
+    pub fn list_signatures(
+        &self,
+        sort_by: Option<SignatureSortField>,
+        descending: Option<bool>,
+        kind: Option<SignatureKind>,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<SignatureRecord>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signatures can only be accessed in private context",
+            ));
+        }
+
+        let mut signatures = Vec::new();
+        if let Ok(entries) = self.signatures.entries() {
+            for (_, signature) in entries {
+                if kind.is_none_or(|kind| signature.kind == kind) {
+                    signatures.push(signature.clone());
+                }
+            }
+        }
+
+        // Ties fall back to the signature ID so the order is stable between loads
+        match sort_by.unwrap_or(SignatureSortField::CreatedAt) {
+            SignatureSortField::CreatedAt => {
+                signatures.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)))
+            }
+            SignatureSortField::Name => signatures.sort_by(|a, b| {
+                a.name
+                    .to_lowercase()
+                    .cmp(&b.name.to_lowercase())
+                    .then(a.id.cmp(&b.id))
+            }),
+        }
+        if descending.unwrap_or(false) {
+            signatures.reverse();
+        }
+
+        Ok(paginate(signatures, offset, limit))
+    }
+
+    /// Record the sync health of a joined shared context as observed by the client
+    pub fn report_context_health(
+        &mut self,
+        context_id_str: String,
+        reachable: bool,
+        last_sync_at: u64,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Context health can only be reported in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let context_id_key = encode_context_id_base58(&context_id);
+
+        if !self
+            .joined_contexts
+            .contains(&context_id_key)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::not_found("Context not found"));
+        }
+
+        let health = ContextHealth {
+            context_id,
+            reachable,
+            last_sync_at: TimestampNanos::from_nanos(last_sync_at),
+            reported_at: TimestampNanos::now(),
+        };
+
+        self.context_health
+            .insert(context_id_key, health)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store context health: {:?}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// List joined contexts that are unreachable or haven't synced within `threshold` nanoseconds
+    pub fn list_stale_contexts(&self, threshold: u64) -> Result<Vec<StaleContext>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Joined contexts can only be accessed in private context",
+            ));
+        }
+
+        let cutoff = TimestampNanos::from_nanos(env::time_now().saturating_sub(threshold));
+        let mut stale = Vec::new();
+        if let Ok(entries) = self.joined_contexts.entries() {
+            for (key, metadata) in entries {
+                let health = self.context_health.get(&key).ok().flatten();
+                let is_stale = match &health {
+                    Some(health) => !health.reachable || health.last_sync_at < cutoff,
+                    None => true,
+                };
+                if is_stale {
+                    stale.push(StaleContext { metadata, health });
+                }
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Set a personal note on a shared document; an empty note removes it
+    pub fn set_private_note(
+        &mut self,
+        context_id_str: String,
+        document_id: String,
+        note: String,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Private notes can only be managed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let context_id_key = encode_context_id_base58(&context_id);
+
+        if !self
+            .joined_contexts
+            .contains(&context_id_key)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::not_found("Context not found"));
+        }
+
+        let key = format!("{}|{}", context_id_key, document_id);
+
+        if note.trim().is_empty() {
+            self.private_notes.remove(&key).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to remove private note: {:?}", e))
+            })?;
+            return Ok(());
+        }
+
+        let private_note = PrivateNote {
+            context_id,
+            document_id,
+            note,
+            updated_at: TimestampNanos::now(),
+        };
+
+        self.private_notes.insert(key, private_note).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to store private note: {:?}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Get the personal note on a shared document, if any
+    pub fn get_private_note(
+        &self,
+        context_id_str: String,
+        document_id: String,
+    ) -> Result<Option<PrivateNote>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Private notes can only be accessed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let key = format!("{}|{}", encode_context_id_base58(&context_id), document_id);
+
+        self.private_notes
+            .get(&key)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get private note: {:?}", e)))
+    }
+
+    /// List personal notes on documents of a shared context
+    pub fn list_private_notes(
+        &self,
+        context_id_str: String,
+    ) -> Result<Vec<PrivateNote>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Private notes can only be accessed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+
+        let mut notes = Vec::new();
+        if let Ok(entries) = self.private_notes.entries() {
+            for (_, note) in entries {
+                if note.context_id == context_id {
+                    notes.push(note.clone());
+                }
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Get the tombstone left behind by a deleted signature
+    pub fn get_signature_tombstone(
+        &self,
+        signature_id: u64,
+    ) -> Result<SignatureTombstone, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signatures can only be accessed in private context",
+            ));
+        }
+
+        match self.signature_tombstones.get(&signature_id.to_string()) {
+            Ok(Some(tombstone)) => Ok(tombstone),
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Signature tombstone not found: {}",
+                signature_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get signature tombstone: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Record that a stored signature was applied to a document in a shared context.
+    /// Returns how many distinct documents it was applied to within the configured window
+    /// and emits `AnomalousSignatureUse` once that count exceeds the limit.
+    pub fn record_signature_use(
+        &mut self,
+        signature_id: u64,
+        context_id: String,
+        document_id: String,
+    ) -> Result<u64, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signature use can only be recorded in private context",
+            ));
+        }
+
+        let key = signature_id.to_string();
+        if !self.signatures.contains(&key).unwrap_or(false) {
+            return Err(MeroDocsError::not_found(format!(
+                "Signature not found: {}",
+                signature_id
+            )));
+        }
+
+        let now = env::time_now();
+        let limits = self.signature_use_limits.get().clone();
+        let window_start = TimestampNanos::from_nanos(now.saturating_sub(limits.window_nanos));
+
+        // Keep only applications still inside the window so the log stays bounded
+        let mut recent = Vec::new();
+        if let Ok(Some(uses)) = self.signature_uses.get(&key) {
+            if let Ok(iter) = uses.iter() {
+                for entry in iter {
+                    if entry.applied_at >= window_start {
+                        recent.push(entry);
+                    }
+                }
+            }
+        }
+        recent.push(SignatureUse {
+            signature_id,
+            context_id,
+            document_id,
+            applied_at: TimestampNanos::from_nanos(now),
+        });
+
+        let mut documents: Vec<(&str, &str)> = recent
+            .iter()
+            .map(|u| (u.context_id.as_str(), u.document_id.as_str()))
+            .collect();
+        documents.sort_unstable();
+        documents.dedup();
+        let documents_in_window = documents.len() as u64;
+
+        let mut uses = Vector::new();
+        for entry in recent {
+            uses.push(entry).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to record signature use: {:?}", e))
+            })?;
+        }
+        self.signature_uses.insert(key, uses).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to record signature use: {:?}", e))
+        })?;
+
+        if documents_in_window > limits.max_documents {
+            app::log!(
+                "Signature {} applied to {} documents within the window (limit {})",
+                signature_id,
+                documents_in_window,
+                limits.max_documents
+            );
+            app::emit!(MeroSignEvent::AnomalousSignatureUse {
+                signature_id,
+                documents_in_window,
+                max_documents: limits.max_documents,
+                window_nanos: limits.window_nanos,
+            });
+        }
+
+        Ok(documents_in_window)
+    }
+
+    /// Configure how many documents a signature may be applied to per window before alerting
+    pub fn set_signature_use_limits(
+        &mut self,
+        max_documents: u64,
+        window_nanos: u64,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signature use limits can only be set in private context",
+            ));
+        }
+
+        if max_documents == 0 {
+            return Err(MeroDocsError::invalid_input(
+                "Maximum documents per window must be greater than zero",
+            ));
+        }
+        if window_nanos == 0 {
+            return Err(MeroDocsError::invalid_input(
+                "Signature use window must be greater than zero",
+            ));
+        }
+
+        self.signature_use_limits.set(SignatureUseLimits {
+            max_documents,
+            window_nanos,
+        });
+
+        Ok(())
+    }
+
+    /// Get the signature application limits
+    pub fn get_signature_use_limits(&self) -> Result<SignatureUseLimits, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Signature use limits can only be accessed in private context",
+            ));
+        }
+
+        Ok(self.signature_use_limits.get().clone())
+    }
+
+    /// Join a shared context with identity mapping
+    pub fn join_shared_context(
+        &mut self,
+        context_id_str: String,
+        shared_identity_str: String,
+        context_name: String,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Context joining can only be managed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let context_id_key = encode_context_id_base58(&context_id);
+
+        if self
+            .joined_contexts
+            .contains(&context_id_key)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::already_exists("Already joined this context"));
+        }
+
+        let private_identity = *self.owner.get();
+        let shared_identity = parse_public_key_base58(&shared_identity_str)?;
+
+        let metadata = ContextMetadata {
+            context_id,
+            context_name: context_name.clone(),
+            role: ParticipantRole::Unknown,
+            joined_at: TimestampNanos::now(),
+            private_identity,
+            shared_identity,
+        };
+
+        let identity_mapping = IdentityMapping {
+            private_identity,
+            shared_identity,
+            context_id,
+            created_at: TimestampNanos::now(),
+        };
+
+        self.joined_contexts
+            .insert(context_id_key.clone(), metadata)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to join context: {:?}", e)))?;
+
+        self.identity_mappings
+            .insert(context_id_key.clone(), identity_mapping)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store identity mapping: {:?}", e))
+            })?;
+
+        app::emit!(MeroSignEvent::ContextJoined {
+            context_id: context_id_str,
+            context_name
+        });
+        Ok(())
+    }
+
+    /// Leave a shared context
+    pub fn leave_shared_context(&mut self, context_id_str: String) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Context leaving can only be managed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let context_id_key = encode_context_id_base58(&context_id);
+
+        match self.joined_contexts.remove(&context_id_key) {
+            Ok(Some(_)) => {
+                let _ = self.identity_mappings.remove(&context_id_key);
+                let _ = self.context_health.remove(&context_id_key);
+                let note_prefix = format!("{}|", context_id_key);
+                let mut note_keys = Vec::new();
+                if let Ok(entries) = self.private_notes.entries() {
+                    for (key, _) in entries {
+                        if key.starts_with(&note_prefix) {
+                            note_keys.push(key);
+                        }
+                    }
+                }
+                for key in note_keys {
+                    let _ = self.private_notes.remove(&key);
+                }
+                app::emit!(MeroSignEvent::ContextLeft {
+                    context_id: context_id_str
+                });
+                Ok(())
+            }
+            Ok(None) => Err(MeroDocsError::not_found("Context not found")),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to leave context: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// List all joined contexts
+    pub fn list_joined_contexts(&self) -> Result<Vec<ContextMetadata>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Joined contexts can only be accessed in private context",
+            ));
+        }
+
+        let mut contexts = Vec::new();
+        if let Ok(entries) = self.joined_contexts.entries() {
+            for (_, metadata) in entries {
+                contexts.push(metadata.clone());
+            }
+        }
+        Ok(contexts)
+    }
+
+    /// Remember that a joined context holds a document with `hash`. Index the hash the
+    /// document was uploaded with so copies of the same contract match across contexts.
+    pub fn index_context_document(
+        &mut self,
+        context_id_str: String,
+        document_id: String,
+        name: String,
+        hash: String,
+    ) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Documents can only be indexed in private context",
+            ));
+        }
+
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let context_id_key = encode_context_id_base58(&context_id);
+        if !self
+            .joined_contexts
+            .contains(&context_id_key)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::not_found("Context not joined"));
+        }
+
+        let hash = hash.trim().to_lowercase();
+        if hash.is_empty() {
+            return Err(MeroDocsError::invalid_input("Hash cannot be empty"));
+        }
+
+        let mut entries = Vec::new();
+        if let Ok(Some(indexed)) = self.document_digests.get(&hash) {
+            if let Ok(iter) = indexed.iter() {
+                entries.extend(iter.filter(|entry| {
+                    entry.context_id != context_id_key || entry.document_id != document_id
+                }));
+            }
+        }
+        entries.push(IndexedDocument {
+            context_id: context_id_key,
+            document_id,
+            name,
+            indexed_at: TimestampNanos::now(),
+        });
+
+        let mut indexed = Vector::new();
+        for entry in entries {
+            indexed.push(entry).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to index document: {:?}", e))
+            })?;
+        }
+        self.document_digests.insert(hash, indexed).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to update document index: {:?}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Joined contexts holding a document with `hash`, from the local index. More than one
+    /// match means the same contract is being handled in parallel contexts.
+    pub fn find_contexts_with_document(
+        &self,
+        hash: String,
+    ) -> Result<Vec<DocumentContextMatch>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Document index can only be searched in private context",
+            ));
+        }
+
+        let mut matches = Vec::new();
+        if let Ok(Some(indexed)) = self.document_digests.get(&hash.trim().to_lowercase()) {
+            if let Ok(iter) = indexed.iter() {
+                for entry in iter {
+                    // Contexts left since the document was indexed no longer count
+                    let Ok(Some(metadata)) = self.joined_contexts.get(&entry.context_id) else {
+                        continue;
+                    };
+                    matches.push(DocumentContextMatch {
+                        context_id: entry.context_id,
+                        context_name: metadata.context_name,
+                        document_id: entry.document_id,
+                        name: entry.name,
+                        indexed_at: entry.indexed_at,
+                    });
+                }
+            }
+        }
+        Ok(matches)
+    }
+
+    // === RECOVERY METHODS ===
+
+    fn validate_private_owner(&self) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+        if current_caller() != *self.owner.get() {
+            return Err(MeroDocsError::unauthorized(
+                "Only the context owner can manage recovery",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Register an identity that may recover this private context (owner only)
+    pub fn add_recovery_identity(&mut self, user_id_str: String) -> Result<(), MeroDocsError> {
+        self.validate_private_owner()?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if user_id == *self.owner.get() {
+            return Err(MeroDocsError::invalid_input(
+                "The owner cannot be its own recovery identity",
+            ));
+        }
+        if self.recovery_identities.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Recovery identity already registered",
+            ));
+        }
+
+        self.recovery_identities.insert(user_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to add recovery identity: {:?}", e))
+        })?;
+
+        app::emit!(MeroSignEvent::RecoveryIdentityAdded { user_id });
+
+        Ok(())
+    }
+
+    /// Remove a registered recovery identity (owner only)
+    pub fn remove_recovery_identity(&mut self, user_id_str: String) -> Result<(), MeroDocsError> {
+        self.validate_private_owner()?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if !self.recovery_identities.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("Recovery identity not found"));
+        }
+
+        self.recovery_identities.remove(&user_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to remove recovery identity: {:?}", e))
+        })?;
+
+        // A pending request from a revoked identity can no longer complete
+        if let Some(request) = self.recovery_request.get() {
+            if request.requested_by == user_id {
+                self.recovery_request.set(None);
+            }
+        }
+
+        app::emit!(MeroSignEvent::RecoveryIdentityRemoved { user_id });
+
+        Ok(())
+    }
+
+    /// List registered recovery identities
+    pub fn list_recovery_identities(&self) -> Result<Vec<UserId>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+
+        let mut identities = Vec::new();
+        if let Ok(iter) = self.recovery_identities.iter() {
+            for identity in iter {
+                identities.push(identity);
+            }
+        }
+        Ok(identities)
+    }
+
+    /// Start the time-locked recovery challenge (recovery identity only)
+    pub fn initiate_recovery(&mut self) -> Result<RecoveryRequest, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+
+        let requested_by = current_caller();
+        if !self
+            .recovery_identities
+            .contains(&requested_by)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::unauthorized(
+                "Caller is not a registered recovery identity",
+            ));
+        }
+        if self.recovery_request.get().is_some() {
+            return Err(MeroDocsError::already_exists(
+                "A recovery request is already pending",
+            ));
+        }
+
+        let requested_at = TimestampNanos::now();
+        let request = RecoveryRequest {
+            requested_by,
+            requested_at,
+            unlocks_at: requested_at.plus_nanos(RECOVERY_TIMELOCK_NANOS),
+        };
+        self.recovery_request.set(Some(request.clone()));
+
+        app::emit!(MeroSignEvent::RecoveryInitiated {
+            requested_by,
+            unlocks_at: request.unlocks_at.as_nanos(),
+        });
+
+        Ok(request)
+    }
+
+    /// Cancel a pending recovery request (owner, or the identity that started it)
+    pub fn cancel_recovery(&mut self) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+
+        let request = match self.recovery_request.get() {
+            Some(request) => request.clone(),
+            None => return Err(MeroDocsError::not_found("No recovery request is pending")),
+        };
+
+        let caller = current_caller();
+        if caller != *self.owner.get() && caller != request.requested_by {
+            return Err(MeroDocsError::unauthorized(
+                "Only the owner or the requesting identity can cancel recovery",
+            ));
+        }
+
+        self.recovery_request.set(None);
+
+        app::emit!(MeroSignEvent::RecoveryCancelled {
+            requested_by: request.requested_by,
+        });
+
+        Ok(())
+    }
+
+    /// Get the pending recovery request, if any
+    pub fn get_recovery_request(&self) -> Result<Option<RecoveryRequest>, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+
+        Ok(self.recovery_request.get().clone())
+    }
+
+    /// Take over ownership once the recovery time lock has elapsed (requesting identity only)
+    pub fn complete_recovery(&mut self) -> Result<(), MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Recovery can only be managed in private context",
+            ));
+        }
+
+        let request = match self.recovery_request.get() {
+            Some(request) => request.clone(),
+            None => return Err(MeroDocsError::not_found("No recovery request is pending")),
+        };
+
+        let new_owner = current_caller();
+        if new_owner != request.requested_by {
+            return Err(MeroDocsError::unauthorized(
+                "Only the identity that initiated recovery can complete it",
+            ));
+        }
+        if !self
+            .recovery_identities
+            .contains(&new_owner)
+            .unwrap_or(false)
+        {
+            return Err(MeroDocsError::unauthorized(
+                "Caller is no longer a registered recovery identity",
+            ));
+        }
+        if !request.unlocks_at.has_passed(TimestampNanos::now()) {
+            return Err(MeroDocsError::invalid_state(format!(
+                "Recovery is time-locked until {}",
+                request.unlocks_at.as_nanos()
+            )));
+        }
+
+        let previous_owner = *self.owner.get();
+        self.owner.set(new_owner);
+
+        // Re-point the joined-context records at the new private identity
+        let mut contexts_to_update = Vec::new();
+        if let Ok(entries) = self.joined_contexts.entries() {
+            for (key, metadata) in entries {
+                contexts_to_update.push((key, metadata));
+            }
+        }
+        for (key, mut metadata) in contexts_to_update {
+            metadata.private_identity = new_owner;
+            let _ = self.joined_contexts.insert(key, metadata);
+        }
+
+        let mut mappings_to_update = Vec::new();
+        if let Ok(entries) = self.identity_mappings.entries() {
+            for (key, mapping) in entries {
+                mappings_to_update.push((key, mapping));
+            }
+        }
+        for (key, mut mapping) in mappings_to_update {
+            mapping.private_identity = new_owner;
+            let _ = self.identity_mappings.insert(key, mapping);
+        }
+
+        let _ = self.recovery_identities.remove(&new_owner);
+        self.recovery_request.set(None);
+
+        app::emit!(MeroSignEvent::OwnershipRecovered {
+            previous_owner,
+            new_owner,
+        });
+
+        Ok(())
+    }
+
+    // === SHARED CONTEXT METHODS ===
+
+    /// Get detailed information about the shared context
+    pub fn get_context_details(
+        &self,
+        context_id_str: String,
+    ) -> Result<ContextDetails, MeroDocsError> {
+        let context_id = parse_context_id_base58(&context_id_str)?;
+        let mut participants_with_permissions = Vec::new();
+
+        if let Ok(iter) = self.participants.iter() {
+            for participant in iter {
+                let permission = self
+                    .permissions
+                    .get(&participant)
+                    .map_err(|e| {
+                        MeroDocsError::storage(format!(
+                            "Failed to get permission for user: {:?}",
+                            e
+                        ))
+                    })?
+                    .unwrap_or(PermissionLevel::Read);
+
+                let oversight_role = self.oversight_roles.get(&participant).map_err(|e| {
+                    MeroDocsError::storage(format!(
+                        "Failed to get oversight role for user: {:?}",
+                        e
+                    ))
+                })?;
+
+                let deactivation = self.deactivations.get(&participant).map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to get deactivation for user: {:?}", e))
+                })?;
+
+                participants_with_permissions.push(ParticipantInfo {
+                    user_id: participant.clone(),
+                    permission_level: permission,
+                    oversight_role,
+                    deactivation,
+                });
+            }
+        }
+
+        let document_count =
+            self.documents.len().map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get document count: {:?}", e))
+            })? as u64;
+
+        let context_details = ContextDetails {
+            context_id,
+            context_name: self.context_name.get().clone(),
+            owner: *self.owner.get(),
+            is_private: *self.is_private.get(),
+            participant_count: participants_with_permissions.len() as u64,
+            participants: participants_with_permissions,
+            document_count,
+            created_at: TimestampNanos::now(),
+            archive: self.archive.get().clone(),
+            profile: self.profile.get().clone(),
+        };
+
+        Ok(context_details)
+    }
+
+    /// Check that the current user holds `capability` in a shared context
+    fn require_capability(&self, capability: Capabilities) -> Result<(), MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "This method can only be called from shared context",
+            ));
+        }
+
+        let current_user = current_caller();
+        match self.permissions.get(&current_user) {
+            Ok(Some(_)) if self.has_capability(&current_user, capability) => Ok(()),
+            Ok(Some(_)) => Err(MeroDocsError::unauthorized(format!(
+                "Missing capability for this operation: {}",
+                roles::capability_names(capability).join(", ")
+            ))),
+            Ok(None) => Err(MeroDocsError::not_found("User permissions not found")),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to check user permissions: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Reject mutations while the context is archived or from a deactivated participant
+    fn ensure_writable(&self) -> Result<(), MeroDocsError> {
+        if self.archive.get().is_some() {
+            return Err(MeroDocsError::ContextArchived);
+        }
+        if self.is_deactivated(&current_caller()) {
+            return Err(MeroDocsError::unauthorized(
+                "Deactivated participants cannot make changes",
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_deactivated(&self, user_id: &UserId) -> bool {
+        self.deactivations.contains(user_id).unwrap_or(false)
+    }
+
+    /// Capabilities of a user: those of their assigned role, or the defaults of their
+    /// permission level
+    fn capabilities_of(&self, user: &UserId) -> Capabilities {
+        if let Some(capabilities) = self.assigned_role_capabilities(user) {
+            return capabilities;
+        }
+        match self.permissions.get(user) {
+            Ok(Some(level)) => roles::default_capabilities(&level),
+            _ => 0,
+        }
+    }
+
+    /// Capabilities of the custom role assigned to a user, if any
+    fn assigned_role_capabilities(&self, user: &UserId) -> Option<Capabilities> {
+        let assignment = self.role_assignments.get(user).ok().flatten()?;
+        let role = self.roles.get(&assignment.role).ok().flatten()?;
+        Some(role.capabilities)
+    }
+
+    /// Fail unless some active participant would still hold the manage capability after a
+    /// change. `changed` returns the capabilities a participant would hold afterwards, or
+    /// `None` when the change leaves them as they are.
+    fn ensure_manager_remains(
+        &self,
+        changed: impl Fn(&UserId) -> Option<Capabilities>,
+    ) -> Result<(), MeroDocsError> {
+        let remains = self.participants.iter().is_ok_and(|mut participants| {
+            participants.any(|participant| {
+                !self.is_deactivated(&participant)
+                    && changed(&participant).unwrap_or_else(|| self.capabilities_of(&participant))
+                        & roles::MANAGE
+                        != 0
+            })
+        });
+        if remains {
+            Ok(())
+        } else {
+            Err(MeroDocsError::invalid_state(
+                "At least one active participant must keep the manage capability",
+            ))
+        }
+    }
+
+    fn has_capability(&self, user: &UserId, capability: Capabilities) -> bool {
+        self.capabilities_of(user) & capability == capability
+    }
+
+    /// Announce a batch of blobs to the current context, skipping repeats within the batch
+    /// and blobs that were announced successfully moments ago. Returns how many were announced.
+    fn announce_blobs(&mut self, blobs: &[BlobId]) -> Result<u64, MeroDocsError> {
+        let now = env::time_now();
+        let current_context = env::context_id();
+        let mut announced = 0u64;
+
+        for blob_id in announcements::dedupe(blobs) {
+            let key = bs58::encode(&blob_id).into_string();
+            let previous = self.blob_announcements.get(&key).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get blob announcement: {:?}", e))
+            })?;
+            if !announcements::is_due(previous.as_ref(), now) {
+                continue;
+            }
+
+            let succeeded = env::blob_announce_to_context(&blob_id, &current_context);
+            if succeeded {
+                app::log!("Successfully announced blob {} to network", key);
+                announced += 1;
+            } else {
+                app::log!("Failed to announce blob {} to network", key);
+            }
+
+            let record = BlobAnnouncement {
+                blob_id,
+                last_announced_at: TimestampNanos::from_nanos(now),
+                succeeded,
+                announce_count: previous.map_or(0, |p| p.announce_count) + 1,
+            };
+            self.blob_announcements.insert(key, record).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to record blob announcement: {:?}", e))
+            })?;
+        }
+
+        Ok(announced)
+    }
+
+    /// Re-announce the blobs this context references whose last announcement failed or is
+    /// older than the throttle window. Returns how many were announced.
+    pub fn reannounce_blobs(&mut self) -> Result<u64, MeroDocsError> {
+        let mut blobs = Vec::new();
+        if *self.is_private.get() {
+            if let Ok(entries) = self.signatures.entries() {
+                for (_, signature) in entries {
+                    blobs.push(signature.blob_id);
+                }
+            }
+        } else {
+            let executor_id = current_caller();
+            if !self.participants.contains(&executor_id).unwrap_or(false) {
+                return Err(MeroDocsError::unauthorized(
+                    "Only participants can re-announce blobs",
+                ));
+            }
+            if let Ok(entries) = self.documents.entries() {
+                for (_, document) in entries {
+                    blobs.push(document.pdf_blob_id);
+                }
+            }
+            if let Ok(entries) = self.document_attachments.entries() {
+                for (_, attachments) in entries {
+                    if let Ok(iter) = attachments.iter() {
+                        blobs.extend(iter.map(|attachment| attachment.blob_id));
+                    }
+                }
+            }
+        }
+
+        self.announce_blobs(&blobs)
+    }
+
+    /// Get the last recorded announcement of a blob
+    pub fn get_blob_announcement(
+        &self,
+        blob_id_str: String,
+    ) -> Result<Option<BlobAnnouncement>, MeroDocsError> {
+        let blob_id = parse_blob_id_base58(&blob_id_str)?;
+        self.blob_announcements
+            .get(&bs58::encode(&blob_id).into_string())
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get blob announcement: {:?}", e))
+            })
+    }
+
+    /// Describe the registered embedding model for error messages
+    fn embedding_model_label(&self) -> String {
+        if let Some(job) = self.reindex_job.get() {
+            return format!(
+                " (re-indexing to model '{}', dim {})",
+                job.target_model, job.target_dimension
+            );
+        }
+        match self.embedding_model.get() {
+            Some(model) => format!(" (model '{}', dim {})", model.name, model.dimension),
+            None => String::new(),
+        }
+    }
+
+    /// Turn client-supplied embeddings into their stored form, quantizing them when the
+    /// `quantized_embeddings` feature is on
+    fn prepare_embeddings(
+        &self,
+        embeddings: Option<Vec<f32>>,
+        chunks: Option<Vec<DocumentChunk>>,
+    ) -> (Option<Embedding>, Vec<DocumentChunk>) {
+        let quantize = self.feature_enabled("quantized_embeddings");
+        let store = |embedding: Embedding| {
+            if quantize {
+                embedding.quantize()
+            } else {
+                embedding
+            }
+        };
+
+        let embeddings = embeddings.map(|values| store(Embedding::Float(values)));
+        let chunks = chunks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut chunk| {
+                chunk.embedding = store(chunk.embedding);
+                chunk
+            })
+            .collect();
+        (embeddings, chunks)
+    }
+
+    /// Convert up to `limit` documents' full-precision embeddings to int8 (admin only).
+    /// Call repeatedly until `remaining` is zero.
+    pub fn quantize_existing_embeddings(
+        &mut self,
+        limit: Option<u64>,
+    ) -> Result<QuantizationProgress, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let limit = limit.unwrap_or(DEFAULT_QUANTIZATION_BATCH);
+        let mut pending = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                let document_pending = document
+                    .embeddings
+                    .as_ref()
+                    .is_some_and(|e| !e.is_quantized());
+                let chunks_pending = self
+                    .load_document_chunks(&id)?
+                    .iter()
+                    .any(|chunk| !chunk.embedding.is_quantized());
+                if document_pending || chunks_pending {
+                    pending.push((document, chunks_pending));
+                }
+            }
+        }
+
+        let total = pending.len() as u64;
+        let mut converted = 0u64;
+        for (mut document, chunks_pending) in pending {
+            if converted >= limit {
+                break;
+            }
+
+            if chunks_pending {
+                let chunks: Vec<DocumentChunk> = self
+                    .load_document_chunks(&document.id)?
+                    .into_iter()
+                    .map(|mut chunk| {
+                        chunk.embedding = chunk.embedding.quantize();
+                        chunk
+                    })
+                    .collect();
+                self.store_document_chunks(&document.id, chunks)?;
+            }
+            if document
+                .embeddings
+                .as_ref()
+                .is_some_and(|e| !e.is_quantized())
+            {
+                document.embeddings = document.embeddings.map(Embedding::quantize);
+                self.save_document(document)?;
+            }
+            converted += 1;
+        }
+
+        if converted > 0 {
+            self.record_audit(
+                AuditAction::EmbeddingsQuantized,
+                "context",
+                format!("converted={} remaining={}", converted, total - converted),
+            )?;
+        }
+
+        Ok(QuantizationProgress {
+            converted,
+            remaining: total - converted,
+        })
+    }
+
+    /// Load every stored chunk of a document, in order
+    fn load_document_chunks(&self, document_id: &str) -> Result<Vec<DocumentChunk>, MeroDocsError> {
+        let mut chunks = Vec::new();
+        match self.document_chunks.get(document_id) {
+            Ok(Some(stored)) => {
+                if let Ok(iter) = stored.iter() {
+                    for chunk in iter {
+                        chunks.push(chunk);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document chunks: {:?}",
+                    e
+                )))
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Replace the stored chunks of a document; an empty list removes them
+    fn store_document_chunks(
+        &mut self,
+        document_id: &str,
+        chunks: Vec<DocumentChunk>,
+    ) -> Result<(), MeroDocsError> {
+        if chunks.is_empty() {
+            let _ = self.document_chunks.remove(document_id);
+            return Ok(());
+        }
+
+        let mut stored = Vector::new();
+        for chunk in chunks {
+            stored.push(chunk).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store document chunk: {:?}", e))
+            })?;
+        }
+        self.document_chunks
+            .insert(document_id.to_string(), stored)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store document chunks: {:?}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Embedding dimension of a document's first stored chunk
+    fn first_chunk_dimension(&self, document_id: &str) -> Option<usize> {
+        match self.document_chunks.get(document_id) {
+            Ok(Some(stored)) => match stored.get(0) {
+                Ok(Some(chunk)) => Some(chunk.embedding.len()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Page through the stored chunks of a document
+    pub fn get_document_chunks(
+        &self,
+        document_id: String,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<DocumentChunk>, MeroDocsError> {
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
+        }
+        self.get_visible_document(&document_id)?;
+
+        let stored = match self.document_chunks.get(&document_id) {
+            Ok(Some(stored)) => stored,
+            Ok(None) => {
+                return Ok(Page {
+                    items: Vec::new(),
+                    total: 0,
+                    next_cursor: None,
+                })
+            }
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document chunks: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let total = stored.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get document chunks: {:?}", e))
+        })? as u64;
+        let start = offset.unwrap_or(0);
+        let end = start
+            .saturating_add(limit.unwrap_or(DEFAULT_PAGE_LIMIT))
+            .min(total);
+
+        // Only the requested window is read from storage
+        let mut items = Vec::new();
+        for index in start..end {
+            match stored.get(index as usize) {
+                Ok(Some(chunk)) => items.push(chunk),
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to get document chunk: {:?}",
+                        e
+                    )))
+                }
+            }
+        }
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor: (end < total).then_some(end),
+        })
+    }
+
+    /// Check that document and chunk embeddings match the registered embedding model
+    fn validate_embeddings(
+        &self,
+        embeddings: &Option<Vec<f32>>,
+        chunks: &Option<Vec<DocumentChunk>>,
+    ) -> Result<(), MeroDocsError> {
+        let registered = match self.reindex_job.get() {
+            Some(job) => Some(job.target_dimension),
+            None => self.embedding_model.get().as_ref().map(|m| m.dimension),
+        };
+        let expected = match registered {
+            Some(dimension) => Some(dimension as usize),
+            None => chunks
+                .as_ref()
+                .and_then(|chunks| chunks.first())
+                .map(|chunk| chunk.embedding.len()),
+        };
+
+        let Some(expected) = expected else {
+            return Ok(());
+        };
+
+        if let Some(embedding) = embeddings {
+            if embedding.len() != expected {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Embedding dimension mismatch: document={}, expected={}{}",
+                    embedding.len(),
+                    expected,
+                    self.embedding_model_label()
+                )));
+            }
+        }
+
+        if let Some(chunks) = chunks {
+            for (index, chunk) in chunks.iter().enumerate() {
+                if chunk.embedding.len() != expected {
+                    return Err(MeroDocsError::invalid_input(format!(
+                        "Embedding dimension mismatch: chunk {}={}, expected={}{}",
+                        index,
+                        chunk.embedding.len(),
+                        expected,
+                        self.embedding_model_label()
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Register the embedding model used for this context's chunks (admin only)
+    pub fn set_embedding_model(
+        &mut self,
+        name: String,
+        dimension: u64,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if self.reindex_job.get().is_some() {
+            return Err(MeroDocsError::invalid_state(
+                "Cannot change embedding model while a re-index is in progress",
+            ));
+        }
+
+        if name.trim().is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Embedding model name cannot be empty",
+            ));
+        }
+        if dimension == 0 {
+            return Err(MeroDocsError::invalid_input(
+                "Embedding dimension must be greater than zero",
+            ));
+        }
+
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                let existing_dimension = self
+                    .first_chunk_dimension(&document.id)
+                    .or_else(|| document.embeddings.as_ref().map(|e| e.len()));
+
+                if let Some(existing_dimension) = existing_dimension {
+                    if existing_dimension as u64 != dimension {
+                        return Err(MeroDocsError::invalid_input(format!(
+                            "Document '{}' has embeddings of dimension {}, which does not match {}",
+                            document.id, existing_dimension, dimension
+                        )));
+                    }
+                }
+            }
+        }
+
+        self.embedding_model.set(Some(EmbeddingModel {
+            name: name.clone(),
+            dimension,
+            set_by: current_caller(),
+            set_at: TimestampNanos::now(),
+        }));
+
+        self.record_audit(
+            AuditAction::EmbeddingModelSet,
+            "context",
+            format!("model={} dimension={}", name, dimension),
+        )?;
+
+        app::emit!(MeroSignEvent::EmbeddingModelSet { name, dimension });
+
+        Ok(())
+    }
+
+    /// Get the embedding model registered for this context
+    pub fn get_embedding_model(&self) -> Result<Option<EmbeddingModel>, MeroDocsError> {
+        Ok(self.embedding_model.get().clone())
+    }
+
+    /// Start migrating to a new embedding model; existing chunks are marked stale (admin only)
+    pub fn begin_reindex(
+        &mut self,
+        new_model: String,
+        dimension: u64,
+    ) -> Result<u64, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if self.reindex_job.get().is_some() {
+            return Err(MeroDocsError::invalid_state(
+                "A re-index is already in progress",
+            ));
+        }
+        if new_model.trim().is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Embedding model name cannot be empty",
+            ));
+        }
+        if dimension == 0 {
+            return Err(MeroDocsError::invalid_input(
+                "Embedding dimension must be greater than zero",
+            ));
+        }
+
+        let mut stale_ids = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                if document.embeddings.is_some()
+                    || self.document_chunks.contains(&id).unwrap_or(false)
+                {
+                    stale_ids.push(id);
+                }
+            }
+        }
+
+        for id in &stale_ids {
+            self.reindex_status
+                .insert(id.clone(), ReindexStatus::Stale)
+                .map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to mark document stale: {:?}", e))
+                })?;
+        }
+
+        self.reindex_job.set(Some(ReindexJob {
+            target_model: new_model.clone(),
+            target_dimension: dimension,
+            previous_model: self.embedding_model.get().clone(),
+            started_by: current_caller(),
+            started_at: TimestampNanos::now(),
+        }));
+
+        let stale_documents = stale_ids.len() as u64;
+        self.record_audit(
+            AuditAction::ReindexStarted,
+            "context",
+            format!(
+                "model={} dimension={} stale={}",
+                new_model, dimension, stale_documents
+            ),
+        )?;
+
+        app::emit!(MeroSignEvent::ReindexStarted {
+            target_model: new_model,
+            target_dimension: dimension,
+            stale_documents,
+        });
+
+        self.finish_reindex_if_complete()?;
+
+        Ok(stale_documents)
+    }
+
+    /// Replace a document's embeddings with ones produced by the re-index target model (admin only)
+    pub fn submit_reindexed_chunks(
+        &mut self,
+        document_id: String,
+        embeddings: Option<Vec<f32>>,
+        chunks: Option<Vec<DocumentChunk>>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if self.reindex_job.get().is_none() {
+            return Err(MeroDocsError::invalid_state("No re-index is in progress"));
+        }
+        if embeddings.is_none() && chunks.is_none() {
+            return Err(MeroDocsError::invalid_input(
+                "Re-indexed embeddings or chunks are required",
+            ));
+        }
+
+        self.validate_embeddings(&embeddings, &chunks)?;
+
+        let mut document = self.get_visible_document(&document_id)?;
+
+        let previous_chunks = self.load_document_chunks(&document_id)?;
+        self.unindex_document_keywords(&document, &previous_chunks)?;
+        let (embeddings, chunks) = self.prepare_embeddings(embeddings, chunks);
+        document.embeddings = embeddings;
+        self.index_document_keywords(&document, &chunks)?;
+        self.store_document_chunks(&document_id, chunks)?;
+
+        self.save_document(document)?;
+
+        self.reindex_status
+            .insert(document_id.clone(), ReindexStatus::Reindexed)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update re-index status: {:?}", e))
+            })?;
+
+        self.record_audit(AuditAction::DocumentReindexed, &document_id, String::new())?;
+
+        app::emit!(MeroSignEvent::DocumentReindexed { document_id });
+
+        self.finish_reindex_if_complete()?;
+
+        Ok(())
+    }
+
+    /// Get progress of the current re-index, if any
+    pub fn get_reindex_status(&self) -> Result<Option<ReindexProgress>, MeroDocsError> {
+        let Some(job) = self.reindex_job.get().clone() else {
+            return Ok(None);
+        };
+
+        let mut total_documents = 0;
+        let mut reindexed_documents = 0;
+        let mut stale_document_ids = Vec::new();
+        if let Ok(entries) = self.reindex_status.entries() {
+            for (id, status) in entries {
+                total_documents += 1;
+                match status {
+                    ReindexStatus::Reindexed => reindexed_documents += 1,
+                    ReindexStatus::Stale => stale_document_ids.push(id),
+                }
+            }
+        }
+
+        Ok(Some(ReindexProgress {
+            job,
+            total_documents,
+            reindexed_documents,
+            stale_document_ids,
+        }))
+    }
+
+    /// Check whether a document still carries embeddings from the previous model
+    fn is_document_stale(&self, document_id: &str) -> bool {
+        self.reindex_job.get().is_some()
+            && matches!(
+                self.reindex_status.get(document_id),
+                Ok(Some(ReindexStatus::Stale))
+            )
+    }
+
+    /// Switch to the target model once every stale document has been re-indexed
+    fn finish_reindex_if_complete(&mut self) -> Result<(), MeroDocsError> {
+        let Some(job) = self.reindex_job.get().clone() else {
+            return Ok(());
+        };
+
+        if let Ok(entries) = self.reindex_status.entries() {
+            for (_, status) in entries {
+                if status == ReindexStatus::Stale {
+                    return Ok(());
+                }
+            }
+        }
+
+        self.embedding_model.set(Some(EmbeddingModel {
+            name: job.target_model.clone(),
+            dimension: job.target_dimension,
+            set_by: job.started_by,
+            set_at: TimestampNanos::now(),
+        }));
+        self.reindex_job.set(None);
+        self.reindex_status.clear().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to clear re-index status: {:?}", e))
+        })?;
+
+        app::emit!(MeroSignEvent::ReindexCompleted {
+            model: job.target_model,
+            dimension: job.target_dimension,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the numbering scheme applied to newly uploaded documents (admin only)
+    pub fn set_numbering_scheme(
+        &mut self,
+        prefix: String,
+        padding: u32,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if padding > 12 {
+            return Err(MeroDocsError::limit_exceeded(
+                "Document number padding cannot exceed 12 digits",
+            ));
+        }
+
+        self.record_audit(
+            AuditAction::NumberingSchemeChanged,
+            "context",
+            format!("prefix={} padding={}", prefix, padding),
+        )?;
+
+        self.numbering_scheme
+            .set(Some(NumberingScheme { prefix, padding }));
+
+        Ok(())
+    }
+
+    /// Get the numbering scheme applied to new documents
+    pub fn get_numbering_scheme(&self) -> Result<Option<NumberingScheme>, MeroDocsError> {
+        Ok(self.numbering_scheme.get().clone())
+    }
+
+    /// Issue the next document number, if a numbering scheme is configured.
+    /// Each member counts the numbers it issued in its own register, so concurrent
+    /// uploads never overwrite each other's count; the next number follows the total.
+    /// Members uploading concurrently can reach the same total, so the number ends with
+    /// the issuer to stay unique.
+    fn next_document_number(&mut self) -> Result<Option<String>, MeroDocsError> {
+        let Some(scheme) = self.numbering_scheme.get().clone() else {
+            return Ok(None);
+        };
+
+        let issuer = bs58::encode(current_caller()).into_string();
+        let issuer_suffix: String = issuer.chars().take(DOCUMENT_NUMBER_ISSUER_CHARS).collect();
+        let mut issued_total = 0u64;
+        let mut issued_by_caller = 0u64;
+        let entries = self.document_counters.entries().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to read document counters: {:?}", e))
+        })?;
+        for (member, issued) in entries {
+            issued_total += *issued.get();
+            if member == issuer {
+                issued_by_caller = *issued.get();
+            }
+        }
+
+        self.document_counters
+            .insert(issuer, (issued_by_caller + 1).into())
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update document counter: {:?}", e))
+            })?;
+
+        Ok(Some(format!(
+            "{}{:0width$}-{}",
+            scheme.prefix,
+            issued_total + 1,
+            issuer_suffix,
+            width = scheme.padding as usize
+        )))
+    }
+
+    /// Find documents whose number contains `query` (case-insensitive)
+    pub fn find_documents_by_number(
+        &self,
+        query: String,
+    ) -> Result<Vec<DocumentInfo>, MeroDocsError> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Err(MeroDocsError::invalid_input("Search query cannot be empty"));
+        }
+
+        let oversight_role = self.caller_oversight_role();
+        let mut documents = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                let matches = document
+                    .document_number
+                    .as_ref()
+                    .is_some_and(|number| number.to_lowercase().contains(&query));
+                if matches && self.can_view_document(&document) {
+                    documents.push(redact_document(document, oversight_role.as_ref()));
+                }
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Upload a document
+    #[allow(clippy::too_many_arguments)]
+    pub fn upload_document(
+        &mut self,
+        name: String,
+        hash: String,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        embeddings: Option<Vec<f32>>,
+        extracted_text: Option<String>,
+        chunks: Option<Vec<DocumentChunk>>,
+        options: Option<UploadOptions>,
+    ) -> Result<String, MeroDocsError> {
+        let UploadOptions {
+            signing_order,
+            required_signers,
+            expires_at,
+            signature_fields,
+            page_count,
+            page_sizes,
+            required_witnesses,
+        } = options.unwrap_or_default();
+
+        self.ensure_writable()?;
+
+        if !*self.is_private.get() {
+            self.require_capability(roles::UPLOAD)?;
+        }
+
+        let document_id = format!("doc_{}_{}", env::time_now(), name);
+
+        if self.documents.contains(&document_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Document with this ID already exists",
+            ));
+        }
+
+        self.validate_embeddings(&embeddings, &chunks)?;
+
+        if let Some(expires_at) = expires_at {
+            self.require_feature("document_expiry")?;
+            if expires_at <= TimestampNanos::now() {
+                return Err(MeroDocsError::invalid_input("Expiry must be in the future"));
+            }
+        }
+
+        let signing_order = match signing_order {
+            Some(signer_strs) if !signer_strs.is_empty() => {
+                self.require_feature("sequential_signing")?;
+                let mut signers = Vec::with_capacity(signer_strs.len());
+                for signer_str in &signer_strs {
+                    let signer = parse_public_key_base58(signer_str)?;
+                    if signers.contains(&signer) {
+                        return Err(MeroDocsError::invalid_input(format!(
+                            "Duplicate signer in signing order: {}",
+                            signer_str
+                        )));
+                    }
+                    signers.push(signer);
+                }
+                Some(SigningOrder { signers })
+            }
+            _ => None,
+        };
+
+        let required_signers = match required_signers {
+            Some(signer_strs) => {
+                let mut signers = Vec::with_capacity(signer_strs.len());
+                for signer_str in &signer_strs {
+                    let signer = parse_public_key_base58(signer_str)?;
+                    if !signers.contains(&signer) {
+                        signers.push(signer);
+                    }
+                }
+                if signers.is_empty() {
+                    return Err(MeroDocsError::invalid_input(
+                        "Required signers cannot be empty",
+                    ));
+                }
+                Some(signers)
+            }
+            None => None,
+        };
+
+        if let (Some(order), Some(required)) = (&signing_order, &required_signers) {
+            if order.signers.iter().any(|s| !required.contains(s)) {
+                return Err(MeroDocsError::invalid_input(
+                    "Signing order must only contain required signers",
+                ));
+            }
+        }
+
+        let page_sizes = page_sizes.unwrap_or_default();
+        validate_page_sizes(&page_sizes)?;
+        let page_count = match (page_count, page_sizes.len()) {
+            (_, 0) => page_count,
+            (Some(count), pages) if count as usize != pages => {
+                return Err(MeroDocsError::invalid_input(
+                    "Page count does not match the page geometry",
+                ))
+            }
+            (_, pages) => Some(pages as u32),
+        };
+
+        let mut fields = Vec::new();
+        if signature_fields
+            .as_ref()
+            .is_some_and(|specs| !specs.is_empty())
+        {
+            self.require_feature("signature_fields")?;
+        }
+        for spec in signature_fields.unwrap_or_default() {
+            let field = build_signature_field(spec, &fields, &page_sizes)?;
+            fields.push(field);
+        }
+
+        let pdf_blob_id = parse_blob_id_base58(&pdf_blob_id_str)?;
+
+        // Announce blob to the network for discovery
+        self.announce_blobs(&[pdf_blob_id])?;
+
+        let document_number = self.next_document_number()?;
+        let (embeddings, chunks) = self.prepare_embeddings(embeddings, chunks);
+
+        let uploaded_by = current_caller();
+        let document = DocumentInfo {
+            id: document_id.clone(),
+            name: name.clone(),
+            hash,
+            uploaded_by,
+            uploaded_at: TimestampNanos::now(),
+            status: DocumentStatus::Pending,
+            pdf_blob_id,
+            size: file_size,
+            embeddings,
+            extracted_text,
+            summary: None,
+            first_signed_at: None,
+            completed_at: None,
+            document_number,
+            requires_fully_signed: None,
+            signing_order,
+            required_signers,
+            expires_at,
+            signature_fields: fields,
+            tags: Vec::new(),
+            folder_id: None,
+            signer_due_dates: Vec::new(),
+            access_list: Vec::new(),
+            page_count,
+            page_sizes,
+            organization_id: None,
+            key_epoch: self.current_key_epoch(),
+            template: None,
+            required_witnesses: required_witnesses.unwrap_or(0),
+        };
+
+        let details = format!("name={}", name);
+        self.insert_new_document(document, chunks, details)
+    }
+
+    /// Store a newly created document with its chunks, first version and empty signature
+    /// list, then record and announce the upload. Returns the document ID.
+    fn insert_new_document(
+        &mut self,
+        document: DocumentInfo,
+        chunks: Vec<DocumentChunk>,
+        audit_details: String,
+    ) -> Result<String, MeroDocsError> {
+        let document_id = document.id.clone();
+        let name = document.name.clone();
+        let uploaded_by = document.uploaded_by;
+        let signers = self.required_signers_for(&document);
+
+        self.index_document_keywords(&document, &chunks)?;
+        self.store_document_chunks(&document_id, chunks)?;
+        self.push_document_version(&document, uploaded_by, "Uploaded")?;
+
+        self.documents
+            .insert(document_id.clone(), document)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to upload document: {:?}", e)))?;
+        self.record_change(ChangeEntity::Document, &document_id, ChangeKind::Created)?;
+
+        self.document_signatures
+            .insert(document_id.clone(), Vector::new())
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to initialize document signatures: {:?}", e))
+            })?;
+
+        for signer in &signers {
+            self.refresh_pending_action(signer, &document_id)?;
+        }
+
+        self.record_audit(AuditAction::DocumentUploaded, &document_id, audit_details)?;
+
+        app::emit!(MeroSignEvent::DocumentUploaded {
+            id: document_id.clone(),
+            name,
+            uploaded_by,
+        });
+
+        Ok(document_id)
+    }
+
+    // === TEMPLATES ===
+
+    /// Store a reusable template PDF with named merge fields; returns the template ID
+    pub fn create_template(
+        &mut self,
+        name: String,
+        hash: String,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        merge_fields: Vec<MergeField>,
+        page_count: Option<u32>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        if !*self.is_private.get() {
+            self.require_capability(roles::UPLOAD)?;
+        }
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Template name cannot be empty",
+            ));
+        }
+
+        let mut fields: Vec<MergeField> = Vec::with_capacity(merge_fields.len());
+        for field in merge_fields {
+            let field_name = field.name.trim().to_string();
+            if field_name.is_empty() {
+                return Err(MeroDocsError::invalid_input(
+                    "Merge field name cannot be empty",
+                ));
+            }
+            if fields.iter().any(|f| f.name == field_name) {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Duplicate merge field: {}",
+                    field_name
+                )));
+            }
+            fields.push(MergeField {
+                name: field_name,
+                required: field.required,
+            });
+        }
+
+        let pdf_blob_id = parse_blob_id_base58(&pdf_blob_id_str)?;
+        self.announce_blobs(&[pdf_blob_id])?;
+
+        let now = env::time_now();
+        let template = DocumentTemplate {
+            id: format!("template_{}_{}", now, name),
+            name: name.clone(),
+            pdf_blob_id,
+            hash,
+            size: file_size,
+            page_count,
+            merge_fields: fields,
+            created_by: current_caller(),
+            updated_at: TimestampNanos::from_nanos(now),
+        };
+        let template_id = template.id.clone();
+        self.templates
+            .insert(template_id.clone(), template)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store template: {:?}", e)))?;
+
+        self.record_audit(
+            AuditAction::TemplateCreated,
+            &template_id,
+            format!("name={}", name),
+        )?;
+
+        app::emit!(MeroSignEvent::TemplateCreated {
+            id: template_id.clone(),
+            name,
+        });
+
+        Ok(template_id)
+    }
+
+    /// List all templates, sorted by name
+    pub fn list_templates(&self) -> Result<Vec<DocumentTemplate>, MeroDocsError> {
+        let mut templates: Vec<DocumentTemplate> = self
+            .templates
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list templates: {:?}", e)))?
+            .map(|(_, template)| template)
+            .collect();
+        templates.sort_by_key(|template| template.name.to_lowercase());
+        Ok(templates)
+    }
+
+    pub fn get_template(&self, template_id: String) -> Result<DocumentTemplate, MeroDocsError> {
+        match self.templates.get(&template_id) {
+            Ok(Some(template)) => Ok(template),
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Template not found: {}",
+                template_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get template: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Delete a template. Documents created from it keep their provenance.
+    pub fn delete_template(&mut self, template_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        if !*self.is_private.get() {
+            self.require_capability(roles::DELETE)?;
+        }
+
+        match self.templates.remove(&template_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err(MeroDocsError::not_found(format!(
+                    "Template not found: {}",
+                    template_id
+                )))
+            }
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to delete template: {:?}",
+                    e
+                )))
+            }
+        }
+
+        self.record_audit(AuditAction::TemplateDeleted, &template_id, String::new())?;
+
+        Ok(())
+    }
+
+    /// Create a document from a template. Every value must name one of the template's
+    /// merge fields and every required field needs a non-empty value. The document uses
+    /// the template's PDF and records the template and values it came from.
+    pub fn instantiate_template(
+        &mut self,
+        template_id: String,
+        field_values: Vec<MergeFieldValue>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        if !*self.is_private.get() {
+            self.require_capability(roles::UPLOAD)?;
+        }
+
+        let template = self.get_template(template_id)?;
+        let values = validate_merge_values(&template, field_values)?;
+        let name = template.name.clone();
+        let document_id = format!("doc_{}_{}", env::time_now(), name);
+        self.create_from_template(template, document_id, name, values, None, current_caller())
+    }
+
+    /// Create a pending document from a template's PDF, recording its provenance
+    fn create_from_template(
+        &mut self,
+        template: DocumentTemplate,
+        document_id: String,
+        name: String,
+        values: Vec<MergeFieldValue>,
+        required_signers: Option<Vec<UserId>>,
+        uploaded_by: UserId,
+    ) -> Result<String, MeroDocsError> {
+        let now = env::time_now();
+        if self.documents.contains(&document_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Document with this ID already exists",
+            ));
+        }
+
+        let template_id = template.id.clone();
+        let document = DocumentInfo {
+            id: document_id,
+            name,
+            hash: template.hash.clone(),
+            uploaded_by,
+            uploaded_at: TimestampNanos::from_nanos(now),
+            status: DocumentStatus::Pending,
+            pdf_blob_id: template.pdf_blob_id,
+            size: template.size,
+            embeddings: None,
+            extracted_text: None,
+            summary: None,
+            first_signed_at: None,
+            completed_at: None,
+            document_number: self.next_document_number()?,
+            requires_fully_signed: None,
+            signing_order: None,
+            required_signers,
+            expires_at: None,
+            signature_fields: Vec::new(),
+            tags: Vec::new(),
+            folder_id: None,
+            signer_due_dates: Vec::new(),
+            access_list: Vec::new(),
+            page_count: template.page_count,
+            page_sizes: Vec::new(),
+            organization_id: None,
+            key_epoch: self.current_key_epoch(),
+            template: Some(TemplateProvenance {
+                template_id: template_id.clone(),
+                template_hash: template.hash,
+                field_values: values,
+            }),
+            required_witnesses: 0,
+        };
+
+        let details = format!("name={} template={}", document.name, template_id);
+        self.insert_new_document(document, Vec::new(), details)
+    }
+
+    // === RECURRING SCHEDULES ===
+
+    /// Instantiate a template for `recipients` on every period of `cron_like_spec`
+    /// (`@daily`, `@weekly` or `@monthly`), starting at `starts_at` or one period from
+    /// now. Documents are generated by `run_due_schedules`. Returns the schedule ID.
+    pub fn create_recurring(
+        &mut self,
+        template_id: String,
+        cron_like_spec: String,
+        recipients: Vec<String>,
+        field_values: Option<Vec<MergeFieldValue>>,
+        starts_at: Option<u64>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::UPLOAD)?;
+
+        let recurrence = Recurrence::parse(&cron_like_spec)?;
+        let template = self.get_template(template_id.clone())?;
+        let field_values = validate_merge_values(&template, field_values.unwrap_or_default())?;
+
+        let mut signers = Vec::with_capacity(recipients.len());
+        for recipient in &recipients {
+            let signer = self.parse_participant(recipient)?;
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+        if signers.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "A recurring schedule needs at least one recipient",
+            ));
+        }
+
+        let now = TimestampNanos::now();
+        let starts_at = match starts_at {
+            Some(at) if at <= now.as_nanos() => {
+                return Err(MeroDocsError::invalid_input(
+                    "Schedule must start in the future",
+                ))
+            }
+            Some(at) => TimestampNanos::from_nanos(at),
+            None => recurrence.nth_run(now, 1),
+        };
+
+        let schedule = RecurringSchedule {
+            id: format!("schedule_{}_{}", now.as_nanos(), template.name),
+            template_id: template_id.clone(),
+            recurrence,
+            recipients: signers,
+            field_values,
+            starts_at,
+            periods_elapsed: 0,
+            next_run_at: starts_at,
+            last_run_at: None,
+            documents_generated: 0,
+            created_by: current_caller(),
+            updated_at: now,
+        };
+        let schedule_id = schedule.id.clone();
+        self.recurring_schedules
+            .insert(schedule_id.clone(), schedule)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store schedule: {:?}", e)))?;
+
+        self.record_audit(
+            AuditAction::RecurringScheduleCreated,
+            &schedule_id,
+            format!("template={} recurrence={:?}", template_id, recurrence),
+        )?;
+
+        Ok(schedule_id)
+    }
+
+    /// List recurring schedules, soonest next run first
+    pub fn list_recurring(&self) -> Result<Vec<RecurringSchedule>, MeroDocsError> {
+        let mut schedules: Vec<RecurringSchedule> = self
+            .recurring_schedules
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list schedules: {:?}", e)))?
+            .map(|(_, schedule)| schedule)
+            .collect();
+        schedules.sort_by_key(|schedule| schedule.next_run_at);
+        Ok(schedules)
+    }
+
+    /// Stop a recurring schedule. Documents it already generated are kept.
+    pub fn delete_recurring(&mut self, schedule_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::UPLOAD)?;
+
+        match self.recurring_schedules.remove(&schedule_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err(MeroDocsError::not_found(format!(
+                    "Schedule not found: {}",
+                    schedule_id
+                )))
+            }
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to delete schedule: {:?}",
+                    e
+                )))
+            }
+        }
+
+        self.record_audit(
+            AuditAction::RecurringScheduleDeleted,
+            &schedule_id,
+            String::new(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Generate a document for every period that has come due on every schedule, up to
+    /// `MAX_SCHEDULE_CATCH_UP_RUNS` per schedule. Schedules whose template was deleted
+    /// are skipped, and a schedule that fails to generate a document stops at that period
+    /// without holding up the others. Returns the IDs of the generated documents.
+    pub fn run_due_schedules(&mut self) -> Result<Vec<String>, MeroDocsError> {
+        self.ensure_writable()?;
+
+        let executor_id = current_caller();
+        if !self.participants.contains(&executor_id).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Only participants can run recurring schedules",
+            ));
+        }
+
+        let now = TimestampNanos::now();
+        let mut due = Vec::new();
+        if let Ok(entries) = self.recurring_schedules.entries() {
+            for (_, schedule) in entries {
+                if schedule.next_run_at.has_passed(now) {
+                    due.push(schedule);
+                }
+            }
+        }
+
+        let mut generated = Vec::new();
+        for mut schedule in due {
+            let template = match self.templates.get(&schedule.template_id) {
+                Ok(Some(template)) => template,
+                _ => {
+                    app::log!(
+                        "Skipping schedule {}: template {} not found",
+                        schedule.id,
+                        schedule.template_id
+                    );
+                    continue;
+                }
+            };
+
+            let mut runs = 0;
+            while schedule.next_run_at.has_passed(now) {
+                if runs < MAX_SCHEDULE_CATCH_UP_RUNS {
+                    let document_id = format!(
+                        "doc_{}_{}_{}",
+                        now.as_nanos(),
+                        schedule.id,
+                        schedule.periods_elapsed
+                    );
+                    let name = format!("{} {}", template.name, schedule.next_run_at.date());
+                    match self.create_from_template(
+                        template.clone(),
+                        document_id,
+                        name,
+                        schedule.field_values.clone(),
+                        Some(schedule.recipients.clone()),
+                        schedule.created_by,
+                    ) {
+                        Ok(document_id) => generated.push(document_id),
+                        Err(e) => {
+                            app::log!("Schedule {} stopped at a failed run: {}", schedule.id, e);
+                            break;
+                        }
+                    }
+                    runs += 1;
+                }
+                schedule.periods_elapsed += 1;
+                schedule.next_run_at = schedule
+                    .recurrence
+                    .nth_run(schedule.starts_at, schedule.periods_elapsed);
+            }
+
+            schedule.last_run_at = Some(now);
+            schedule.documents_generated += runs;
+            schedule.updated_at = now;
+            self.recurring_schedules
+                .insert(schedule.id.clone(), schedule)
+                .map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to update schedule: {:?}", e))
+                })?;
+        }
+
+        Ok(generated)
+    }
+
+    /// Delete a document by ID
+    pub fn delete_document(&mut self, document_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::DELETE)?;
+
+        // Documents gated on this one would stay hidden forever once it is gone
+        let mut dependents = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                if document.requires_fully_signed.as_deref() == Some(document_id.as_str()) {
+                    dependents.push(id);
+                }
+            }
+        }
+        if !dependents.is_empty() {
+            return Err(MeroDocsError::invalid_state(format!(
+                "Document is the visibility condition of {}; clear their conditions first",
+                dependents.join(", ")
+            )));
+        }
+
+        match self.documents.remove(&document_id) {
+            Ok(Some(document)) => {
+                let chunks = self.load_document_chunks(&document_id)?;
+                self.unindex_document_keywords(&document, &chunks)?;
+                let _ = self.document_chunks.remove(&document_id);
+                self.record_change(ChangeEntity::Document, &document_id, ChangeKind::Deleted)?;
+                let _ = self.document_signatures.remove(&document_id);
+                let _ = self.document_versions.remove(&document_id);
+                let _ = self.document_access_log.remove(&document_id);
+                let _ = self.document_attachments.remove(&document_id);
+                let _ = self.document_declines.remove(&document_id);
+                let _ = self.document_voids.remove(&document_id);
+                let key_prefix = format!("{}:", document_id);
+                let mut delegation_keys = Vec::new();
+                if let Ok(entries) = self.signing_delegations.entries() {
+                    for (key, _) in entries {
+                        if key.starts_with(&key_prefix) {
+                            delegation_keys.push(key);
+                        }
+                    }
+                }
+                for key in delegation_keys {
+                    let _ = self.signing_delegations.remove(&key);
+                }
+                let mut snooze_keys = Vec::new();
+                if let Ok(entries) = self.document_snoozes.entries() {
+                    for (key, _) in entries {
+                        if key.starts_with(&key_prefix) {
+                            snooze_keys.push(key);
+                        }
+                    }
+                }
+                for key in snooze_keys {
+                    let _ = self.document_snoozes.remove(&key);
+                }
+                let _ = self.qa_history.remove(&document_id);
+                let _ = self.consent_history.remove(&document_id);
+                let _ = self.reindex_status.remove(&document_id);
+
+                self.record_audit(AuditAction::DocumentDeleted, &document_id, String::new())?;
+
+                app::emit!(MeroSignEvent::DocumentDeleted { id: document_id });
+
+                self.finish_reindex_if_complete()?;
+
+                Ok(())
+            }
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Document not found: {}",
+                document_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to delete document: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// List all documents
+    pub fn list_documents(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<DocumentInfo>, MeroDocsError> {
+        Ok(self.page_visible_documents(|_| true, offset, limit))
+    }
+
+    /// List documents carrying a tag
+    pub fn list_documents_by_tag(
+        &self,
+        tag: String,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<DocumentInfo>, MeroDocsError> {
+        let tag = normalize_tag(&tag)?;
+        Ok(self.page_visible_documents(|doc| doc.tags.contains(&tag), offset, limit))
+    }
+
+    /// Tag a document (admin only)
+    pub fn add_document_tag(
+        &mut self,
+        document_id: String,
+        tag: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+        let tag = normalize_tag(&tag)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        if document.tags.contains(&tag) {
+            return Ok(());
+        }
+        document.tags.push(tag.clone());
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::DocumentTagsChanged,
+            &document_id,
+            format!("added={}", tag),
+        )?;
+
+        Ok(())
+    }
+
+    /// Remove a tag from a document (admin only)
+    pub fn remove_document_tag(
+        &mut self,
+        document_id: String,
+        tag: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+        let tag = normalize_tag(&tag)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let Some(position) = document.tags.iter().position(|t| *t == tag) else {
+            return Err(MeroDocsError::not_found(format!(
+                "Document is not tagged '{}'",
+                tag
+            )));
+        };
+        document.tags.remove(position);
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::DocumentTagsChanged,
+            &document_id,
+            format!("removed={}", tag),
+        )?;
+
+        Ok(())
+    }
+
+    /// Create a folder, optionally nested under another folder (admin only)
+    pub fn create_folder(
+        &mut self,
+        name: String,
+        parent: Option<String>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input("Folder name cannot be empty"));
+        }
+
+        if let Some(parent_id) = &parent {
+            if !self.folders.contains(parent_id).unwrap_or(false) {
+                return Err(MeroDocsError::not_found(format!(
+                    "Folder not found: {}",
+                    parent_id
+                )));
+            }
+        }
+
+        if let Ok(entries) = self.folders.entries() {
+            for (_, folder) in entries {
+                if folder.parent == parent && folder.name.eq_ignore_ascii_case(&name) {
+                    return Err(MeroDocsError::already_exists(format!(
+                        "A folder named '{}' already exists here",
+                        name
+                    )));
+                }
+            }
+        }
+
+        let folder_id = format!("folder_{}_{}", env::time_now(), name);
+        let folder = DocumentFolder {
+            id: folder_id.clone(),
+            name: name.clone(),
+            parent: parent.clone(),
+            created_by: current_caller(),
+            created_at: TimestampNanos::now(),
+        };
+
+        self.folders
+            .insert(folder_id.clone(), folder)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to create folder: {:?}", e)))?;
+        self.record_change(ChangeEntity::Folder, &folder_id, ChangeKind::Created)?;
+        self.record_audit(
+            AuditAction::FolderCreated,
+            &folder_id,
+            format!("name={}", name),
+        )?;
+
+        app::emit!(MeroSignEvent::FolderCreated {
+            id: folder_id.clone(),
+            name,
+            parent,
+        });
+
+        Ok(folder_id)
+    }
+
+    /// Move a document into a folder, or back to the root with `None` (admin only)
+    pub fn move_document_to_folder(
+        &mut self,
+        document_id: String,
+        folder_id: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if let Some(folder_id) = &folder_id {
+            if !self.folders.contains(folder_id).unwrap_or(false) {
+                return Err(MeroDocsError::not_found(format!(
+                    "Folder not found: {}",
+                    folder_id
+                )));
+            }
+        }
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        document.folder_id = folder_id.clone();
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::DocumentMoved,
+            &document_id,
+            format!("folder={}", folder_id.as_deref().unwrap_or("root")),
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentMoved {
+            document_id,
+            folder_id,
+        });
+
+        Ok(())
+    }
+
+    /// List the subfolders and a page of the documents directly inside a folder (`None` for
+    /// the root)
+    pub fn list_folder_contents(
+        &self,
+        folder_id: Option<String>,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<FolderContents, MeroDocsError> {
+        if let Some(id) = &folder_id {
+            if !self.folders.contains(id).unwrap_or(false) {
+                return Err(MeroDocsError::not_found(format!(
+                    "Folder not found: {}",
+                    id
+                )));
+            }
+        }
+
+        let mut folders = Vec::new();
+        if let Ok(entries) = self.folders.entries() {
+            for (_, folder) in entries {
+                if folder.parent == folder_id {
+                    folders.push(folder);
+                }
+            }
+        }
+        folders.sort_by_key(|f| f.name.to_lowercase());
+
+        let documents =
+            self.page_visible_documents(|doc| doc.folder_id == folder_id, offset, limit);
+
+        Ok(FolderContents { folders, documents })
+    }
+
+    /// Build a download manifest of the fully signed documents in a folder and its subfolders,
+    /// or in the whole context when no folder is given
+    pub fn get_download_manifest(
+        &self,
+        folder_id: Option<String>,
+    ) -> Result<DownloadManifest, MeroDocsError> {
+        // Collect the folder and every folder nested below it
+        let mut scope = Vec::new();
+        if let Some(id) = &folder_id {
+            if !self.folders.contains(id).unwrap_or(false) {
+                return Err(MeroDocsError::not_found(format!(
+                    "Folder not found: {}",
+                    id
+                )));
+            }
+            let all_folders: Vec<DocumentFolder> = match self.folders.entries() {
+                Ok(entries) => entries.map(|(_, folder)| folder).collect(),
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to list folders: {:?}",
+                        e
+                    )))
+                }
+            };
+            let mut pending = vec![id.clone()];
+            while let Some(current) = pending.pop() {
+                for folder in &all_folders {
+                    if folder.parent.as_ref() == Some(&current) && !scope.contains(&folder.id) {
+                        pending.push(folder.id.clone());
+                    }
+                }
+                scope.push(current);
+            }
+        }
+
+        let mut documents = self
+            .page_visible_documents(
+                |doc| {
+                    doc.status == DocumentStatus::FullySigned
+                        && (folder_id.is_none()
+                            || doc.folder_id.as_ref().is_some_and(|f| scope.contains(f)))
+                },
+                None,
+                Some(u64::MAX),
+            )
+            .items;
+        documents.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let mut entries: Vec<ManifestEntry> = Vec::with_capacity(documents.len());
+        let mut total_size = 0u64;
+        for document in documents {
+            let filename = manifest_filename(&document.name, &entries);
+            total_size = total_size.saturating_add(document.size);
+            entries.push(ManifestEntry {
+                document_id: document.id,
+                filename,
+                blob_id: bs58::encode(&document.pdf_blob_id).into_string(),
+                hash: document.hash,
+                size: document.size,
+            });
+        }
+
+        Ok(DownloadManifest {
+            folder_id,
+            entries,
+            total_size,
+        })
+    }
+
+    /// Page through the documents visible to the caller that match a filter
+    fn page_visible_documents(
+        &self,
+        filter: impl Fn(&DocumentInfo) -> bool,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Page<DocumentInfo> {
+        let oversight_role = self.caller_oversight_role();
+        let start = offset.unwrap_or(0);
+        let end = start.saturating_add(limit.unwrap_or(DEFAULT_PAGE_LIMIT));
+
+        // Only documents inside the requested window are cloned and redacted
+        let mut total = 0u64;
+        let mut documents = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                if !self.can_view_document(&document) || !filter(&document) {
+                    continue;
+                }
+                if total >= start && total < end {
+                    documents.push(redact_document(document, oversight_role.as_ref()));
+                }
+                total += 1;
+            }
+        }
+
+        Page {
+            items: documents,
+            total,
+            next_cursor: (end < total).then_some(end),
+        }
+    }
+
+    /// Check whether the caller may see a document given its access list and visibility
+    /// condition. Admins and the uploader always see it; others only once the umbrella
+    /// document it depends on is fully signed.
+    fn can_view_document(&self, document: &DocumentInfo) -> bool {
+        let caller = current_caller();
+        if self.document_permission(document, &caller).is_none() {
+            return false;
+        }
+
+        let Some(umbrella_id) = &document.requires_fully_signed else {
+            return true;
+        };
+
+        if caller == document.uploaded_by
+            || matches!(
+                self.permissions.get(&caller),
+                Ok(Some(PermissionLevel::Admin))
+            )
+        {
+            return true;
+        }
+
+        matches!(
+            self.documents.get(umbrella_id),
+            Ok(Some(umbrella)) if umbrella.status == DocumentStatus::FullySigned
+        )
+    }
+
+    /// Effective permission of a user on a document. Once a document has an access list,
+    /// only listed users, context admins and the uploader can reach it.
+    fn document_permission(
+        &self,
+        document: &DocumentInfo,
+        user: &UserId,
+    ) -> Option<PermissionLevel> {
+        let context_permission = self.permissions.get(user).ok().flatten();
+        if document.access_list.is_empty() {
+            return context_permission;
+        }
+
+        if let Some(entry) = document.access_list.iter().find(|a| a.user_id == *user) {
+            return Some(entry.permission.clone());
+        }
+        if *user == document.uploaded_by
+            || matches!(context_permission, Some(PermissionLevel::Admin))
+        {
+            return Some(PermissionLevel::Admin);
+        }
+        None
+    }
+
+    /// Grant a user a permission on one document, or clear it with `None` (admin only).
+    /// Restricting a document hides it from every participant not on its access list.
+    pub fn set_document_access(
+        &mut self,
+        document_id: String,
+        user_id_str: String,
+        permission: Option<PermissionLevel>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        document.access_list.retain(|a| a.user_id != user_id);
+        if let Some(permission) = &permission {
+            document.access_list.push(DocumentAccess {
+                user_id,
+                permission: permission.clone(),
+                granted_by: current_caller(),
+                granted_at: TimestampNanos::now(),
+            });
+        }
+
+        // The access list decides who is a default required signer
+        self.recalculate_document_status(&mut document)?;
+        self.save_document(document)?;
+        self.refresh_document_pending_actions(&document_id)?;
+
+        self.record_audit(
+            AuditAction::DocumentAccessChanged,
+            &document_id,
+            format!("user={} permission={:?}", user_id_str, permission),
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentAccessChanged {
+            document_id,
+            user_id,
+            permission,
+        });
+
+        Ok(())
+    }
+
+    // === GROUPS ===
+
+    /// Create a group of participants (manage capability)
+    pub fn create_group(
+        &mut self,
+        name: String,
+        members: Vec<String>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input("Group name cannot be empty"));
+        }
+        if let Ok(entries) = self.groups.entries() {
+            for (_, group) in entries {
+                if group.name.eq_ignore_ascii_case(&name) {
+                    return Err(MeroDocsError::already_exists(format!(
+                        "A group named '{}' already exists",
+                        name
+                    )));
+                }
+            }
+        }
+
+        let mut group_members = Vec::with_capacity(members.len());
+        for member_str in &members {
+            let member = self.parse_participant(member_str)?;
+            if !group_members.contains(&member) {
+                group_members.push(member);
+            }
+        }
+
+        let now = env::time_now();
+        let group = Group {
+            id: format!("group_{}_{}", now, name),
+            name,
+            members: group_members,
+            created_by: current_caller(),
+            updated_at: TimestampNanos::from_nanos(now),
+        };
+        let group_id = group.id.clone();
+        self.save_group(group)?;
+
+        Ok(group_id)
+    }
+
+    /// Add a participant to a group (manage capability)
+    pub fn add_to_group(
+        &mut self,
+        group_id: String,
+        user_id_str: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let member = self.parse_participant(&user_id_str)?;
+        let mut group = self.get_group(&group_id)?;
+        if group.members.contains(&member) {
+            return Err(MeroDocsError::already_exists(
+                "User is already in this group",
+            ));
+        }
+        group.members.push(member);
+        group.updated_at = TimestampNanos::now();
+
+        self.save_group(group)
+    }
+
+    /// Remove a user from a group (manage capability)
+    pub fn remove_from_group(
+        &mut self,
+        group_id: String,
+        user_id_str: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let member = parse_public_key_base58(&user_id_str)?;
+        let mut group = self.get_group(&group_id)?;
+        let Some(position) = group.members.iter().position(|m| *m == member) else {
+            return Err(MeroDocsError::not_found("User is not in this group"));
+        };
+        group.members.remove(position);
+        group.updated_at = TimestampNanos::now();
+
+        self.save_group(group)
+    }
+
+    /// List all groups, sorted by name
+    pub fn list_groups(&self) -> Result<Vec<Group>, MeroDocsError> {
+        let mut groups: Vec<Group> = self
+            .groups
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list groups: {:?}", e)))?
+            .map(|(_, group)| group)
+            .collect();
+        groups.sort_by_key(|group| group.name.to_lowercase());
+        Ok(groups)
+    }
+
+    /// Add every member of a group to a document's required signers (manage capability).
+    /// A document that had no explicit signer list then requires only the listed signers.
+    /// Members are copied, so later changes to the group do not affect the document.
+    pub fn add_group_signers(
+        &mut self,
+        document_id: String,
+        group_id: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let group = self.get_group(&group_id)?;
+        if group.members.is_empty() {
+            return Err(MeroDocsError::invalid_input("Group has no members"));
+        }
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+        if !matches!(
+            document.status,
+            DocumentStatus::Pending | DocumentStatus::PartiallySigned
+        ) {
+            return Err(MeroDocsError::invalid_state(
+                "Signers can only be added to documents that are still open",
+            ));
+        }
+
+        let mut required = document.required_signers.take().unwrap_or_default();
+        for member in &group.members {
+            if !required.contains(member) {
+                required.push(*member);
+            }
+        }
+        document.required_signers = Some(required);
+        self.recalculate_document_status(&mut document)?;
+
+        self.save_document(document)?;
+        self.refresh_document_pending_actions(&document_id)?;
+
+        self.record_audit(
+            AuditAction::GroupAppliedToDocument,
+            &document_id,
+            format!("group={} required_signers", group_id),
+        )?;
+
+        Ok(())
+    }
+
+    /// Give every member of a group the same access to a document, or remove their entries
+    /// with `None` (manage capability). Members are copied into the access list.
+    pub fn set_group_document_access(
+        &mut self,
+        document_id: String,
+        group_id: String,
+        permission: Option<PermissionLevel>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let group = self.get_group(&group_id)?;
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let now = env::time_now();
+        document
+            .access_list
+            .retain(|a| !group.members.contains(&a.user_id));
+        if let Some(permission) = &permission {
+            for member in &group.members {
+                document.access_list.push(DocumentAccess {
+                    user_id: *member,
+                    permission: permission.clone(),
+                    granted_by: current_caller(),
+                    granted_at: TimestampNanos::from_nanos(now),
+                });
+            }
+        }
+
+        self.recalculate_document_status(&mut document)?;
+        self.save_document(document)?;
+        self.refresh_document_pending_actions(&document_id)?;
+
+        self.record_audit(
+            AuditAction::GroupAppliedToDocument,
+            &document_id,
+            format!("group={} permission={:?}", group_id, permission),
+        )?;
+
+        for member in group.members {
+            app::emit!(MeroSignEvent::DocumentAccessChanged {
+                document_id: document_id.clone(),
+                user_id: member,
+                permission: permission.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn get_group(&self, group_id: &str) -> Result<Group, MeroDocsError> {
+        match self.groups.get(group_id) {
+            Ok(Some(group)) => Ok(group),
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Group not found: {}",
+                group_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get group: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Store a group, then record and announce the change
+    fn save_group(&mut self, group: Group) -> Result<(), MeroDocsError> {
+        let (id, name, members) = (group.id.clone(), group.name.clone(), group.members.clone());
+        self.groups
+            .insert(id.clone(), group)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store group: {:?}", e)))?;
+
+        self.record_audit(
+            AuditAction::GroupChanged,
+            &id,
+            format!("name={} members={}", name, members.len()),
+        )?;
+
+        app::emit!(MeroSignEvent::GroupChanged { id, name, members });
+
+        Ok(())
+    }
+
+    /// Drop a removed participant from every group they were in
+    fn remove_from_all_groups(&mut self, user_id: &UserId) -> Result<(), MeroDocsError> {
+        let mut affected = Vec::new();
+        if let Ok(entries) = self.groups.entries() {
+            for (_, group) in entries {
+                if group.members.contains(user_id) {
+                    affected.push(group);
+                }
+            }
+        }
+        for mut group in affected {
+            group.members.retain(|m| m != user_id);
+            group.updated_at = TimestampNanos::now();
+            self.save_group(group)?;
+        }
+        Ok(())
+    }
+
+    /// Parse a user ID and check that it belongs to a participant
+    fn parse_participant(&self, user_id_str: &str) -> Result<UserId, MeroDocsError> {
+        let user_id = parse_public_key_base58(user_id_str)?;
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found(format!(
+                "User is not a participant: {}",
+                user_id_str
+            )));
+        }
+        Ok(user_id)
+    }
+
+    /// Load a document the caller is allowed to see; hidden documents read as not found
+    fn get_visible_document(&self, document_id: &str) -> Result<DocumentInfo, MeroDocsError> {
+        match self.documents.get(document_id) {
+            Ok(Some(doc)) if self.can_view_document(&doc) => Ok(doc),
+            Ok(_) => Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get document: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Hide a document until another document is fully signed; `None` clears the condition
+    /// (admin only)
+    pub fn set_visibility_condition(
+        &mut self,
+        document_id: String,
+        requires_fully_signed: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        if let Some(umbrella_id) = &requires_fully_signed {
+            // Walk the dependency chain so conditions can't form a cycle
+            let mut current = Some(umbrella_id.clone());
+            while let Some(id) = current {
+                if id == document_id {
+                    return Err(MeroDocsError::invalid_input(
+                        "Visibility conditions cannot form a cycle",
+                    ));
+                }
+                current = match self.documents.get(&id) {
+                    Ok(Some(doc)) => doc.requires_fully_signed,
+                    Ok(None) => {
+                        return Err(MeroDocsError::not_found(format!(
+                            "Document not found: {}",
+                            id
+                        )))
+                    }
+                    Err(e) => {
+                        return Err(MeroDocsError::storage(format!(
+                            "Failed to get document: {:?}",
+                            e
+                        )))
+                    }
+                };
+            }
+        }
+
+        document.requires_fully_signed = requires_fully_signed.clone();
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::VisibilityConditionChanged,
+            &document_id,
+            format!(
+                "requires_fully_signed={}",
+                requires_fully_signed.as_deref().unwrap_or("none")
+            ),
+        )?;
+
+        app::emit!(MeroSignEvent::VisibilityConditionSet {
+            document_id,
+            requires_fully_signed,
+        });
+
+        Ok(())
+    }
+
+    /// Store an LLM-produced summary on a document so clients don't each regenerate it
+    pub fn set_document_summary(
+        &mut self,
+        document_id: String,
+        summary: String,
+        generated_by: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if self.caller_oversight_role().is_some() {
+            return Err(MeroDocsError::unauthorized(
+                "Oversight roles cannot change document summaries",
+            ));
+        }
+        if summary.trim().is_empty() {
+            return Err(MeroDocsError::invalid_input("Summary cannot be empty"));
+        }
+        if summary.chars().count() > MAX_DOCUMENT_SUMMARY_CHARS {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Summary cannot exceed {} characters",
+                MAX_DOCUMENT_SUMMARY_CHARS
+            )));
+        }
+
+        let mut document = self.get_visible_document(&document_id)?;
+        if matches!(
+            self.document_permission(&document, &current_caller()),
+            Some(PermissionLevel::Read)
+        ) {
+            return Err(MeroDocsError::unauthorized(
+                "Read-only participants cannot change document summaries",
+            ));
+        }
+
+        document.summary = Some(DocumentSummary {
+            text: summary,
+            generated_by: generated_by.clone(),
+            set_by: current_caller(),
+            set_at: TimestampNanos::now(),
+        });
+
+        self.save_document(document)?;
+
+        self.record_audit(
+            AuditAction::DocumentSummarySet,
+            &document_id,
+            format!("generated_by={}", generated_by),
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentSummarySet {
+            document_id,
+            generated_by,
+        });
+
+        Ok(())
+    }
+
+    /// Check a hash against a document for third parties. Only reveals whether it matches the
+    /// current version, the signing status and the completion time.
+    pub fn verify_document_public(
+        &self,
+        document_id: String,
+        hash: String,
+    ) -> Result<PublicVerification, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Documents can only be verified in shared context",
+            ));
+        }
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        Ok(PublicVerification {
+            matches: document.hash.eq_ignore_ascii_case(hash.trim()),
+            status: document.status,
+            completed_at: document.completed_at,
+        })
+    }
+
+    /// Get the canonical SHA-256 digest of a document's current version
+    pub fn get_document_digest(&self, document_id: String) -> Result<String, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        Ok(canonical::digest(&canonical::document_bytes(&document)))
+    }
+
+    /// Get the canonical SHA-256 digest of every signature on a document, in signing order
+    pub fn get_signature_digests(&self, document_id: String) -> Result<Vec<String>, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+
+        let mut digests = Vec::new();
+        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    digests.push(canonical::digest(&canonical::signature_bytes(
+                        &document_id,
+                        &document.hash,
+                        &sig,
+                    )));
+                }
+            }
+        }
+        Ok(digests)
+    }
+
+    /// Assemble every reference about a document a dispute needs - final blob, hash
+    /// chain, signatures with their attestations, consent records, audit slice and
+    /// access log - into one object clients can render to PDF or ZIP
+    pub fn build_evidence_package(
+        &self,
+        document_id: String,
+    ) -> Result<EvidencePackage, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Evidence packages are only available in shared context",
+            ));
+        }
+
+        let document = self.get_visible_document(&document_id)?;
+        let context_id = env::context_id();
+
+        let mut signatures = Vec::new();
+        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    signatures.push(EvidenceSignature {
+                        digest: canonical::digest(&canonical::signature_bytes(
+                            &document_id,
+                            &document.hash,
+                            &sig,
+                        )),
+                        signing_payload_hex: hex::encode(canonical::signing_payload(
+                            &context_id,
+                            &document_id,
+                            &sig.signed_hash,
+                        )),
+                        signature_hex: sig.signature.as_ref().map(hex::encode),
+                        signature: sig,
+                    });
+                }
+            }
+        }
+
+        let mut audit_entries = Vec::new();
+        if let Ok(iter) = self.audit_log.iter() {
+            for entry in iter {
+                if entry.entity_id == document_id {
+                    audit_entries.push(entry);
+                }
+            }
+        }
+
+        let mut access_events = Vec::new();
+        if let Ok(Some(access_log)) = self.document_access_log.get(&document_id) {
+            if let Ok(iter) = access_log.iter() {
+                access_events.extend(iter);
+            }
+        }
+
+        Ok(EvidencePackage {
+            agreement_digest: self.get_agreement_digest()?,
+            document_digest: canonical::digest(&canonical::document_bytes(&document)),
+            versions: self.list_document_versions(document_id.clone())?,
+            consents: self.get_consent_history(document_id.clone())?,
+            document_id,
+            name: document.name,
+            document_number: document.document_number,
+            status: document.status,
+            completed_at: document.completed_at,
+            final_blob_id: bs58::encode(&document.pdf_blob_id).into_string(),
+            final_hash: document.hash,
+            signatures,
+            audit_entries,
+            access_events,
+            context_id,
+            generated_by: current_caller(),
+            generated_at: TimestampNanos::now(),
+        })
+    }
+
+    /// Get the canonical SHA-256 digest of this agreement (context, name and participants)
+    pub fn get_agreement_digest(&self) -> Result<String, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Agreement digests are only available in shared contexts",
+            ));
+        }
+
+        let mut participants = Vec::new();
+        if let Ok(iter) = self.participants.iter() {
+            participants.extend(iter);
+        }
+
+        Ok(canonical::digest(&canonical::agreement_bytes(
+            &env::context_id(),
+            self.context_name.get(),
+            &participants,
+        )))
+    }
+
+    /// Record that a document is shared externally; gateways honor the returned descriptor
+    /// until it expires or is revoked (admin only). `signature_hex` is the caller's ed25519
+    /// signature over the canonical share descriptor payload.
+    pub fn create_share_descriptor(
+        &mut self,
+        document_id: String,
+        scope: ShareScope,
+        expires_at: u64,
+        signature_hex: String,
+    ) -> Result<ShareDescriptor, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let created_at = TimestampNanos::now();
+        if expires_at <= created_at.as_nanos() {
+            return Err(MeroDocsError::invalid_input(
+                "Share descriptor expiry must be in the future",
+            ));
+        }
+
+        let created_by = current_caller();
+        let signature = hex::decode(&signature_hex)
+            .map_err(|_| MeroDocsError::invalid_input("Signature must be hex-encoded"))?;
+        verify_share_descriptor_payload(
+            &created_by,
+            &document_id,
+            &document.hash,
+            &scope,
+            expires_at,
+            &signature,
+        )?;
+
+        // The digest binds the descriptor to the exact document version and terms
+        let digest = canonical::digest(&canonical::share_descriptor_bytes(
+            &env::context_id(),
+            &document_id,
+            &document.hash,
+            &scope,
+            &created_by,
+            created_at,
+            expires_at,
+        ));
+        let id = format!("share_{}", &digest[..16]);
+
+        if self.share_descriptors.contains(&id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Share descriptor already exists",
+            ));
+        }
+
+        let descriptor = ShareDescriptor {
+            id: id.clone(),
+            document_id: document_id.clone(),
+            document_hash: document.hash,
+            scope,
+            created_by,
+            created_at,
+            expires_at: TimestampNanos::from_nanos(expires_at),
+            revoked_at: None,
+            digest,
+            signature,
+        };
+
+        self.share_descriptors
+            .insert(id.clone(), descriptor.clone())
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store share descriptor: {:?}", e))
+            })?;
+
+        self.record_audit(
+            AuditAction::ShareDescriptorCreated,
+            &document_id,
+            format!("descriptor={} expires_at={}", id, expires_at),
+        )?;
+
+        app::emit!(MeroSignEvent::ShareDescriptorCreated {
+            id,
+            document_id,
+            expires_at,
+        });
+
+        Ok(descriptor)
+    }
+
+    /// Revoke an external share descriptor (admin only)
+    pub fn revoke_share_descriptor(&mut self, descriptor_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut descriptor = match self.share_descriptors.get(&descriptor_id) {
+            Ok(Some(descriptor)) => descriptor,
+            Ok(None) => return Err(MeroDocsError::not_found("Share descriptor not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get share descriptor: {:?}",
+                    e
+                )))
+            }
+        };
+
+        if descriptor.revoked_at.is_some() {
+            return Err(MeroDocsError::invalid_state(
+                "Share descriptor is already revoked",
+            ));
+        }
+
+        descriptor.revoked_at = Some(TimestampNanos::now());
+        let document_id = descriptor.document_id.clone();
+
+        self.share_descriptors
+            .insert(descriptor_id.clone(), descriptor)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to revoke share descriptor: {:?}", e))
+            })?;
+
+        self.record_audit(
+            AuditAction::ShareDescriptorRevoked,
+            &document_id,
+            format!("descriptor={}", descriptor_id),
+        )?;
+
+        app::emit!(MeroSignEvent::ShareDescriptorRevoked {
+            id: descriptor_id,
+            document_id,
+        });
+
+        Ok(())
+    }
+
+    /// Get a share descriptor so a gateway can check it is still valid
+    pub fn get_share_descriptor(
+        &self,
+        descriptor_id: String,
+    ) -> Result<ShareDescriptor, MeroDocsError> {
+        match self.share_descriptors.get(&descriptor_id) {
+            Ok(Some(descriptor)) => Ok(descriptor),
+            Ok(None) => Err(MeroDocsError::not_found("Share descriptor not found")),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get share descriptor: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// List share descriptors issued for a document the caller can see
+    pub fn list_share_descriptors(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<ShareDescriptor>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut descriptors = Vec::new();
+        if let Ok(entries) = self.share_descriptors.entries() {
+            for (_, descriptor) in entries {
+                if descriptor.document_id == document_id {
+                    descriptors.push(descriptor.clone());
+                }
+            }
+        }
+        Ok(descriptors)
+    }
+
+    /// Turn a feature on for this context (admin only)
+    pub fn enable_feature(&mut self, name: String) -> Result<(), MeroDocsError> {
+        self.set_feature(name, true)
+    }
+
+    /// Turn a feature off for this context (admin only)
+    pub fn disable_feature(&mut self, name: String) -> Result<(), MeroDocsError> {
+        self.set_feature(name, false)
+    }
+
+    /// Store a feature's state after checking the caller and the feature name
+    fn set_feature(&mut self, name: String, enabled: bool) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if !FEATURES.iter().any(|(feature, _)| *feature == name) {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Unknown feature: {}",
+                name
+            )));
+        }
+
+        self.feature_flags
+            .insert(name.clone(), enabled.into())
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store feature flag: {:?}", e))
+            })?;
+        self.record_audit(
+            AuditAction::FeatureToggled,
+            &name,
+            format!("enabled={}", enabled),
+        )?;
+
+        app::emit!(MeroSignEvent::FeatureToggled { name, enabled });
+
+        Ok(())
+    }
+
+    /// Check whether a feature is enabled in this context
+    pub fn is_feature_enabled(&self, name: String) -> Result<bool, MeroDocsError> {
+        if !FEATURES.iter().any(|(feature, _)| *feature == name) {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Unknown feature: {}",
+                name
+            )));
+        }
+        Ok(self.feature_enabled(&name))
+    }
+
+    /// List every known feature and whether it is enabled, for client feature detection
+    pub fn list_features(&self) -> Result<Vec<FeatureStatus>, MeroDocsError> {
+        Ok(FEATURES
+            .iter()
+            .map(|(name, _)| FeatureStatus {
+                name: name.to_string(),
+                enabled: self.feature_enabled(name),
+            })
+            .collect())
+    }
+
+    /// Current state of a feature, falling back to its default when never toggled
+    fn feature_enabled(&self, name: &str) -> bool {
+        match self.feature_flags.get(name) {
+            Ok(Some(enabled)) => *enabled.get(),
+            _ => FEATURES
+                .iter()
+                .find(|(feature, _)| *feature == name)
+                .is_some_and(|(_, default)| *default),
+        }
+    }
+
+    /// Fail with a clear error when a feature is switched off in this context
+    fn require_feature(&self, name: &str) -> Result<(), MeroDocsError> {
+        if self.feature_enabled(name) {
+            Ok(())
+        } else {
+            Err(MeroDocsError::feature_disabled(format!(
+                "Feature '{}' is disabled in this context",
+                name
+            )))
+        }
+    }
+
+    /// Set the caller's consent on a document, recording the consent text version they saw
+    pub fn set_consent(
+        &mut self,
+        user_id_str: String,
+        document_id: String,
+        consent_text_hash: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if user_id != current_caller() {
+            return Err(MeroDocsError::unauthorized(
+                "Consent can only be given by the calling identity",
+            ));
+        }
+        self.record_consent(user_id, &document_id, true, consent_text_hash.clone())?;
+        self.refresh_pending_action(&user_id, &document_id)?;
+
+        self.record_audit(
+            AuditAction::ConsentGiven,
+            &document_id,
+            match consent_text_hash {
+                Some(hash) => format!("user={} text_hash={}", user_id_str, hash),
+                None => format!("user={}", user_id_str),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdraw the caller's consent on a document; they cannot sign it until consenting again
+    pub fn withdraw_consent(
+        &mut self,
+        user_id_str: String,
+        document_id: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if user_id != current_caller() {
+            return Err(MeroDocsError::unauthorized(
+                "Consent can only be withdrawn by the calling identity",
+            ));
+        }
+        if !self.check_consent(&user_id, &document_id)? {
+            return Err(MeroDocsError::consent_required(
+                "User has not consented to this document",
+            ));
+        }
+
+        self.record_consent(user_id, &document_id, false, None)?;
+        self.refresh_pending_action(&user_id, &document_id)?;
+
+        self.record_audit(
+            AuditAction::ConsentWithdrawn,
+            &document_id,
+            format!("user={}", user_id_str),
+        )?;
+
+        Ok(())
+    }
+
+    /// Get every consent decision recorded on a document, oldest first
+    pub fn get_consent_history(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<ConsentRecord>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut records = Vec::new();
+        if let Ok(Some(history)) = self.consent_history.get(&document_id) {
+            if let Ok(iter) = history.iter() {
+                for record in iter {
+                    records.push(record.clone());
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Update the current consent flag and append the decision to the document's history
+    fn record_consent(
+        &mut self,
+        user_id: UserId,
+        document_id: &str,
+        given: bool,
+        consent_text_hash: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        let key = format!("{}|{}", bs58::encode(&user_id).into_string(), document_id);
+        self.consents
+            .insert(key, given.into())
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store consent: {:?}", e)))?;
+
+        let mut history = self
+            .consent_history
+            .get(document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get consent history: {:?}", e)))?
+            .unwrap_or_else(Vector::new);
+        history
+            .push(ConsentRecord {
+                user_id,
+                document_id: document_id.to_string(),
+                given,
+                consent_text_hash,
+                recorded_at: TimestampNanos::now(),
+            })
+            .map_err(|e| MeroDocsError::storage(format!("Failed to record consent: {:?}", e)))?;
+        self.consent_history
+            .insert(document_id.to_string(), history)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update consent history: {:?}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Check if user has given consent for a document (internal helper)
+    fn check_consent(&self, user_id: &UserId, document_id: &str) -> Result<bool, MeroDocsError> {
+        let key = format!("{}|{}", bs58::encode(user_id).into_string(), document_id);
+        match self.consents.get(&key) {
+            Ok(Some(consented)) => Ok(*consented.get()),
+            Ok(None) => Ok(false),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to check consent: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Check if user has given consent for a document (public API)
+    pub fn has_consented(
+        &self,
+        user_id_str: String,
+        document_id: String,
+    ) -> Result<bool, MeroDocsError> {
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        self.check_consent(&user_id, &document_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_document(
+        &mut self,
+        document_id: String,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        new_hash: String,
+        signer_id_str: String,
+        field_ids: Option<Vec<String>>,
+        signature_hex: Option<String>,
+        page_count: Option<u32>,
+        organization_id: Option<String>,
+        capacity: Option<SignatureCapacity>,
+    ) -> Result<(), MeroDocsError> {
+        let signer_id = parse_public_key_base58(&signer_id_str)?;
+        if signer_id != current_caller() {
+            return Err(MeroDocsError::unauthorized(
+                "Signer must be the calling identity",
+            ));
+        }
+        let signed_pdf = SignedPdf {
+            pdf_blob_id_str,
+            file_size,
+            new_hash,
+            field_ids,
+            signature_hex,
+            page_count,
+            organization_id,
+            capacity: capacity.unwrap_or(SignatureCapacity::Party),
+        };
+        self.record_signature(document_id, signed_pdf, signer_id, None)
+    }
+
+    /// Sign a document for a required signer who delegated signing authority to the caller
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_document_on_behalf(
+        &mut self,
+        document_id: String,
+        delegator_str: String,
+        pdf_blob_id_str: String,
+        file_size: u64,
+        new_hash: String,
+        field_ids: Option<Vec<String>>,
+        signature_hex: Option<String>,
+        page_count: Option<u32>,
+        organization_id: Option<String>,
+        capacity: Option<SignatureCapacity>,
+    ) -> Result<(), MeroDocsError> {
+        let delegator = parse_public_key_base58(&delegator_str)?;
+        let delegate = current_caller();
+
+        let delegation = match self
+            .signing_delegations
+            .get(&delegation_key(&document_id, &delegator))
+        {
+            Ok(Some(delegation)) => delegation,
+            Ok(None) => return Err(MeroDocsError::not_found("No signing delegation found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get delegation: {:?}",
+                    e
+                )))
+            }
+        };
+        if delegation.delegate != delegate {
+            return Err(MeroDocsError::unauthorized(
+                "Signing authority was delegated to another participant",
+            ));
+        }
+        if !delegation.is_active(env::time_now()) {
+            return Err(MeroDocsError::invalid_state(
+                "Signing delegation has expired or been revoked",
+            ));
+        }
+
+        let signed_pdf = SignedPdf {
+            pdf_blob_id_str,
+            file_size,
+            new_hash,
+            field_ids,
+            signature_hex,
+            page_count,
+            organization_id,
+            capacity: capacity.unwrap_or(SignatureCapacity::Party),
+        };
+        self.record_signature(document_id, signed_pdf, delegate, Some(delegator))
+    }
+
+    /// Apply a signature to a document. `signed_by` is who performed it; the signature
+    /// counts for `on_behalf_of` when signing under a delegation.
+    fn record_signature(
+        &mut self,
+        document_id: String,
+        signed_pdf: SignedPdf,
+        signed_by: UserId,
+        on_behalf_of: Option<UserId>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let signer_id = on_behalf_of.unwrap_or(signed_by);
+        if self.is_deactivated(&signer_id) {
+            return Err(MeroDocsError::unauthorized(
+                "Deactivated participants cannot sign documents",
+            ));
+        }
+        if self.oversight_roles.contains(&signed_by).unwrap_or(false)
+            || self.oversight_roles.contains(&signer_id).unwrap_or(false)
+        {
+            return Err(MeroDocsError::unauthorized(
+                "Observers and auditors cannot sign documents",
+            ));
+        }
+
+        let has_consent = self.check_consent(&signed_by, &document_id)?;
+        if !has_consent {
+            return Err(MeroDocsError::consent_required(
+                "User must provide consent before signing this document",
+            ));
+        }
+
+        let signature_bytes = match &signed_pdf.signature_hex {
+            Some(signature_hex) => {
+                let bytes = hex::decode(signature_hex)
+                    .map_err(|_| MeroDocsError::invalid_input("Signature must be hex-encoded"))?;
+                verify_signing_payload(&signed_by, &document_id, &signed_pdf.new_hash, &bytes)?;
+                Some(bytes)
+            }
+            None if self.feature_enabled("require_signature_payloads") => {
+                return Err(MeroDocsError::invalid_input(
+                    "A cryptographic signature over the document hash is required",
+                ))
+            }
+            None => None,
+        };
+
+        if let Some(organization_id) = &signed_pdf.organization_id {
+            let organization = self.get_organization(organization_id)?;
+            if !organization.members.contains(&signer_id) {
+                return Err(MeroDocsError::unauthorized(
+                    "Signer is not a member of this organization",
+                ));
+            }
+        }
+
+        let mut document = self.get_visible_document(&document_id)?;
+
+        if !self.has_capability(&signer_id, roles::SIGN) {
+            return Err(MeroDocsError::unauthorized(
+                "Signer's role does not allow signing",
+            ));
+        }
+        match self.document_permission(&document, &signer_id) {
+            Some(PermissionLevel::Sign | PermissionLevel::Admin) => {}
+            Some(PermissionLevel::Read) if document.access_list.is_empty() => {}
+            Some(PermissionLevel::Read) => {
+                return Err(MeroDocsError::unauthorized(
+                    "Signer only has read access to this document",
+                ))
+            }
+            None => {
+                return Err(MeroDocsError::unauthorized(
+                    "Signer does not have access to this document",
+                ))
+            }
+        }
+
+        if signed_pdf.capacity != SignatureCapacity::Party {
+            if self.required_signers_for(&document).contains(&signer_id) {
+                return Err(MeroDocsError::invalid_input(
+                    "A required signer cannot also witness or notarize the document",
+                ));
+            }
+            if self
+                .signatures_in_capacity(&document_id, signed_pdf.capacity)?
+                .contains(&signer_id)
+            {
+                return Err(MeroDocsError::already_exists(format!(
+                    "Signer has already signed this document as {:?}",
+                    signed_pdf.capacity
+                )));
+            }
+        }
+
+        if document
+            .expires_at
+            .is_some_and(|t| t.has_passed(TimestampNanos::now()))
+        {
+            return Err(MeroDocsError::invalid_state("Document has expired"));
+        }
+
+        if let Some(order) = &document.signing_order {
+            // Signers in the chain must wait for everyone ahead of them
+            if let Some(position) = order.signers.iter().position(|s| *s == signer_id) {
+                let signed = self.signed_users(&document_id)?;
+                if let Some(waiting_on) = order.signers[..position]
+                    .iter()
+                    .find(|s| !signed.contains(s))
+                {
+                    return Err(MeroDocsError::invalid_state(format!(
+                        "Signing order not satisfied: waiting on {}",
+                        bs58::encode(waiting_on).into_string()
+                    )));
+                }
+            }
+        }
+
+        let now = env::time_now();
+        for field_id in signed_pdf.field_ids.unwrap_or_default() {
+            let field = document
+                .signature_fields
+                .iter_mut()
+                .find(|f| f.id == field_id)
+                .ok_or_else(|| {
+                    MeroDocsError::not_found(format!("Signature field not found: {}", field_id))
+                })?;
+            if field.filled_by.is_some() {
+                return Err(MeroDocsError::already_exists(format!(
+                    "Signature field already filled: {}",
+                    field_id
+                )));
+            }
+            if field.assigned_signer.is_some_and(|s| s != signer_id) {
+                return Err(MeroDocsError::unauthorized(format!(
+                    "Signature field {} is assigned to another signer",
+                    field_id
+                )));
+            }
+            field.filled_by = Some(signer_id);
+            field.filled_at = Some(TimestampNanos::from_nanos(now));
+        }
+
+        let pdf_blob_id = parse_blob_id_base58(&signed_pdf.pdf_blob_id_str)?;
+
+        // Only change status once every check has passed, so a rejected signature
+        // never emits DocumentStatusChanged
+        self.transition_document(&mut document, StatusTrigger::Signed)?;
+
+        // Announce the signed blob to the network for discovery
+        self.announce_blobs(&[pdf_blob_id])?;
+
+        document.pdf_blob_id = pdf_blob_id;
+        document.size = signed_pdf.file_size;
+        document.hash = signed_pdf.new_hash.clone();
+        if signed_pdf.page_count.is_some() {
+            document.page_count = signed_pdf.page_count;
+        }
+        if document.first_signed_at.is_none() {
+            document.first_signed_at = Some(TimestampNanos::now());
+        }
+        document.key_epoch = self.current_key_epoch();
+
+        self.push_document_version(&document, signed_by, "Signed")?;
+
+        self.save_document(document)?;
+
+        let signature = DocumentSignature {
+            signer: signer_id,
+            signed_at: TimestampNanos::now(),
+            signed_by,
+            on_behalf_of,
+            signed_hash: signed_pdf.new_hash,
+            signature: signature_bytes,
+            organization_id: signed_pdf.organization_id,
+            capacity: signed_pdf.capacity,
+        };
+
+        let mut signatures = self
+            .document_signatures
+            .get(&document_id)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get document signatures: {:?}", e))
+            })?
+            .unwrap_or_else(Vector::new);
+
+        signatures
+            .push(signature)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to add signature: {:?}", e)))?;
+
+        self.document_signatures
+            .insert(document_id.clone(), signatures)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update document signatures: {:?}", e))
+            })?;
+        self.record_change(ChangeEntity::Signature, &document_id, ChangeKind::Created)?;
+        self.refresh_pending_action(&signer_id, &document_id)?;
+
+        self.record_audit(
+            AuditAction::DocumentSigned,
+            &document_id,
+            match on_behalf_of {
+                Some(delegator) => format!(
+                    "signer={} on_behalf_of={}",
+                    bs58::encode(&signed_by).into_string(),
+                    bs58::encode(&delegator).into_string()
+                ),
+                None => format!("signer={}", bs58::encode(&signer_id).into_string()),
+            },
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentSigned {
+            document_id,
+            signer: signer_id,
+            on_behalf_of,
+        });
+
+        Ok(())
+    }
+
+    // === ENVELOPES ===
+
+    /// Group documents that must be signed together; returns the envelope ID
+    pub fn create_envelope(
+        &mut self,
+        name: String,
+        document_ids: Vec<String>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::UPLOAD)?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Envelope name cannot be empty",
+            ));
+        }
+
+        let mut ids: Vec<String> = Vec::with_capacity(document_ids.len());
+        for document_id in document_ids {
+            if ids.contains(&document_id) {
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Duplicate document in envelope: {}",
+                    document_id
+                )));
+            }
+            let document = self.get_visible_document(&document_id)?;
+            if !matches!(
+                document.status,
+                DocumentStatus::Pending | DocumentStatus::PartiallySigned
+            ) {
+                return Err(MeroDocsError::invalid_state(format!(
+                    "Document {} is no longer awaiting signatures",
+                    document_id
+                )));
+            }
+            ids.push(document_id);
+        }
+        if ids.len() < 2 {
+            return Err(MeroDocsError::invalid_input(
+                "An envelope needs at least two documents",
+            ));
+        }
+
+        let now = TimestampNanos::now();
+        let envelope = Envelope {
+            id: format!("envelope_{}_{}", now.as_nanos(), name),
+            name: name.clone(),
+            document_ids: ids,
+            created_by: current_caller(),
+            created_at: now,
+            updated_at: now,
+        };
+        let envelope_id = envelope.id.clone();
+        let details = format!(
+            "name={} documents={}",
+            name,
+            envelope.document_ids.join(",")
+        );
+        self.envelopes
+            .insert(envelope_id.clone(), envelope)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store envelope: {:?}", e)))?;
+
+        self.record_audit(AuditAction::EnvelopeCreated, &envelope_id, details)?;
+
+        Ok(envelope_id)
+    }
+
+    pub fn get_envelope(&self, envelope_id: String) -> Result<Envelope, MeroDocsError> {
+        match self.envelopes.get(&envelope_id) {
+            Ok(Some(envelope)) => Ok(envelope),
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Envelope not found: {}",
+                envelope_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get envelope: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// List envelopes whose documents are all visible to the caller, sorted by name
+    pub fn list_envelopes(&self) -> Result<Vec<Envelope>, MeroDocsError> {
+        let mut envelopes: Vec<Envelope> = self
+            .envelopes
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list envelopes: {:?}", e)))?
+            .map(|(_, envelope)| envelope)
+            .filter(|envelope| {
+                envelope
+                    .document_ids
+                    .iter()
+                    .all(|id| self.get_visible_document(id).is_ok())
+            })
+            .collect();
+        envelopes.sort_by_key(|envelope| envelope.name.to_lowercase());
+        Ok(envelopes)
+    }
+
+    /// Sign every document in an envelope as the caller. Consent and status are checked
+    /// for all documents before any signature is applied, and any failure fails the whole
+    /// call, so the packet is executed as a set or not at all.
+    pub fn sign_envelope(
+        &mut self,
+        envelope_id: String,
+        documents: Vec<EnvelopeDocumentSignature>,
+        organization_id: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let envelope = self.get_envelope(envelope_id.clone())?;
+        let signer = current_caller();
+
+        if documents.len() != envelope.document_ids.len() {
+            return Err(MeroDocsError::invalid_input(
+                "Provide exactly one signed PDF per document in the envelope",
+            ));
+        }
+
+        let mut submissions = Vec::with_capacity(envelope.document_ids.len());
+        for document_id in &envelope.document_ids {
+            let submission = match documents.iter().find(|d| d.document_id == *document_id) {
+                Some(submission) => submission.clone(),
+                None => {
+                    return Err(MeroDocsError::invalid_input(format!(
+                        "Missing signed PDF for document {}",
+                        document_id
+                    )))
+                }
+            };
+
+            if !self.check_consent(&signer, document_id)? {
+                return Err(MeroDocsError::consent_required(format!(
+                    "Consent required before signing document {}",
+                    document_id
+                )));
+            }
+            let document = self.get_visible_document(document_id)?;
+            if !matches!(
+                document.status,
+                DocumentStatus::Pending | DocumentStatus::PartiallySigned
+            ) {
+                return Err(MeroDocsError::invalid_state(format!(
+                    "Document {} is no longer awaiting signatures",
+                    document_id
+                )));
+            }
+            if self.signed_users(document_id)?.contains(&signer) {
+                return Err(MeroDocsError::already_exists(format!(
+                    "Document {} is already signed by this signer",
+                    document_id
+                )));
+            }
+            submissions.push(submission);
+        }
+
+        for submission in submissions {
+            let signed_pdf = SignedPdf {
+                pdf_blob_id_str: submission.pdf_blob_id_str,
+                file_size: submission.file_size,
+                new_hash: submission.new_hash,
+                field_ids: submission.field_ids,
+                signature_hex: submission.signature_hex,
+                page_count: submission.page_count,
+                organization_id: organization_id.clone(),
+                capacity: SignatureCapacity::Party,
+            };
+            self.record_signature(submission.document_id, signed_pdf, signer, None)?;
+        }
+
+        self.record_audit(
+            AuditAction::EnvelopeSigned,
+            &envelope_id,
+            format!("signer={}", bs58::encode(&signer).into_string()),
+        )?;
+
+        app::emit!(MeroSignEvent::EnvelopeSigned {
+            envelope_id,
+            signer,
+            document_ids: envelope.document_ids,
+        });
+
+        Ok(())
+    }
+
+    /// Place a new signature field on a document (admin only)
+    pub fn add_signature_field(
+        &mut self,
+        document_id: String,
+        field: SignatureFieldSpec,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        self.require_feature("signature_fields")?;
+
+        let field = build_signature_field(field, &document.signature_fields, &document.page_sizes)?;
+        let field_id = field.id.clone();
+        document.signature_fields.push(field);
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::SignatureFieldAdded,
+            &document_id,
+            format!("field={}", field_id),
+        )?;
+
+        Ok(field_id)
+    }
+
+    /// Remove an unfilled signature field from a document (admin only)
+    pub fn remove_signature_field(
+        &mut self,
+        document_id: String,
+        field_id: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let position = document
+            .signature_fields
+            .iter()
+            .position(|f| f.id == field_id)
+            .ok_or_else(|| {
+                MeroDocsError::not_found(format!("Signature field not found: {}", field_id))
+            })?;
+        if document.signature_fields[position].filled_by.is_some() {
+            return Err(MeroDocsError::invalid_state(
+                "Cannot remove a signature field that has been filled",
+            ));
+        }
+        document.signature_fields.remove(position);
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::SignatureFieldRemoved,
+            &document_id,
+            format!("field={}", field_id),
+        )?;
+
+        Ok(())
+    }
+
+    /// Store the size of each page of a document so signature fields can be checked against
+    /// the page bounds (manage capability). Fails if an existing field would fall outside.
+    pub fn set_page_geometry(
+        &mut self,
+        document_id: String,
+        page_sizes: Vec<PageSize>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        if page_sizes.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Page geometry must describe at least one page",
+            ));
+        }
+        validate_page_sizes(&page_sizes)?;
+        for field in &document.signature_fields {
+            check_field_on_page(
+                field.page,
+                field.x,
+                field.y,
+                field.width,
+                field.height,
+                &page_sizes,
+            )
+            .map_err(|e| MeroDocsError::invalid_input(format!("Field {}: {}", field.id, e)))?;
+        }
+
+        let page_count = page_sizes.len() as u32;
+        document.page_count = Some(page_count);
+        document.page_sizes = page_sizes;
+
+        self.save_document(document)?;
+
+        self.record_audit(
+            AuditAction::PageGeometryChanged,
+            &document_id,
+            format!("pages={}", page_count),
+        )?;
+
+        Ok(())
+    }
+
+    /// Let another participant sign a document on the caller's behalf until `expires_at`
+    pub fn delegate_signing(
+        &mut self,
+        document_id: String,
+        delegate_str: String,
+        expires_at: u64,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        self.require_feature("signing_delegation")?;
+
+        let delegator = current_caller();
+        let delegate = parse_public_key_base58(&delegate_str)?;
+
+        if delegate == delegator {
+            return Err(MeroDocsError::invalid_input(
+                "Cannot delegate signing to yourself",
+            ));
+        }
+        if !self.participants.contains(&delegate).unwrap_or(false) {
+            return Err(MeroDocsError::invalid_input(
+                "Delegate must be a participant",
+            ));
+        }
+        if self.oversight_roles.contains(&delegate).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Observers and auditors cannot sign documents",
+            ));
+        }
+
+        let now = env::time_now();
+        if expires_at <= now {
+            return Err(MeroDocsError::invalid_input(
+                "Delegation expiry must be in the future",
+            ));
+        }
+
+        let document = self.get_visible_document(&document_id)?;
+        if !self.outstanding_signers(&document)?.contains(&delegator) {
+            return Err(MeroDocsError::unauthorized(
+                "Only required signers who have not signed can delegate",
+            ));
+        }
+
+        let delegation = SigningDelegation {
+            document_id: document_id.clone(),
+            delegator,
+            delegate,
+            created_at: TimestampNanos::from_nanos(now),
+            expires_at: TimestampNanos::from_nanos(expires_at),
+            revoked_at: None,
+        };
+        self.signing_delegations
+            .insert(delegation_key(&document_id, &delegator), delegation)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store delegation: {:?}", e)))?;
+        self.record_audit(
+            AuditAction::SigningDelegated,
+            &document_id,
+            format!("delegate={} expires_at={}", delegate_str, expires_at),
+        )?;
+
+        app::emit!(MeroSignEvent::SigningDelegated {
+            document_id,
+            delegator,
+            delegate,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke the caller's signing delegation on a document
+    pub fn revoke_signing_delegation(&mut self, document_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let delegator = current_caller();
+        let key = delegation_key(&document_id, &delegator);
+
+        let mut delegation = match self.signing_delegations.get(&key) {
+            Ok(Some(delegation)) => delegation,
+            Ok(None) => return Err(MeroDocsError::not_found("No signing delegation found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get delegation: {:?}",
+                    e
+                )))
+            }
+        };
+        if delegation.revoked_at.is_some() {
+            return Err(MeroDocsError::invalid_state(
+                "Signing delegation already revoked",
+            ));
+        }
+
+        delegation.revoked_at = Some(TimestampNanos::now());
+        self.signing_delegations
+            .insert(key, delegation)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to revoke delegation: {:?}", e)))?;
+        self.record_audit(
+            AuditAction::SigningDelegationRevoked,
+            &document_id,
+            String::new(),
+        )?;
+
+        app::emit!(MeroSignEvent::SigningDelegationRevoked {
+            document_id,
+            delegator,
+        });
+
+        Ok(())
+    }
+
+    /// List the signing delegations granted on a document, including revoked ones
+    pub fn list_signing_delegations(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<SigningDelegation>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut delegations = Vec::new();
+        if let Ok(entries) = self.signing_delegations.entries() {
+            for (_, delegation) in entries {
+                if delegation.document_id == document_id {
+                    delegations.push(delegation);
+                }
+            }
+        }
+        Ok(delegations)
+    }
+
+    /// Formally reject a document; blocks further signatures until an admin reopens it
+    pub fn decline_document(
+        &mut self,
+        document_id: String,
+        reason: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let executor_id = current_caller();
+        if !self.participants.contains(&executor_id).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Only participants can decline documents",
+            ));
+        }
+        if self.oversight_roles.contains(&executor_id).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Observers and auditors cannot decline documents",
+            ));
+        }
+        if reason.trim().is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "A reason is required to decline a document",
+            ));
+        }
+
+        let mut document = self.get_visible_document(&document_id)?;
+        self.transition_document(&mut document, StatusTrigger::Declined)?;
+
+        let decline = DocumentDecline {
+            document_id: document_id.clone(),
+            declined_by: executor_id,
+            reason: reason.clone(),
+            declined_at: TimestampNanos::now(),
+        };
+        self.document_declines
+            .insert(document_id.clone(), decline)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store decline: {:?}", e)))?;
+
+        self.save_document(document)?;
+
+        self.record_audit(
+            AuditAction::DocumentDeclined,
+            &document_id,
+            format!("reason={}", reason),
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentDeclined {
+            document_id,
+            declined_by: executor_id,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    /// Get the decline recorded for a document, if it is currently declined
+    pub fn get_document_decline(
+        &self,
+        document_id: String,
+    ) -> Result<Option<DocumentDecline>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+        self.document_declines
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get decline: {:?}", e)))
+    }
+
+    /// Reopen a declined document for signing (admin only)
+    pub fn reopen_document(&mut self, document_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+        self.transition_document(&mut document, StatusTrigger::Reopened)?;
+        self.save_document(document)?;
+
+        self.document_declines
+            .remove(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to clear decline: {:?}", e)))?;
+
+        self.record_audit(AuditAction::DocumentReopened, &document_id, String::new())?;
+
+        app::emit!(MeroSignEvent::DocumentReopened { document_id });
+
+        Ok(())
+    }
+
+    /// Withdraw a document that is still awaiting signatures (uploader or admin). Unlike
+    /// deleting it, the document and its signatures stay on record with the reason.
+    pub fn void_document(
+        &mut self,
+        document_id: String,
+        reason: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Documents can only be voided in shared context",
+            ));
+        }
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "A reason is required to void a document",
+            ));
+        }
+
+        let executor_id = current_caller();
+        let mut document = self.get_visible_document(&document_id)?;
+        if document.uploaded_by != executor_id && !self.has_capability(&executor_id, roles::MANAGE)
+        {
+            return Err(MeroDocsError::unauthorized(
+                "Only the uploader or an admin can void a document",
+            ));
+        }
+        self.transition_document(&mut document, StatusTrigger::Voided)?;
+
+        let void = DocumentVoid {
+            document_id: document_id.clone(),
+            voided_by: executor_id,
+            reason: reason.clone(),
+            voided_at: TimestampNanos::now(),
+        };
+        self.document_voids
+            .insert(document_id.clone(), void)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store void: {:?}", e)))?;
+
+        self.save_document(document)?;
+
+        self.record_audit(
+            AuditAction::DocumentVoided,
+            &document_id,
+            format!("reason={}", reason),
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentVoided {
+            document_id,
+            voided_by: executor_id,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    /// Get the reason a document was voided, if it was
+    pub fn get_document_void(
+        &self,
+        document_id: String,
+    ) -> Result<Option<DocumentVoid>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+        self.document_voids
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get void: {:?}", e)))
+    }
+
+    /// Set or clear the date a required signer has to sign by (admin only)
+    pub fn set_signer_due_date(
+        &mut self,
+        document_id: String,
+        signer_str: String,
+        due_at: Option<u64>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let signer = parse_public_key_base58(&signer_str)?;
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+
+        if !self.required_signers_for(&document).contains(&signer) {
+            return Err(MeroDocsError::invalid_input(
+                "User is not a required signer of this document",
+            ));
+        }
+
+        document.signer_due_dates.retain(|d| d.signer != signer);
+        if let Some(due_at) = due_at {
+            document.signer_due_dates.push(SignerDueDate {
+                signer,
+                due_at: TimestampNanos::from_nanos(due_at),
+            });
+        }
+
+        self.save_document(document)?;
+        self.record_audit(
+            AuditAction::SignerDueDateChanged,
+            &document_id,
+            match due_at {
+                Some(due_at) => format!("signer={} due_at={}", signer_str, due_at),
+                None => format!("signer={} due_at=none", signer_str),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the required signers who are past their due date and still have to sign
+    pub fn get_overdue_signers(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<SignerDueDate>, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        self.overdue_signers(&document, env::time_now())
+    }
+
+    /// Emit a reminder for every overdue signer on open documents, skipping signers who
+    /// snoozed the document; returns how many were sent
+    pub fn check_signing_deadlines(&mut self) -> Result<u64, MeroDocsError> {
+        let now = env::time_now();
+        let mut reminders = 0u64;
+        if let Ok(entries) = self.documents.entries() {
+            for (document_id, document) in entries {
+                if !matches!(
+                    document.status,
+                    DocumentStatus::Pending | DocumentStatus::PartiallySigned
+                ) {
+                    continue;
+                }
+                for overdue in self.overdue_signers(&document, now)? {
+                    if self.is_snoozed(&document_id, &overdue.signer, now) {
+                        continue;
+                    }
+                    app::emit!(MeroSignEvent::OverdueSignatureReminder {
+                        document_id: document_id.clone(),
+                        signer: overdue.signer,
+                        due_at: overdue.due_at.as_nanos(),
+                    });
+                    reminders += 1;
+                }
+            }
+        }
+        Ok(reminders)
+    }
+
+    /// Mute reminders and escalations for the caller on a document until `until`
+    /// (nanoseconds), or unmute with `None`
+    pub fn snooze_document(
+        &mut self,
+        document_id: String,
+        until: Option<u64>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let user_id = current_caller();
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Only participants can snooze documents",
+            ));
+        }
+        self.get_visible_document(&document_id)?;
+
+        let now = env::time_now();
+        let key = snooze_key(&document_id, &user_id);
+        match until {
+            Some(until) if until <= now => {
+                return Err(MeroDocsError::invalid_input(
+                    "Snooze must end in the future",
+                ))
+            }
+            Some(until) => {
+                self.document_snoozes
+                    .insert(
+                        key,
+                        DocumentSnooze {
+                            document_id: document_id.clone(),
+                            user_id,
+                            until,
+                            set_at: TimestampNanos::from_nanos(now),
+                        },
+                    )
+                    .map_err(|e| {
+                        MeroDocsError::storage(format!("Failed to store snooze: {:?}", e))
+                    })?;
+            }
+            None => {
+                self.document_snoozes.remove(&key).map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to remove snooze: {:?}", e))
+                })?;
+            }
+        }
+
+        self.record_audit(
+            AuditAction::DocumentSnoozed,
+            &document_id,
+            match until {
+                Some(until) => format!("until={}", until),
+                None => "until=none".to_string(),
+            },
+        )?;
+
+        app::emit!(MeroSignEvent::DocumentSnoozed {
+            document_id,
+            user_id,
+            until,
+        });
+
+        Ok(())
+    }
+
+    /// Participants whose reminders for a document are currently muted (manage capability)
+    pub fn list_document_snoozes(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<DocumentSnooze>, MeroDocsError> {
+        self.require_capability(roles::MANAGE)?;
+
+        let now = env::time_now();
+        let key_prefix = format!("{}:", document_id);
+        let mut snoozes = Vec::new();
+        if let Ok(entries) = self.document_snoozes.entries() {
+            for (key, snooze) in entries {
+                if key.starts_with(&key_prefix) && snooze.until > now {
+                    snoozes.push(snooze);
+                }
+            }
+        }
+        Ok(snoozes)
+    }
+
+    fn is_snoozed(&self, document_id: &str, user_id: &UserId, now: u64) -> bool {
+        matches!(
+            self.document_snoozes.get(&snooze_key(document_id, user_id)),
+            Ok(Some(snooze)) if snooze.until > now
+        )
+    }
+
+    // === PENDING ACTIONS ===
+
+    /// What the caller still has to do in this context, oldest first
+    pub fn get_my_pending_actions(&self) -> Result<Vec<PendingAction>, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Pending actions are only available in shared context",
+            ));
+        }
+
+        let mut actions = Vec::new();
+        let key = bs58::encode(&current_caller()).into_string();
+        if let Ok(Some(queue)) = self.pending_actions.get(&key) {
+            if let Ok(iter) = queue.iter() {
+                for action in iter {
+                    // Documents deleted, expired or hidden since the action was queued drop out
+                    let open = match self.documents.get(&action.document_id) {
+                        Ok(Some(document)) => {
+                            matches!(
+                                document.status,
+                                DocumentStatus::Pending | DocumentStatus::PartiallySigned
+                            ) && self.can_view_document(&document)
+                        }
+                        _ => false,
+                    };
+                    if open {
+                        actions.push(action);
+                    }
+                }
+            }
+        }
+        Ok(actions)
+    }
+
+    /// Replace a user's queued action on a document with their next step: consent if they
+    /// have not given it, otherwise sign. Clears it once they are no longer an outstanding
+    /// signer.
+    fn refresh_pending_action(
+        &mut self,
+        user_id: &UserId,
+        document_id: &str,
+    ) -> Result<(), MeroDocsError> {
+        let next = match self.documents.get(document_id) {
+            Ok(Some(document))
+                if matches!(
+                    document.status,
+                    DocumentStatus::Pending | DocumentStatus::PartiallySigned
+                ) && self.outstanding_signers(&document)?.contains(user_id) =>
+            {
+                let kind = if self.check_consent(user_id, document_id)? {
+                    PendingActionKind::Sign
+                } else {
+                    PendingActionKind::Consent
+                };
+                Some(PendingAction {
+                    kind,
+                    document_id: document_id.to_string(),
+                    document_name: document.name,
+                    requested_by: document.uploaded_by,
+                    created_at: TimestampNanos::now(),
+                })
+            }
+            _ => None,
+        };
+
+        let key = bs58::encode(user_id).into_string();
+        let mut actions = Vec::new();
+        if let Ok(Some(queue)) = self.pending_actions.get(&key) {
+            if let Ok(iter) = queue.iter() {
+                actions.extend(iter.filter(|action| action.document_id != document_id));
+            }
+        }
+        if actions.is_empty() && next.is_none() {
+            let _ = self.pending_actions.remove(&key);
+            return Ok(());
+        }
+        actions.extend(next);
+
+        let mut queue = Vector::new();
+        for action in actions {
+            queue.push(action).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to queue pending action: {:?}", e))
+            })?;
+        }
+        self.pending_actions.insert(key, queue).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to update pending actions: {:?}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Refresh every participant's queued action on a document after its signers changed
+    fn refresh_document_pending_actions(&mut self, document_id: &str) -> Result<(), MeroDocsError> {
+        let mut participants = Vec::new();
+        if let Ok(iter) = self.participants.iter() {
+            participants.extend(iter);
+        }
+        for participant in participants {
+            self.refresh_pending_action(&participant, document_id)?;
+        }
+        Ok(())
+    }
+
+    /// Due dates that have passed for signers who have not signed yet
+    fn overdue_signers(
+        &self,
+        document: &DocumentInfo,
+        now: u64,
+    ) -> Result<Vec<SignerDueDate>, MeroDocsError> {
+        let outstanding = self.outstanding_signers(document)?;
+        Ok(document
+            .signer_due_dates
+            .iter()
+            .filter(|d| {
+                d.due_at <= TimestampNanos::from_nanos(now) && outstanding.contains(&d.signer)
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Mark every overdue, not yet fully signed document as expired
+    pub fn reap_expired_documents(&mut self) -> Result<Vec<String>, MeroDocsError> {
+        self.ensure_writable()?;
+
+        let executor_id = current_caller();
+        if !self.participants.contains(&executor_id).unwrap_or(false)
+            && !self.has_capability(&executor_id, roles::MANAGE)
+        {
+            return Err(MeroDocsError::unauthorized(
+                "Only participants can reap expired documents",
+            ));
+        }
+
+        let now = env::time_now();
+        let mut overdue = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                let pending = matches!(
+                    document.status,
+                    DocumentStatus::Pending | DocumentStatus::PartiallySigned
+                );
+                if pending && document.expires_at.is_some_and(|t| now >= t.as_nanos()) {
+                    overdue.push((id, document));
+                }
+            }
+        }
+
+        let mut expired_ids = Vec::with_capacity(overdue.len());
+        for (id, mut document) in overdue {
+            self.transition_document(&mut document, StatusTrigger::Expired)?;
+            self.save_document(document)?;
+            self.record_audit(AuditAction::DocumentExpired, &id, String::new())?;
+
+            app::emit!(MeroSignEvent::DocumentExpired { id: id.clone() });
+            expired_ids.push(id);
+        }
+
+        Ok(expired_ids)
+    }
+
+    /// Re-check the cryptographic signature a signer attached to a document.
+    /// Returns false when the signer's signature carries no payload.
+    pub fn verify_document_signature(
+        &self,
+        document_id: String,
+        signer_str: String,
+    ) -> Result<bool, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+        let signer = parse_public_key_base58(&signer_str)?;
+
+        let mut found = None;
+        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    if sig.signer == signer {
+                        found = Some(sig.clone());
+                    }
+                }
+            }
+        }
+        let Some(sig) = found else {
+            return Err(MeroDocsError::invalid_state(
+                "Signer has not signed this document",
+            ));
+        };
+        let Some(bytes) = &sig.signature else {
+            return Ok(false);
+        };
+
+        Ok(verify_signing_payload(&sig.signed_by, &document_id, &sig.signed_hash, bytes).is_ok())
+    }
+
+    /// Get signatures for a document
+    pub fn get_document_signatures(
+        &self,
+        document_id: String,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<DocumentSignature>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut signatures = Vec::new();
+        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    signatures.push(sig.clone());
+                }
+            }
+        }
+        Ok(paginate(signatures, offset, limit))
+    }
+
+    /// Append the document's current PDF state to its revision history
+    fn push_document_version(
+        &mut self,
+        document: &DocumentInfo,
+        author: UserId,
+        reason: &str,
+    ) -> Result<(), MeroDocsError> {
+        let mut versions = self
+            .document_versions
+            .get(&document.id)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get document versions: {:?}", e))
+            })?
+            .unwrap_or_else(Vector::new);
+
+        let version = versions.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get document versions: {:?}", e))
+        })? as u64
+            + 1;
+
+        // Fingerprint the indexed text so later versions can show which passages changed
+        let chunk_hashes = self
+            .load_document_chunks(&document.id)?
+            .iter()
+            .map(|chunk| sha256_hex(chunk.text.trim().as_bytes()))
+            .collect();
+
+        versions
+            .push(DocumentVersion {
+                version,
+                pdf_blob_id: document.pdf_blob_id,
+                hash: document.hash.clone(),
+                size: document.size,
+                author,
+                created_at: TimestampNanos::now(),
+                reason: reason.to_string(),
+                page_count: document.page_count,
+                chunk_hashes,
+            })
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to add document version: {:?}", e))
+            })?;
+
+        self.document_versions
+            .insert(document.id.clone(), versions)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update document versions: {:?}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// List every recorded version of a document, oldest first
+    pub fn list_document_versions(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<DocumentVersion>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut versions = Vec::new();
+        if let Ok(Some(history)) = self.document_versions.get(&document_id) {
+            if let Ok(iter) = history.iter() {
+                for version in iter {
+                    versions.push(version.clone());
+                }
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Get a single version of a document by its version number
+    pub fn get_document_version(
+        &self,
+        document_id: String,
+        version: u64,
+    ) -> Result<DocumentVersion, MeroDocsError> {
+        self.list_document_versions(document_id)?
+            .into_iter()
+            .find(|v| v.version == version)
+            .ok_or_else(|| MeroDocsError::not_found(format!("Version {} not found", version)))
+    }
+
+    /// Record that the caller retrieved a document's current PDF. Clients call this after
+    /// downloading the blob. Like every other write it is refused in an archived context.
+    pub fn record_document_access(&mut self, document_id: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let document = self.get_visible_document(&document_id)?;
+        let accessed_by = current_caller();
+
+        let version = match self.document_versions.get(&document_id) {
+            Ok(Some(versions)) => versions.len().map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get document versions: {:?}", e))
+            })? as u64,
+            Ok(None) => 0,
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document versions: {:?}",
+                    e
+                )))
+            }
+        };
+
+        let mut access_log = self
+            .document_access_log
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get access log: {:?}", e)))?
+            .unwrap_or_else(Vector::new);
+        access_log
+            .push(DocumentAccessEvent {
+                accessed_by,
+                accessed_at: TimestampNanos::now(),
+                pdf_blob_id: document.pdf_blob_id,
+                hash: document.hash,
+                version,
+            })
+            .map_err(|e| MeroDocsError::storage(format!("Failed to record access: {:?}", e)))?;
+        self.document_access_log
+            .insert(document_id.clone(), access_log)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to update access log: {:?}", e)))?;
+
+        app::emit!(MeroSignEvent::DocumentAccessed {
+            document_id,
+            accessed_by,
+            version,
+        });
+
+        Ok(())
+    }
+
+    /// Who retrieved a document and which version they got, oldest first. Readable by
+    /// the uploader and by anyone who can read the audit log.
+    pub fn get_document_access_log(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<DocumentAccessEvent>, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        if document.uploaded_by != current_caller() && !self.can_read_audit_log() {
+            return Err(MeroDocsError::unauthorized(
+                "Only the uploader, admins and auditors can read the access log",
+            ));
+        }
+
+        let mut events = Vec::new();
+        if let Ok(Some(access_log)) = self.document_access_log.get(&document_id) {
+            if let Ok(iter) = access_log.iter() {
+                for event in iter {
+                    events.push(event.clone());
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    // === ATTACHMENTS ===
+
+    /// Link a supporting file to a document and announce its blob; returns the attachment ID
+    pub fn add_document_attachment(
+        &mut self,
+        document_id: String,
+        name: String,
+        blob_id_str: String,
+        mime_type: String,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::UPLOAD)?;
+        self.get_visible_document(&document_id)?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Attachment name cannot be empty",
+            ));
+        }
+        let mime_type = mime_type.trim().to_lowercase();
+        if !mime_type
+            .split_once('/')
+            .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty())
+        {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Invalid MIME type: {}",
+                mime_type
+            )));
+        }
+
+        let blob_id = parse_blob_id_base58(&blob_id_str)?;
+        self.announce_blobs(&[blob_id])?;
+
+        let now = env::time_now();
+        let attachment = DocumentAttachment {
+            id: format!("attachment_{}_{}", now, name),
+            document_id: document_id.clone(),
+            name: name.clone(),
+            blob_id,
+            mime_type,
+            added_by: current_caller(),
+            added_at: TimestampNanos::from_nanos(now),
+        };
+        let attachment_id = attachment.id.clone();
+
+        let mut attachments = self
+            .document_attachments
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get attachments: {:?}", e)))?
+            .unwrap_or_else(Vector::new);
+        attachments
+            .push(attachment)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to add attachment: {:?}", e)))?;
+        self.document_attachments
+            .insert(document_id.clone(), attachments)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update attachments: {:?}", e))
+            })?;
+
+        self.record_audit(
+            AuditAction::AttachmentAdded,
+            &document_id,
+            format!("attachment={} name={}", attachment_id, name),
+        )?;
+
+        app::emit!(MeroSignEvent::AttachmentAdded {
+            document_id,
+            attachment_id: attachment_id.clone(),
+            name,
+        });
+
+        Ok(attachment_id)
+    }
+
+    /// List a document's attachments, oldest first
+    pub fn list_document_attachments(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<DocumentAttachment>, MeroDocsError> {
+        self.get_visible_document(&document_id)?;
+
+        let mut attachments = Vec::new();
+        if let Ok(Some(stored)) = self.document_attachments.get(&document_id) {
+            if let Ok(iter) = stored.iter() {
+                attachments.extend(iter);
+            }
+        }
+        Ok(attachments)
+    }
+
+    /// Remove an attachment from a document (whoever added it, or the delete capability)
+    pub fn delete_document_attachment(
+        &mut self,
+        document_id: String,
+        attachment_id: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.get_visible_document(&document_id)?;
+
+        let mut attachments = self.list_document_attachments(document_id.clone())?;
+        let Some(position) = attachments.iter().position(|a| a.id == attachment_id) else {
+            return Err(MeroDocsError::not_found(format!(
+                "Attachment not found: {}",
+                attachment_id
+            )));
+        };
+        if attachments[position].added_by != current_caller() {
+            self.require_capability(roles::DELETE)?;
+        }
+        attachments.remove(position);
+
+        let mut stored = Vector::new();
+        for attachment in attachments {
+            stored.push(attachment).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update attachments: {:?}", e))
+            })?;
+        }
+        self.document_attachments
+            .insert(document_id.clone(), stored)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update attachments: {:?}", e))
+            })?;
+
+        self.record_audit(
+            AuditAction::AttachmentRemoved,
+            &document_id,
+            format!("attachment={}", attachment_id),
+        )?;
+
+        app::emit!(MeroSignEvent::AttachmentRemoved {
+            document_id,
+            attachment_id,
+        });
+
+        Ok(())
+    }
+
+    /// Compare two versions of a document: hash, size and page count changes, and which
+    /// chunks of indexed text differ
+    pub fn compare_document_versions(
+        &self,
+        document_id: String,
+        from_version: u64,
+        to_version: u64,
+    ) -> Result<VersionComparison, MeroDocsError> {
+        let versions = self.list_document_versions(document_id.clone())?;
+        let find = |number: u64| {
+            versions
+                .iter()
+                .find(|v| v.version == number)
+                .ok_or_else(|| MeroDocsError::not_found(format!("Version {} not found", number)))
+        };
+        let from = find(from_version)?;
+        let to = find(to_version)?;
+
+        let text_compared = !from.chunk_hashes.is_empty() && !to.chunk_hashes.is_empty();
+        let mut changed_chunks = Vec::new();
+        let mut added_chunks = Vec::new();
+        let mut removed_chunks = Vec::new();
+        if text_compared {
+            let longest = from.chunk_hashes.len().max(to.chunk_hashes.len());
+            for index in 0..longest {
+                match (from.chunk_hashes.get(index), to.chunk_hashes.get(index)) {
+                    (Some(a), Some(b)) if a != b => changed_chunks.push(index as u64),
+                    (None, Some(_)) => added_chunks.push(index as u64),
+                    (Some(_), None) => removed_chunks.push(index as u64),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(VersionComparison {
+            document_id,
+            from_version,
+            to_version,
+            from_hash: from.hash.clone(),
+            to_hash: to.hash.clone(),
+            hash_changed: from.hash != to.hash,
+            size_delta: to.size as i64 - from.size as i64,
+            page_count_delta: match (from.page_count, to.page_count) {
+                (Some(a), Some(b)) => Some(b as i64 - a as i64),
+                _ => None,
+            },
+            text_compared,
+            changed_chunks,
+            added_chunks,
+            removed_chunks,
+        })
+    }
+
+    /// Users who must sign before a document counts as fully signed. Falls back to
+    /// the signing order, then to every participant allowed to sign who was not
+    /// deactivated when the document was uploaded.
+    fn required_signers_for(&self, document: &DocumentInfo) -> Vec<UserId> {
+        if let Some(required) = &document.required_signers {
+            return required.clone();
+        }
+        if let Some(order) = &document.signing_order {
+            return order.signers.clone();
+        }
+        let mut participants = Vec::new();
+        if let Ok(iter) = self.participants.iter() {
+            for participant in iter {
+                let can_sign = match self.document_permission(document, &participant) {
+                    Some(PermissionLevel::Read) => false,
+                    Some(_) => true,
+                    // Restricted documents exclude anyone not on the access list
+                    None => document.access_list.is_empty(),
+                } && self.has_capability(&participant, roles::SIGN)
+                    && !matches!(
+                        self.deactivations.get(&participant),
+                        Ok(Some(deactivation)) if deactivation.deactivated_at <= document.uploaded_at
+                    );
+                if can_sign {
+                    participants.push(participant);
+                }
+            }
+        }
+        participants
+    }
+
+    /// Move a document through the status state machine, keeping its completion time in step
+    fn transition_document(
+        &self,
+        document: &mut DocumentInfo,
+        trigger: StatusTrigger,
+    ) -> Result<(), MeroDocsError> {
+        let has_signatures = matches!(
+            self.document_signatures.get(&document.id),
+            Ok(Some(sigs)) if sigs.len().unwrap_or(0) > 0
+        );
+        let next = lifecycle::next_status(&document.status, trigger, has_signatures)?;
+        if next == document.status {
+            return Ok(());
+        }
+
+        if next == DocumentStatus::FullySigned {
+            if document.completed_at.is_none() {
+                document.completed_at = Some(TimestampNanos::now());
+            }
+        } else if document.status == DocumentStatus::FullySigned {
+            document.completed_at = None;
+        }
+
+        app::emit!(MeroSignEvent::DocumentStatusChanged {
+            document_id: document.id.clone(),
+            from: document.status.clone(),
+            to: next.clone(),
+        });
+
+        document.status = next;
+        Ok(())
+    }
+
+    /// Re-evaluate a document's status against its outstanding required signers
+    fn recalculate_document_status(
+        &self,
+        document: &mut DocumentInfo,
+    ) -> Result<(), MeroDocsError> {
+        let witnesses = self
+            .signatures_in_capacity(&document.id, SignatureCapacity::Witness)?
+            .len();
+        let complete = self.outstanding_signers(document)?.is_empty()
+            && witnesses >= document.required_witnesses as usize;
+        self.transition_document(document, StatusTrigger::Recalculated { complete })
+    }
+
+    /// Re-evaluate every document after the participant set changed
+    fn recalculate_all_document_statuses(&mut self) -> Result<(), MeroDocsError> {
+        let mut documents = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                documents.push(document);
+            }
+        }
+
+        for mut document in documents {
+            let previous = document.status.clone();
+            self.recalculate_document_status(&mut document)?;
+            if document.status != previous {
+                self.save_document(document)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Required signers of a document who have not signed yet
+    fn outstanding_signers(&self, document: &DocumentInfo) -> Result<Vec<UserId>, MeroDocsError> {
+        let signed = self.signed_users(&document.id)?;
+        Ok(self
+            .required_signers_for(document)
+            .into_iter()
+            .filter(|s| !signed.contains(s))
+            .collect())
+    }
+
+    /// Get the required signers who still have to sign a document
+    pub fn get_outstanding_signers(
+        &self,
+        document_id: String,
+    ) -> Result<Vec<UserId>, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        self.outstanding_signers(&document)
+    }
+
+    /// Get the next signer in the document's signing order who has not signed yet
+    pub fn get_next_required_signer(
+        &self,
+        document_id: String,
+    ) -> Result<Option<UserId>, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+
+        let Some(order) = document.signing_order else {
+            return Ok(None);
+        };
+
+        let signed = self.signed_users(&document_id)?;
+        Ok(order.signers.into_iter().find(|s| !signed.contains(s)))
+    }
+
+    /// Collect the users who have signed a document as a party
+    fn signed_users(&self, document_id: &str) -> Result<Vec<UserId>, MeroDocsError> {
+        self.signatures_in_capacity(document_id, SignatureCapacity::Party)
+    }
+
+    /// Collect the users who have signed a document in `capacity`
+    fn signatures_in_capacity(
+        &self,
+        document_id: &str,
+        capacity: SignatureCapacity,
+    ) -> Result<Vec<UserId>, MeroDocsError> {
+        let mut signed = Vec::new();
+        let signatures = self.document_signatures.get(document_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get document signatures: {:?}", e))
+        })?;
+        if let Some(sigs) = signatures {
+            if let Ok(iter) = sigs.iter() {
+                for sig in iter {
+                    if sig.capacity == capacity {
+                        signed.push(sig.signer);
+                    }
+                }
+            }
+        }
+        Ok(signed)
+    }
+
+    /// Update document status to fully signed
+    pub fn mark_participant_signed(
+        &mut self,
+        document_id: String,
+        user_id_str: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        let has_consent = self.check_consent(&user_id, &document_id)?;
+        if !has_consent {
+            return Err(MeroDocsError::consent_required(
+                "User must provide consent before being marked as signed",
+            ));
+        }
+
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
+        };
+        if document.status == DocumentStatus::Declined {
+            return Err(MeroDocsError::invalid_state(
+                "Document has been declined and must be reopened first",
+            ));
+        }
+
+        let signatures = self
+            .document_signatures
+            .get(&document_id)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to get document signatures: {:?}", e))
+            })?
+            .unwrap_or_else(Vector::new);
+
+        let mut already_signed = false;
+        if let Ok(iter) = signatures.iter() {
+            for sig in iter {
+                if sig.signer == user_id {
+                    already_signed = true;
+                    break;
+                }
+            }
+        }
+        if !already_signed {
+            return Err(MeroDocsError::invalid_state(
+                "User has not signed this document yet",
+            ));
+        }
+
+        self.recalculate_document_status(&mut document)?;
+        self.save_document(document)?;
+
+        Ok(())
+    }
+
+    /// Register self as participant (for users who joined via open invitation). Discoverable
+    /// contexts require `request_to_join` instead.
+    pub fn register_self_as_participant(&mut self) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Cannot register as participant in private context",
+            ));
+        }
+        if self.feature_enabled("discoverable") {
+            return Err(MeroDocsError::invalid_state(
+                "This context admits participants through join requests",
+            ));
+        }
+
+        let executor_id = current_caller();
+
+        // Check if already a participant
+        if self.participants.contains(&executor_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Already registered as participant",
+            ));
+        }
+
+        // Add as participant with Sign permission
+        self.participants.insert(executor_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to register as participant: {:?}", e))
+        })?;
+        self.record_change(
+            ChangeEntity::Participant,
+            &bs58::encode(&executor_id).into_string(),
+            ChangeKind::Created,
+        )?;
+
+        self.permissions
+            .insert(executor_id, PermissionLevel::Sign)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to set permissions: {:?}", e)))?;
+
+        // Update document statuses since new signer joined
+        self.recalculate_all_document_statuses()?;
+
+        self.record_audit(
+            AuditAction::ParticipantJoined,
+            &bs58::encode(&executor_id).into_string(),
+            "permission=Sign".to_string(),
+        )?;
+
+        app::emit!(MeroSignEvent::ParticipantJoined {
+            user_id: executor_id
+        });
+
+        Ok(())
+    }
+
+    /// Ask to become a participant of a discoverable context; admins approve or deny it
+    pub fn request_to_join(&mut self, message: Option<String>) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Cannot request to join a private context",
+            ));
+        }
+        self.require_feature("discoverable")?;
+
+        let user_id = current_caller();
+        if self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "Already registered as participant",
+            ));
+        }
+        if matches!(
+            self.join_requests.get(&user_id),
+            Ok(Some(request)) if request.status == JoinRequestStatus::Pending
+        ) {
+            return Err(MeroDocsError::already_exists(
+                "A join request is already pending",
+            ));
+        }
+
+        let message = message
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty());
+        if message
+            .as_ref()
+            .is_some_and(|m| m.chars().count() > MAX_JOIN_REQUEST_MESSAGE_CHARS)
+        {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Join request message cannot exceed {} characters",
+                MAX_JOIN_REQUEST_MESSAGE_CHARS
+            )));
+        }
+
+        let now = env::time_now();
+        let request = JoinRequest {
+            user_id,
+            message: message.clone(),
+            requested_at: TimestampNanos::from_nanos(now),
+            status: JoinRequestStatus::Pending,
+            decided_by: None,
+            updated_at: TimestampNanos::from_nanos(now),
+        };
+        self.join_requests.insert(user_id, request).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to store join request: {:?}", e))
+        })?;
+
+        self.record_audit(
+            AuditAction::JoinRequested,
+            &bs58::encode(&user_id).into_string(),
+            String::new(),
+        )?;
+
+        app::emit!(MeroSignEvent::JoinRequested { user_id, message });
+
+        Ok(())
+    }
+
+    /// List join requests, optionally only those with the given status (invite capability)
+    pub fn list_join_requests(
+        &self,
+        status: Option<JoinRequestStatus>,
+    ) -> Result<Vec<JoinRequest>, MeroDocsError> {
+        self.require_capability(roles::INVITE)?;
+
+        let mut requests: Vec<JoinRequest> = self
+            .join_requests
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list join requests: {:?}", e)))?
+            .map(|(_, request)| request)
+            .filter(|request| status.as_ref().is_none_or(|s| request.status == *s))
+            .collect();
+        requests.sort_by_key(|request| request.requested_at);
+        Ok(requests)
+    }
+
+    /// Approve a pending join request, adding the requester as a participant with the given
+    /// permission (Sign by default) (invite capability)
+    pub fn approve_join_request(
+        &mut self,
+        user_id_str: String,
+        permission: Option<PermissionLevel>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let mut request = self.pending_join_request(&user_id_str)?;
+        let permission = permission.unwrap_or(PermissionLevel::Sign);
+
+        self.add_participant(user_id_str, permission.clone())?;
+
+        request.status = JoinRequestStatus::Approved;
+        request.decided_by = Some(current_caller());
+        request.updated_at = TimestampNanos::now();
+        let user_id = request.user_id;
+        self.join_requests.insert(user_id, request).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to update join request: {:?}", e))
+        })?;
+
+        app::emit!(MeroSignEvent::JoinRequestApproved {
+            user_id,
+            permission,
+        });
+
+        Ok(())
+    }
+
+    /// Deny a pending join request (invite capability)
+    pub fn deny_join_request(&mut self, user_id_str: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let mut request = self.pending_join_request(&user_id_str)?;
+
+        request.status = JoinRequestStatus::Denied;
+        request.decided_by = Some(current_caller());
+        request.updated_at = TimestampNanos::now();
+        let user_id = request.user_id;
+        self.join_requests.insert(user_id, request).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to update join request: {:?}", e))
+        })?;
+
+        self.record_audit(AuditAction::JoinRequestDenied, &user_id_str, String::new())?;
+
+        app::emit!(MeroSignEvent::JoinRequestDenied { user_id });
+
+        Ok(())
+    }
+
+    /// Load a user's pending join request after checking the caller may decide on it
+    fn pending_join_request(&self, user_id_str: &str) -> Result<JoinRequest, MeroDocsError> {
+        self.require_capability(roles::INVITE)?;
+
+        let user_id = parse_public_key_base58(user_id_str)?;
+        match self.join_requests.get(&user_id) {
+            Ok(Some(request)) if request.status == JoinRequestStatus::Pending => Ok(request),
+            Ok(_) => Err(MeroDocsError::not_found(
+                "No pending join request for this user",
+            )),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get join request: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Add participant to shared context (admin only)
+    pub fn add_participant(
+        &mut self,
+        user_id_str: String,
+        permission: PermissionLevel,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::INVITE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::already_exists(
+                "User is already a participant",
+            ));
+        }
+
+        self.participants
+            .insert(user_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to add participant: {:?}", e)))?;
+        self.record_change(ChangeEntity::Participant, &user_id_str, ChangeKind::Created)?;
+
+        self.permissions
+            .insert(user_id, permission.clone())
+            .map_err(|e| MeroDocsError::storage(format!("Failed to set permissions: {:?}", e)))?;
+
+        self.recalculate_all_document_statuses()?;
+
+        // Queue whatever the new participant now has to sign
+        let mut document_ids = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (document_id, _) in entries {
+                document_ids.push(document_id);
+            }
+        }
+        for document_id in document_ids {
+            self.refresh_pending_action(&user_id, &document_id)?;
+        }
+
+        self.record_audit(
+            AuditAction::ParticipantAdded,
+            &user_id_str,
+            format!("permission={:?}", permission),
+        )?;
+
+        app::emit!(MeroSignEvent::ParticipantJoined { user_id });
+
+        Ok(())
+    }
+
+    /// Get the oversight role of the calling identity, if any
+    fn caller_oversight_role(&self) -> Option<OversightRole> {
+        self.oversight_roles.get(&current_caller()).ok().flatten()
+    }
+
+    /// Assign or clear an oversight role for a read-only participant (admin only)
+    pub fn set_oversight_role(
+        &mut self,
+        user_id_str: String,
+        role: Option<OversightRole>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+
+        match &role {
+            Some(role) => {
+                match self.permissions.get(&user_id) {
+                    Ok(Some(PermissionLevel::Read)) => {}
+                    Ok(Some(_)) => {
+                        return Err(MeroDocsError::invalid_input(
+                            "Oversight roles can only be assigned to Read participants",
+                        ))
+                    }
+                    Ok(None) => return Err(MeroDocsError::not_found("User permissions not found")),
+                    Err(e) => {
+                        return Err(MeroDocsError::storage(format!(
+                            "Failed to check user permissions: {:?}",
+                            e
+                        )))
+                    }
+                }
+                self.oversight_roles
+                    .insert(user_id, role.clone())
+                    .map_err(|e| {
+                        MeroDocsError::storage(format!("Failed to set oversight role: {:?}", e))
+                    })?;
+            }
+            None => {
+                self.oversight_roles.remove(&user_id).map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to clear oversight role: {:?}", e))
+                })?;
+            }
+        }
+
+        self.record_audit(
+            AuditAction::OversightRoleChanged,
+            &user_id_str,
+            format!("role={:?}", role),
+        )?;
+
+        app::emit!(MeroSignEvent::OversightRoleChanged { user_id, role });
+
+        Ok(())
+    }
+
+    /// Create or update a custom role with the given capability names (manage capability)
+    pub fn define_role(
+        &mut self,
+        name: String,
+        capabilities: Vec<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let name = roles::normalize_role_name(&name)?;
+        if roles::BUILT_IN_ROLES
+            .iter()
+            .any(|(built_in, _)| *built_in == name)
+        {
+            return Err(MeroDocsError::invalid_input(format!(
+                "'{}' is a built-in role",
+                name
+            )));
+        }
+        let capabilities = roles::parse_capabilities(&capabilities)?;
+        self.ensure_manager_remains(|participant| {
+            self.role_assignments
+                .get(participant)
+                .ok()
+                .flatten()
+                .filter(|assignment| assignment.role == name)
+                .map(|_| capabilities)
+        })?;
+
+        let role = Role {
+            name: name.clone(),
+            capabilities,
+            updated_by: current_caller(),
+            updated_at: TimestampNanos::now(),
+        };
+        self.roles
+            .insert(name.clone(), role)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to store role: {:?}", e)))?;
+
+        // Signing rights may have changed for participants holding this role
+        self.recalculate_all_document_statuses()?;
+
+        let capabilities = roles::capability_names(capabilities);
+        self.record_audit(
+            AuditAction::RoleDefined,
+            &name,
+            format!("capabilities={}", capabilities.join("|")),
+        )?;
+
+        app::emit!(MeroSignEvent::RoleDefined { name, capabilities });
+
+        Ok(())
+    }
+
+    /// Assign a custom role to a participant, or clear it with `None` so the defaults of
+    /// their permission level apply again (manage capability)
+    pub fn assign_role(
+        &mut self,
+        user_id_str: String,
+        role: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+
+        let role = match role {
+            Some(name) => {
+                let name = roles::normalize_role_name(&name)?;
+                let Some(defined) = self.roles.get(&name).ok().flatten() else {
+                    return Err(MeroDocsError::not_found(format!(
+                        "Role not found: {}",
+                        name
+                    )));
+                };
+                self.ensure_manager_remains(|participant| {
+                    (*participant == user_id).then_some(defined.capabilities)
+                })?;
+                let assignment = RoleAssignment {
+                    role: name.clone(),
+                    assigned_by: current_caller(),
+                    assigned_at: TimestampNanos::now(),
+                };
+                self.role_assignments
+                    .insert(user_id, assignment)
+                    .map_err(|e| {
+                        MeroDocsError::storage(format!("Failed to assign role: {:?}", e))
+                    })?;
+                Some(name)
+            }
+            None => {
+                let defaults = match self.permissions.get(&user_id) {
+                    Ok(Some(level)) => roles::default_capabilities(&level),
+                    _ => 0,
+                };
+                self.ensure_manager_remains(|participant| {
+                    (*participant == user_id).then_some(defaults)
+                })?;
+                self.role_assignments.remove(&user_id).map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to clear role: {:?}", e))
+                })?;
+                None
+            }
+        };
+
+        self.recalculate_all_document_statuses()?;
+
+        self.record_audit(
+            AuditAction::RoleAssigned,
+            &user_id_str,
+            format!("role={}", role.as_deref().unwrap_or("none")),
+        )?;
+
+        app::emit!(MeroSignEvent::RoleAssigned { user_id, role });
+
+        Ok(())
+    }
+
+    /// List the built-in and custom roles with their capabilities
+    pub fn list_roles(&self) -> Result<Vec<RoleInfo>, MeroDocsError> {
+        let mut roles: Vec<RoleInfo> = roles::BUILT_IN_ROLES
+            .iter()
+            .map(|(name, level)| RoleInfo {
+                name: name.to_string(),
+                capabilities: roles::capability_names(roles::default_capabilities(level)),
+                built_in: true,
+            })
+            .collect();
+
+        let mut custom: Vec<Role> = self
+            .roles
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list roles: {:?}", e)))?
+            .map(|(_, role)| role)
+            .collect();
+        custom.sort_by(|a, b| a.name.cmp(&b.name));
+        roles.extend(custom.into_iter().map(|role| RoleInfo {
+            name: role.name,
+            capabilities: roles::capability_names(role.capabilities),
+            built_in: false,
+        }));
+
+        Ok(roles)
+    }
+
+    /// Get the capabilities a participant currently holds
+    pub fn get_user_capabilities(&self, user_id_str: String) -> Result<Vec<String>, MeroDocsError> {
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+        Ok(roles::capability_names(self.capabilities_of(&user_id)))
+    }
+
+    /// Link the caller's identity to an IC principal so canisters can attribute actions to it
+    pub fn link_icp_principal(
+        &mut self,
+        principal: String,
+        proof: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let executor_id = current_caller();
+        if !self.participants.contains(&executor_id).unwrap_or(false) {
+            return Err(MeroDocsError::unauthorized(
+                "Only participants can link a principal",
+            ));
+        }
+
+        validate_icp_principal(&principal)?;
+        verify_principal_proof(&executor_id, &principal, &proof)?;
+
+        if let Ok(entries) = self.principal_links.entries() {
+            for (user_id, link) in entries {
+                if link.principal == principal && user_id != executor_id {
+                    return Err(MeroDocsError::already_exists(
+                        "Principal is already linked to another participant",
+                    ));
+                }
+            }
+        }
+
+        let link = PrincipalLink {
+            user_id: executor_id,
+            principal: principal.clone(),
+            proof,
+            linked_at: TimestampNanos::now(),
+        };
+        self.principal_links
+            .insert(executor_id, link)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to link principal: {:?}", e)))?;
+
+        self.record_audit(
+            AuditAction::PrincipalLinked,
+            &bs58::encode(&executor_id).into_string(),
+            format!("principal={}", principal),
+        )?;
+
+        app::emit!(MeroSignEvent::PrincipalLinked {
+            user_id: executor_id,
+            principal,
+        });
+
+        Ok(())
+    }
+
+    /// Remove the caller's principal link
+    pub fn unlink_icp_principal(&mut self) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        let executor_id = current_caller();
+        let link = match self.principal_links.remove(&executor_id) {
+            Ok(Some(link)) => link,
+            Ok(None) => return Err(MeroDocsError::not_found("No principal linked")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to unlink principal: {:?}",
+                    e
+                )))
+            }
+        };
+
+        self.record_audit(
+            AuditAction::PrincipalUnlinked,
+            &bs58::encode(&executor_id).into_string(),
+            format!("principal={}", link.principal),
+        )?;
+
+        app::emit!(MeroSignEvent::PrincipalUnlinked {
+            user_id: executor_id
+        });
+
+        Ok(())
+    }
+
+    /// Get the principal linked to a participant
+    pub fn get_linked_principal(
+        &self,
+        user_id_str: String,
+    ) -> Result<Option<PrincipalLink>, MeroDocsError> {
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        self.principal_links
+            .get(&user_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get principal link: {:?}", e)))
+    }
+
+    /// Find the participant a principal is linked to
+    pub fn find_user_by_principal(
+        &self,
+        principal: String,
+    ) -> Result<Option<UserId>, MeroDocsError> {
+        if let Ok(entries) = self.principal_links.entries() {
+            for (user_id, link) in entries {
+                if link.principal == principal {
+                    return Ok(Some(user_id));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Remove participant from shared context
+    pub fn remove_participant(&mut self, user_id_str: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::INVITE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+        if user_id == *self.owner.get() {
+            return Err(MeroDocsError::invalid_state(
+                "Transfer ownership before removing the owner",
+            ));
+        }
+        if self.is_last_admin(&user_id) {
+            return Err(MeroDocsError::invalid_state("Cannot remove the last admin"));
+        }
+        self.ensure_manager_remains(|participant| (*participant == user_id).then_some(0))?;
+
+        self.participants.remove(&user_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to remove participant: {:?}", e))
+        })?;
+        self.record_change(ChangeEntity::Participant, &user_id_str, ChangeKind::Deleted)?;
+
+        self.permissions.remove(&user_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to remove permissions: {:?}", e))
+        })?;
+
+        let _ = self.oversight_roles.remove(&user_id);
+        let _ = self.role_assignments.remove(&user_id);
+        let _ = self.deactivations.remove(&user_id);
+        let _ = self
+            .pending_actions
+            .remove(&bs58::encode(&user_id).into_string());
+        self.remove_from_all_groups(&user_id)?;
+
+        self.recalculate_all_document_statuses()?;
+
+        self.record_audit(AuditAction::ParticipantRemoved, &user_id_str, String::new())?;
+
+        // The removed participant still holds the current content key
+        self.key_rotation_due.set(true);
+
+        app::emit!(MeroSignEvent::ParticipantLeft { user_id });
+        app::emit!(MeroSignEvent::KeyRotationDue {
+            removed_user: user_id,
+        });
+
+        Ok(())
+    }
+
+    /// Put a participant on hold (invite capability). They keep their signatures,
+    /// permissions and role, but cannot act and are left out of the default signers
+    /// of documents uploaded while they are inactive.
+    pub fn deactivate_participant(
+        &mut self,
+        user_id_str: String,
+        reason: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::INVITE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+        if self.is_deactivated(&user_id) {
+            return Err(MeroDocsError::invalid_state(
+                "Participant is already deactivated",
+            ));
+        }
+        if user_id == *self.owner.get() {
+            return Err(MeroDocsError::invalid_state(
+                "Transfer ownership before deactivating the owner",
+            ));
+        }
+        if self.is_last_admin(&user_id) {
+            return Err(MeroDocsError::invalid_state(
+                "Cannot deactivate the last admin",
+            ));
+        }
+        self.ensure_manager_remains(|participant| (*participant == user_id).then_some(0))?;
+
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        let deactivated_by = current_caller();
+        self.deactivations
+            .insert(
+                user_id,
+                ParticipantDeactivation {
+                    deactivated_by,
+                    deactivated_at: TimestampNanos::now(),
+                    reason: reason.clone(),
+                },
+            )
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to deactivate participant: {:?}", e))
+            })?;
+        self.record_change(ChangeEntity::Participant, &user_id_str, ChangeKind::Updated)?;
+
+        self.record_audit(
+            AuditAction::ParticipantDeactivated,
+            &user_id_str,
+            reason.map(|r| format!("reason={}", r)).unwrap_or_default(),
+        )?;
+
+        app::emit!(MeroSignEvent::ParticipantDeactivated {
+            user_id,
+            deactivated_by,
+        });
+
+        Ok(())
+    }
+
+    /// Let a deactivated participant act again (invite capability)
+    pub fn reactivate_participant(&mut self, user_id_str: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::INVITE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+
+        if !self.is_deactivated(&user_id) {
+            return Err(MeroDocsError::invalid_state(
+                "Participant is not deactivated",
+            ));
+        }
+
+        self.deactivations.remove(&user_id).map_err(|e| {
+            MeroDocsError::storage(format!("Failed to reactivate participant: {:?}", e))
+        })?;
+        self.record_change(ChangeEntity::Participant, &user_id_str, ChangeKind::Updated)?;
+
+        // Documents uploaded while they were inactive now count them as a default signer
+        self.recalculate_all_document_statuses()?;
+
+        self.record_audit(
+            AuditAction::ParticipantReactivated,
+            &user_id_str,
+            String::new(),
+        )?;
+
+        let reactivated_by = current_caller();
+        app::emit!(MeroSignEvent::ParticipantReactivated {
+            user_id,
+            reactivated_by,
+        });
+
+        Ok(())
+    }
+
+    /// Change a participant's permission level (manage capability). The owner and the
+    /// last admin cannot be demoted.
+    pub fn set_participant_permission(
+        &mut self,
+        user_id_str: String,
+        permission: PermissionLevel,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        if !self.participants.contains(&user_id).unwrap_or(false) {
+            return Err(MeroDocsError::not_found("User is not a participant"));
+        }
+
+        if permission != PermissionLevel::Admin {
+            if user_id == *self.owner.get() {
+                return Err(MeroDocsError::invalid_state(
+                    "Transfer ownership before demoting the owner",
                 ));
             }
-            let mut context_id = [0u8; 32];
-            context_id.copy_from_slice(&bytes);
-            Ok(context_id)
+            if self.is_last_admin(&user_id) {
+                return Err(MeroDocsError::invalid_state("Cannot demote the last admin"));
+            }
         }
-        Err(e) => Err(format!(
-            "Failed to decode context ID '{}': {}",
-            context_id_str, e
-        )),
-    }
-}
+        let capabilities = self
+            .assigned_role_capabilities(&user_id)
+            .unwrap_or_else(|| roles::default_capabilities(&permission));
+        self.ensure_manager_remains(|participant| {
+            (*participant == user_id).then_some(capabilities)
+        })?;
 
-/// Helper to encode context ID to base58 string
-fn encode_context_id_base58(context_id: &ContextId) -> String {
-    bs58::encode(context_id).into_string()
-}
+        self.permissions
+            .insert(user_id, permission.clone())
+            .map_err(|e| MeroDocsError::storage(format!("Failed to set permissions: {:?}", e)))?;
 
-#[app::logic]
-impl MeroSignState {
-    #[app::init]
-    pub fn init(is_private: bool, context_name: String) -> MeroSignState {
-        let owner_raw = env::executor_id();
+        // Oversight roles only apply to Read participants
+        if permission != PermissionLevel::Read {
+            let _ = self.oversight_roles.remove(&user_id);
+        }
 
-        let mut state = MeroSignState {
-            is_private: is_private.into(),
-            owner: owner_raw.into(),
-            context_name: context_name.into(),
+        self.recalculate_all_document_statuses()?;
 
-            signatures: UnorderedMap::new(),
-            joined_contexts: UnorderedMap::new(),
-            identity_mappings: UnorderedMap::new(),
-            signature_count: 0u64.into(),
-            participants: UnorderedSet::new(),
-            documents: UnorderedMap::new(),
-            document_signatures: UnorderedMap::new(),
-            permissions: UnorderedMap::new(),
-            consents: UnorderedMap::new(),
-        };
+        self.record_audit(
+            AuditAction::PermissionChanged,
+            &user_id_str,
+            format!("permission={:?}", permission),
+        )?;
 
-        // For shared contexts, add the creator as a participant with admin permissions
-        if !is_private {
-            let _ = state.participants.insert(owner_raw);
-            let _ = state.permissions.insert(owner_raw, PermissionLevel::Admin);
+        app::emit!(MeroSignEvent::ParticipantPermissionChanged {
+            user_id,
+            permission,
+        });
+
+        Ok(())
+    }
+
+    /// Hand ownership of the context to another participant, who becomes an admin.
+    /// Only the current owner can transfer; they stay an admin afterwards.
+    pub fn transfer_ownership(&mut self, new_owner_str: String) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "This method can only be called from shared context",
+            ));
         }
 
-        state
+        let previous_owner = *self.owner.get();
+        if current_caller() != previous_owner {
+            return Err(MeroDocsError::unauthorized(
+                "Only the owner can transfer ownership",
+            ));
+        }
+
+        let new_owner = parse_public_key_base58(&new_owner_str)?;
+        if new_owner == previous_owner {
+            return Err(MeroDocsError::already_exists(
+                "User already owns this context",
+            ));
+        }
+        if !self.participants.contains(&new_owner).unwrap_or(false) {
+            return Err(MeroDocsError::invalid_input(
+                "New owner must be a participant",
+            ));
+        }
+
+        self.permissions
+            .insert(new_owner, PermissionLevel::Admin)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to set permissions: {:?}", e)))?;
+        let _ = self.oversight_roles.remove(&new_owner);
+        self.owner.set(new_owner);
+
+        self.recalculate_all_document_statuses()?;
+
+        self.record_audit(
+            AuditAction::OwnershipTransferred,
+            &new_owner_str,
+            format!(
+                "previous_owner={}",
+                bs58::encode(&previous_owner).into_string()
+            ),
+        )?;
+
+        app::emit!(MeroSignEvent::OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        });
+
+        Ok(())
     }
 
-    pub fn is_default_private_context(&self) -> bool {
-        *self.is_private.get() && self.context_name.get() == "default"
+    /// Freeze the context. Until it is unarchived, every mutating method fails with a
+    /// `ContextArchived` error; documents, signatures and the audit log stay readable.
+    pub fn archive_context(&mut self) -> Result<(), MeroDocsError> {
+        self.require_capability(roles::MANAGE)?;
+        if self.archive.get().is_some() {
+            return Err(MeroDocsError::invalid_state("Context is already archived"));
+        }
+
+        let archived_by = current_caller();
+        self.archive.set(Some(ContextArchive {
+            archived_by,
+            archived_at: TimestampNanos::now(),
+        }));
+
+        self.record_audit(AuditAction::ContextArchived, "context", String::new())?;
+
+        app::emit!(MeroSignEvent::ContextArchived { archived_by });
+
+        Ok(())
     }
 
-    /// Create a new signature and store its blob ID
-    pub fn create_signature(
-        &mut self,
-        name: String,
-        blob_id_str: String,
-        data_size: u64,
-    ) -> Result<u64, String> {
-        if !*self.is_private.get() {
-            return Err("Signatures can only be created in private context".to_string());
+    /// Make an archived context writable again
+    pub fn unarchive_context(&mut self) -> Result<(), MeroDocsError> {
+        self.require_capability(roles::MANAGE)?;
+        if self.archive.get().is_none() {
+            return Err(MeroDocsError::invalid_state("Context is not archived"));
         }
 
-        let signature_id = *self.signature_count.get();
-        self.signature_count.set(signature_id + 1);
+        let unarchived_by = current_caller();
+        self.archive.set(None);
 
-        let blob_id = parse_blob_id_base58(&blob_id_str)?;
+        self.record_audit(AuditAction::ContextUnarchived, "context", String::new())?;
 
-        // Announce the signature blob to the network for discovery
-        let current_context = env::context_id();
-        if env::blob_announce_to_context(&blob_id, &current_context) {
-            app::log!(
-                "Successfully announced signature blob {} to network",
-                blob_id_str
-            );
-        } else {
-            app::log!(
-                "Failed to announce signature blob {} to network",
-                blob_id_str
-            );
+        app::emit!(MeroSignEvent::ContextUnarchived { unarchived_by });
+
+        Ok(())
+    }
+
+    /// Who archived the context and when, if it is archived
+    pub fn get_context_archive(&self) -> Result<Option<ContextArchive>, MeroDocsError> {
+        Ok(self.archive.get().clone())
+    }
+
+    /// Replace the context's description, avatar, deal reference, jurisdiction and tags
+    /// (manage capability). Omitted fields are cleared.
+    pub fn update_context_profile(
+        &mut self,
+        description: Option<String>,
+        avatar_blob_id_str: Option<String>,
+        external_reference: Option<String>,
+        jurisdiction: Option<String>,
+        tags: Option<Vec<String>>,
+    ) -> Result<ContextProfile, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let description =
+            normalize_profile_field(description, "Description", MAX_CONTEXT_DESCRIPTION_CHARS)?;
+        let external_reference = normalize_profile_field(
+            external_reference,
+            "External reference",
+            MAX_CONTEXT_PROFILE_FIELD_CHARS,
+        )?;
+        let jurisdiction = normalize_profile_field(
+            jurisdiction,
+            "Jurisdiction",
+            MAX_CONTEXT_PROFILE_FIELD_CHARS,
+        )?;
+
+        let mut normalized_tags = Vec::new();
+        for tag in tags.unwrap_or_default() {
+            let tag = normalize_tag(&tag)?;
+            if !normalized_tags.contains(&tag) {
+                normalized_tags.push(tag);
+            }
         }
 
-        let signature = SignatureRecord {
-            id: signature_id,
-            name: name.clone(),
-            blob_id,
-            size: data_size,
-            created_at: env::time_now(),
+        let avatar_blob_id = match avatar_blob_id_str {
+            Some(blob_id_str) => {
+                let blob_id = parse_blob_id_base58(&blob_id_str)?;
+                self.announce_blobs(&[blob_id])?;
+                Some(blob_id)
+            }
+            None => None,
         };
 
-        self.signatures
-            .insert(signature_id.to_string(), signature)
-            .map_err(|e| format!("Failed to store signature: {:?}", e))?;
+        let updated_by = current_caller();
+        let profile = ContextProfile {
+            description,
+            avatar_blob_id,
+            external_reference,
+            jurisdiction,
+            tags: normalized_tags,
+            updated_by: Some(updated_by),
+            updated_at: TimestampNanos::now(),
+        };
+        self.profile.set(profile.clone());
 
-        app::emit!(MeroSignEvent::SignatureCreated {
-            id: signature_id,
-            name,
-            size: data_size,
-        });
+        self.record_audit(
+            AuditAction::ContextProfileUpdated,
+            "context",
+            format!("tags={}", profile.tags.join(",")),
+        )?;
 
-        Ok(signature_id)
+        app::emit!(MeroSignEvent::ContextProfileUpdated { updated_by });
+
+        Ok(profile)
     }
 
-    /// Delete a signature by ID
-    pub fn delete_signature(&mut self, signature_id: u64) -> Result<(), String> {
-        if !*self.is_private.get() {
-            return Err("Signatures can only be deleted in private context".to_string());
+    /// List the participants with admin permissions
+    pub fn list_admins(&self) -> Result<Vec<UserId>, MeroDocsError> {
+        let mut admins = Vec::new();
+        if let Ok(iter) = self.participants.iter() {
+            for participant in iter {
+                if matches!(
+                    self.permissions.get(&participant),
+                    Ok(Some(PermissionLevel::Admin))
+                ) {
+                    admins.push(participant);
+                }
+            }
         }
+        Ok(admins)
+    }
 
-        let key = signature_id.to_string();
+    /// Whether the user is the only admin left in the context
+    fn is_last_admin(&self, user_id: &UserId) -> bool {
+        let admins = self.list_admins().unwrap_or_default();
+        admins.len() == 1 && admins[0] == *user_id
+    }
 
-        match self.signatures.remove(&key) {
-            Ok(Some(_)) => {
-                app::emit!(MeroSignEvent::SignatureDeleted { id: signature_id });
-                Ok(())
+    /// List all participants
+    pub fn list_participants(&self) -> Result<Vec<UserId>, MeroDocsError> {
+        let mut participants = Vec::new();
+        if let Ok(iter) = self.participants.iter() {
+            for participant in iter {
+                participants.push(participant.clone());
             }
-            Ok(None) => Err(format!("Signature not found: {}", signature_id)),
-            Err(e) => Err(format!("Failed to delete signature: {:?}", e)),
         }
+        Ok(participants)
     }
 
-    /// Get all signatures
-    pub fn list_signatures(&self) -> Result<Vec<SignatureRecord>, String> {
-        if !*self.is_private.get() {
-            return Err("Signatures can only be accessed in private context".to_string());
+    /// Get user permission level
+    pub fn get_user_permission(
+        &self,
+        user_id_str: String,
+    ) -> Result<PermissionLevel, MeroDocsError> {
+        let user_id = parse_public_key_base58(&user_id_str)?;
+        match self.permissions.get(&user_id) {
+            Ok(Some(perm)) => Ok(perm.clone()),
+            Ok(None) => Err(MeroDocsError::not_found("User not found")),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get permission: {:?}",
+                e
+            ))),
         }
+    }
 
-        let mut signatures = Vec::new();
-        if let Ok(entries) = self.signatures.entries() {
-            for (_, signature) in entries {
-                signatures.push(signature.clone());
-            }
-        }
-        Ok(signatures)
+    /// Get current context ID
+    pub fn get_context_id(&self) -> ContextId {
+        env::context_id()
     }
 
-    /// Join a shared context with identity mapping
-    pub fn join_shared_context(
-        &mut self,
+    /// Get identity mapping for a specific context
+    pub fn get_identity_mapping(
+        &self,
         context_id_str: String,
-        shared_identity_str: String,
-        context_name: String,
-    ) -> Result<(), String> {
+    ) -> Result<IdentityMapping, MeroDocsError> {
         if !*self.is_private.get() {
-            return Err("Context joining can only be managed in private context".to_string());
+            return Err(MeroDocsError::wrong_context(
+                "Identity mappings can only be accessed in private context",
+            ));
         }
 
         let context_id = parse_context_id_base58(&context_id_str)?;
         let context_id_key = encode_context_id_base58(&context_id);
 
-        if self
-            .joined_contexts
-            .contains(&context_id_key)
-            .unwrap_or(false)
-        {
-            return Err("Already joined this context".to_string());
+        match self.identity_mappings.get(&context_id_key) {
+            Ok(Some(mapping)) => Ok(mapping.clone()),
+            Ok(None) => Err(MeroDocsError::not_found(
+                "Identity mapping not found for this context",
+            )),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get identity mapping: {:?}",
+                e
+            ))),
         }
+    }
 
-        let private_identity = *self.owner.get();
-        let shared_identity = parse_public_key_base58(&shared_identity_str)?;
-
-        let metadata = ContextMetadata {
-            context_id,
-            context_name: context_name.clone(),
-            role: ParticipantRole::Unknown,
-            joined_at: env::time_now(),
-            private_identity,
-            shared_identity,
-        };
-
-        let identity_mapping = IdentityMapping {
-            private_identity,
-            shared_identity,
-            context_id,
-            created_at: env::time_now(),
-        };
-
-        self.joined_contexts
-            .insert(context_id_key.clone(), metadata)
-            .map_err(|e| format!("Failed to join context: {:?}", e))?;
-
-        self.identity_mappings
-            .insert(context_id_key.clone(), identity_mapping)
-            .map_err(|e| format!("Failed to store identity mapping: {:?}", e))?;
+    /// Get shared identity for a specific context
+    pub fn get_shared_identity(&self, context_id_str: String) -> Result<UserId, MeroDocsError> {
+        if !*self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Identity resolution can only be done in private context",
+            ));
+        }
 
-        app::emit!(MeroSignEvent::ContextJoined {
-            context_id: context_id_str,
-            context_name
-        });
-        Ok(())
+        let mapping = self.get_identity_mapping(context_id_str)?;
+        Ok(mapping.shared_identity)
     }
 
-    /// Leave a shared context
-    pub fn leave_shared_context(&mut self, context_id_str: String) -> Result<(), String> {
-        if !*self.is_private.get() {
-            return Err("Context leaving can only be managed in private context".to_string());
+    /// Resolve private identity from shared identity
+    pub fn resolve_private_identity(
+        &self,
+        shared_identity_str: String,
+    ) -> Result<Option<UserId>, MeroDocsError> {
+        if *self.is_private.get() {
+            let shared_identity = parse_public_key_base58(&shared_identity_str)?;
+            if let Ok(entries) = self.identity_mappings.entries() {
+                for (_, mapping) in entries {
+                    if mapping.shared_identity == shared_identity {
+                        return Ok(Some(mapping.private_identity));
+                    }
+                }
+            }
+            Ok(None)
+        } else {
+            Err(MeroDocsError::wrong_context(
+                "Cannot resolve private identity from shared context",
+            ))
         }
+    }
 
-        let context_id = parse_context_id_base58(&context_id_str)?;
-        let context_id_key = encode_context_id_base58(&context_id);
+    // === KEY EPOCHS ===
+
+    /// Record that the context's content key was rotated client-side and start a new
+    /// epoch; returns the new epoch number (manage capability). Documents uploaded or
+    /// signed from now on belong to the new epoch.
+    pub fn rotate_context_key(&mut self, reason: Option<String>) -> Result<u64, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let rotated_by = current_caller();
+        let epoch = self.current_key_epoch() + 1;
+        let recipients = match self.participants.iter() {
+            Ok(iter) => iter.collect(),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to list participants: {:?}",
+                    e
+                )))
+            }
+        };
+        let reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+
+        self.key_epochs
+            .push(KeyEpoch {
+                epoch,
+                rotated_by,
+                rotated_at: TimestampNanos::now(),
+                reason: reason.clone(),
+                recipients,
+            })
+            .map_err(|e| MeroDocsError::storage(format!("Failed to record key epoch: {:?}", e)))?;
+        self.key_rotation_due.set(false);
+
+        self.record_audit(
+            AuditAction::ContextKeyRotated,
+            "context",
+            format!(
+                "epoch={} reason={}",
+                epoch,
+                reason.as_deref().unwrap_or("none")
+            ),
+        )?;
+
+        app::emit!(MeroSignEvent::ContextKeyRotated { epoch, rotated_by });
+
+        Ok(epoch)
+    }
 
-        match self.joined_contexts.remove(&context_id_key) {
-            Ok(Some(_)) => {
-                let _ = self.identity_mappings.remove(&context_id_key);
-                app::emit!(MeroSignEvent::ContextLeft {
-                    context_id: context_id_str
-                });
-                Ok(())
+    /// Current key epoch, whether a rotation is due, and which epochs' wrapped keys the
+    /// caller needs to read the documents they can see
+    pub fn get_current_epoch(&self) -> Result<KeyEpochStatus, MeroDocsError> {
+        let latest = self.latest_key_epoch()?;
+
+        let mut needed_epochs = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                if self.can_view_document(&document) && !needed_epochs.contains(&document.key_epoch)
+                {
+                    needed_epochs.push(document.key_epoch);
+                }
             }
-            Ok(None) => Err("Context not found".to_string()),
-            Err(e) => Err(format!("Failed to leave context: {:?}", e)),
         }
+        needed_epochs.sort_unstable();
+
+        Ok(KeyEpochStatus {
+            epoch: latest.as_ref().map_or(0, |e| e.epoch),
+            rotated_at: latest.map(|e| e.rotated_at),
+            rotation_due: *self.key_rotation_due.get(),
+            needed_epochs,
+        })
     }
 
-    /// List all joined contexts
-    pub fn list_joined_contexts(&self) -> Result<Vec<ContextMetadata>, String> {
-        if !*self.is_private.get() {
-            return Err("Joined contexts can only be accessed in private context".to_string());
+    /// All key rotations, oldest first
+    pub fn list_key_epochs(&self) -> Result<Vec<KeyEpoch>, MeroDocsError> {
+        let mut epochs = Vec::new();
+        if let Ok(iter) = self.key_epochs.iter() {
+            for epoch in iter {
+                epochs.push(epoch);
+            }
         }
+        Ok(epochs)
+    }
 
-        let mut contexts = Vec::new();
-        if let Ok(entries) = self.joined_contexts.entries() {
-            for (_, metadata) in entries {
-                contexts.push(metadata.clone());
+    /// IDs of the visible documents whose current blob belongs to a key epoch
+    pub fn list_epoch_documents(&self, epoch: u64) -> Result<Vec<String>, MeroDocsError> {
+        let mut document_ids = Vec::new();
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                if document.key_epoch == epoch && self.can_view_document(&document) {
+                    document_ids.push(id);
+                }
             }
         }
-        Ok(contexts)
+        document_ids.sort();
+        Ok(document_ids)
     }
 
-    // === SHARED CONTEXT METHODS ===
+    fn latest_key_epoch(&self) -> Result<Option<KeyEpoch>, MeroDocsError> {
+        let len = self
+            .key_epochs
+            .len()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get key epochs: {:?}", e)))?;
+        if len == 0 {
+            return Ok(None);
+        }
+        self.key_epochs
+            .get(len - 1)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get key epoch: {:?}", e)))
+    }
 
-    /// Get detailed information about the shared context
-    pub fn get_context_details(&self, context_id_str: String) -> Result<ContextDetails, String> {
-        let context_id = parse_context_id_base58(&context_id_str)?;
-        let mut participants_with_permissions = Vec::new();
+    fn current_key_epoch(&self) -> u64 {
+        self.latest_key_epoch()
+            .ok()
+            .flatten()
+            .map_or(0, |epoch| epoch.epoch)
+    }
 
-        if let Ok(iter) = self.participants.iter() {
-            for participant in iter {
-                let permission = self
-                    .permissions
-                    .get(&participant)
-                    .map_err(|e| format!("Failed to get permission for user: {:?}", e))?
-                    .unwrap_or(PermissionLevel::Read);
+    // === ORGANIZATIONS ===
 
-                participants_with_permissions.push(ParticipantInfo {
-                    user_id: participant.clone(),
-                    permission_level: permission,
-                });
+    /// Register an organization that participants sign for (manage capability)
+    pub fn create_organization(
+        &mut self,
+        name: String,
+        registration_number: Option<String>,
+        members: Vec<String>,
+    ) -> Result<String, MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Organization name cannot be empty",
+            ));
+        }
+        let registration_number = registration_number
+            .map(|number| number.trim().to_string())
+            .filter(|number| !number.is_empty());
+        if let Ok(entries) = self.organizations.entries() {
+            for (_, organization) in entries {
+                if organization.name.eq_ignore_ascii_case(&name)
+                    && organization.registration_number == registration_number
+                {
+                    return Err(MeroDocsError::already_exists(format!(
+                        "Organization '{}' already exists",
+                        name
+                    )));
+                }
             }
         }
 
-        let document_count =
-            self.documents
-                .len()
-                .map_err(|e| format!("Failed to get document count: {:?}", e))? as u64;
+        let mut organization_members = Vec::with_capacity(members.len());
+        for member_str in &members {
+            let member = self.parse_participant(member_str)?;
+            if !organization_members.contains(&member) {
+                organization_members.push(member);
+            }
+        }
 
-        let context_details = ContextDetails {
-            context_id,
-            context_name: self.context_name.get().clone(),
-            owner: *self.owner.get(),
-            is_private: *self.is_private.get(),
-            participant_count: participants_with_permissions.len() as u64,
-            participants: participants_with_permissions,
-            document_count,
-            created_at: env::time_now(),
+        let now = env::time_now();
+        let organization = Organization {
+            id: format!("org_{}_{}", now, name),
+            name,
+            registration_number,
+            members: organization_members,
+            created_by: current_caller(),
+            updated_at: TimestampNanos::from_nanos(now),
         };
+        let organization_id = organization.id.clone();
+        self.save_organization(organization)?;
 
-        Ok(context_details)
+        Ok(organization_id)
     }
 
-    fn validate_admin_permissions(&self) -> Result<(), String> {
-        if *self.is_private.get() {
-            return Err("This method can only be called from shared context".to_string());
+    /// Add a participant to an organization (manage capability)
+    pub fn add_organization_member(
+        &mut self,
+        organization_id: String,
+        user_id_str: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let member = self.parse_participant(&user_id_str)?;
+        let mut organization = self.get_organization(&organization_id)?;
+        if organization.members.contains(&member) {
+            return Err(MeroDocsError::already_exists(
+                "User is already a member of this organization",
+            ));
         }
+        organization.members.push(member);
+        organization.updated_at = TimestampNanos::now();
 
-        let current_user = *self.owner.get();
-        match self.permissions.get(&current_user) {
-            Ok(Some(PermissionLevel::Admin)) => Ok(()),
-            Ok(Some(_)) => Err("Admin permissions required for this operation".to_string()),
-            Ok(None) => Err("User permissions not found".to_string()),
-            Err(e) => Err(format!("Failed to check user permissions: {:?}", e)),
-        }
+        self.save_organization(organization)
     }
 
-    /// Upload a document
-    pub fn upload_document(
+    /// Remove a user from an organization (manage capability). Signatures they already
+    /// made for it keep naming the organization.
+    pub fn remove_organization_member(
         &mut self,
-        name: String,
-        hash: String,
-        pdf_blob_id_str: String,
-        file_size: u64,
-        embeddings: Option<Vec<f32>>,
-        extracted_text: Option<String>,
-        chunks: Option<Vec<DocumentChunk>>,
-    ) -> Result<String, String> {
-        let document_id = format!("doc_{}_{}", env::time_now(), name);
+        organization_id: String,
+        user_id_str: String,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        let member = parse_public_key_base58(&user_id_str)?;
+        let mut organization = self.get_organization(&organization_id)?;
+        let Some(position) = organization.members.iter().position(|m| *m == member) else {
+            return Err(MeroDocsError::not_found(
+                "User is not a member of this organization",
+            ));
+        };
+        organization.members.remove(position);
+        organization.updated_at = TimestampNanos::now();
 
-        if self.documents.contains(&document_id).unwrap_or(false) {
-            return Err("Document with this ID already exists".to_string());
-        }
+        self.save_organization(organization)
+    }
 
-        let pdf_blob_id = parse_blob_id_base58(&pdf_blob_id_str)?;
+    /// List all organizations, sorted by name
+    pub fn list_organizations(&self) -> Result<Vec<Organization>, MeroDocsError> {
+        let mut organizations: Vec<Organization> = self
+            .organizations
+            .entries()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to list organizations: {:?}", e)))?
+            .map(|(_, organization)| organization)
+            .collect();
+        organizations.sort_by_key(|organization| organization.name.to_lowercase());
+        Ok(organizations)
+    }
 
-        // Announce blob to the network for discovery
-        let current_context = env::context_id();
-        if env::blob_announce_to_context(&pdf_blob_id, &current_context) {
-            app::log!(
-                "Successfully announced PDF blob {} to network",
-                pdf_blob_id_str
-            );
-        } else {
-            app::log!("Failed to announce PDF blob {} to network", pdf_blob_id_str);
-        }
+    /// Set or clear the organization a document is sent on behalf of (manage capability)
+    pub fn set_document_organization(
+        &mut self,
+        document_id: String,
+        organization_id: Option<String>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
 
-        let uploaded_by = *self.owner.get();
-        let document = DocumentInfo {
-            id: document_id.clone(),
-            name: name.clone(),
-            hash,
-            uploaded_by,
-            uploaded_at: env::time_now(),
-            status: DocumentStatus::Pending,
-            pdf_blob_id,
-            size: file_size,
-            embeddings,
-            extracted_text,
-            chunks,
+        if let Some(organization_id) = &organization_id {
+            self.get_organization(organization_id)?;
+        }
+        let mut document = match self.documents.get(&document_id) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return Err(MeroDocsError::not_found("Document not found")),
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to get document: {:?}",
+                    e
+                )))
+            }
         };
+        document.organization_id = organization_id.clone();
 
-        self.documents
-            .insert(document_id.clone(), document)
-            .map_err(|e| format!("Failed to upload document: {:?}", e))?;
-
-        self.document_signatures
-            .insert(document_id.clone(), Vector::new())
-            .map_err(|e| format!("Failed to initialize document signatures: {:?}", e))?;
+        self.save_document(document)?;
 
-        app::emit!(MeroSignEvent::DocumentUploaded {
-            id: document_id.clone(),
-            name,
-            uploaded_by,
-        });
+        self.record_audit(
+            AuditAction::DocumentOrganizationChanged,
+            &document_id,
+            format!(
+                "organization={}",
+                organization_id.as_deref().unwrap_or("none")
+            ),
+        )?;
 
-        Ok(document_id)
+        Ok(())
     }
 
-    /// Delete a document by ID
-    pub fn delete_document(&mut self, document_id: String) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+    /// Organizations behind a document: the one it was sent for and the one each
+    /// signature was made for
+    pub fn get_document_parties(
+        &self,
+        document_id: String,
+    ) -> Result<DocumentParties, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        let sender = match &document.organization_id {
+            Some(organization_id) => self.organizations.get(organization_id).ok().flatten(),
+            None => None,
+        };
 
-        match self.documents.remove(&document_id) {
-            Ok(Some(_)) => {
-                let _ = self.document_signatures.remove(&document_id);
+        let mut signers = Vec::new();
+        if let Ok(Some(signatures)) = self.document_signatures.get(&document_id) {
+            if let Ok(iter) = signatures.iter() {
+                for signature in iter {
+                    let organization = match &signature.organization_id {
+                        Some(organization_id) => {
+                            self.organizations.get(organization_id).ok().flatten()
+                        }
+                        None => None,
+                    };
+                    signers.push(SigningParty {
+                        signer: signature.signer,
+                        signed_by: signature.signed_by,
+                        signed_at: signature.signed_at,
+                        organization,
+                    });
+                }
+            }
+        }
 
-                app::emit!(MeroSignEvent::DocumentDeleted { id: document_id });
+        Ok(DocumentParties {
+            document_id,
+            sender,
+            signers,
+        })
+    }
 
-                Ok(())
-            }
-            Ok(None) => Err(format!("Document not found: {}", document_id)),
-            Err(e) => Err(format!("Failed to delete document: {:?}", e)),
+    fn get_organization(&self, organization_id: &str) -> Result<Organization, MeroDocsError> {
+        match self.organizations.get(organization_id) {
+            Ok(Some(organization)) => Ok(organization),
+            Ok(None) => Err(MeroDocsError::not_found(format!(
+                "Organization not found: {}",
+                organization_id
+            ))),
+            Err(e) => Err(MeroDocsError::storage(format!(
+                "Failed to get organization: {:?}",
+                e
+            ))),
         }
     }
 
-    /// List all documents
-    pub fn list_documents(&self) -> Result<Vec<DocumentInfo>, String> {
+    /// Store an organization, then record and announce the change
+    fn save_organization(&mut self, organization: Organization) -> Result<(), MeroDocsError> {
+        let (id, name, members) = (
+            organization.id.clone(),
+            organization.name.clone(),
+            organization.members.clone(),
+        );
+        self.organizations
+            .insert(id.clone(), organization)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to store organization: {:?}", e))
+            })?;
+
+        self.record_audit(
+            AuditAction::OrganizationChanged,
+            &id,
+            format!("name={} members={}", name, members.len()),
+        )?;
+
+        app::emit!(MeroSignEvent::OrganizationChanged { id, name, members });
+
+        Ok(())
+    }
+
+    // === METRICS ===
+
+    /// Get time-to-first-signature and time-to-completion for a document
+    pub fn get_document_signing_metrics(
+        &self,
+        document_id: String,
+    ) -> Result<DocumentSigningMetrics, MeroDocsError> {
+        let document = self.get_visible_document(&document_id)?;
+        Ok(document_signing_metrics(&document))
+    }
+
+    /// Get signing turnaround aggregates for the shared context
+    pub fn get_signing_metrics(&self) -> Result<SigningMetrics, MeroDocsError> {
         let mut documents = Vec::new();
         if let Ok(entries) = self.documents.entries() {
             for (_, document) in entries {
-                documents.push(document.clone());
+                documents.push(document_signing_metrics(&document));
             }
         }
-        Ok(documents)
-    }
 
-    /// Set consent for a user on a document
-    pub fn set_consent(&mut self, user_id_str: String, document_id: String) -> Result<(), String> {
-        let user_id = parse_public_key_base58(&user_id_str)?;
-        let key = format!("{}|{}", bs58::encode(&user_id).into_string(), document_id);
-        self.consents
-            .insert(key, true.into())
-            .map_err(|e| format!("Failed to store consent: {:?}", e))?;
-        Ok(())
+        let first_signature_times: Vec<u64> = documents
+            .iter()
+            .filter_map(|m| m.time_to_first_signature)
+            .collect();
+        let completion_times: Vec<u64> = documents
+            .iter()
+            .filter_map(|m| m.time_to_completion)
+            .collect();
+
+        Ok(SigningMetrics {
+            total_documents: documents.len() as u64,
+            signed_documents: first_signature_times.len() as u64,
+            completed_documents: completion_times.len() as u64,
+            average_time_to_first_signature: average(&first_signature_times),
+            average_time_to_completion: average(&completion_times),
+            max_time_to_completion: completion_times.iter().copied().max(),
+            documents,
+        })
     }
 
-    /// Check if user has given consent for a document (internal helper)
-    fn check_consent(&self, user_id: &UserId, document_id: &str) -> Result<bool, String> {
-        let key = format!("{}|{}", bs58::encode(user_id).into_string(), document_id);
-        match self.consents.get(&key) {
-            Ok(Some(consented)) => Ok(*consented.get()),
-            Ok(None) => Ok(false),
-            Err(e) => Err(format!("Failed to check consent: {:?}", e)),
+    /// Documents sent, documents completed and signatures applied per month (manage
+    /// capability). `period` narrows the report to a year (`YYYY`) or a month (`YYYY-MM`).
+    /// Uploads and signatures are counted from the audit log, so deleted documents still
+    /// count; completions are counted from documents that are currently fully signed.
+    pub fn get_usage_report(&self, period: Option<String>) -> Result<UsageReport, MeroDocsError> {
+        self.require_capability(roles::MANAGE)?;
+
+        let period = period
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+        if let Some(period) = &period {
+            validate_usage_period(period)?;
+        }
+        let in_period = |month: &str| period.as_ref().is_none_or(|p| month.starts_with(p));
+
+        let mut months: BTreeMap<String, MonthlyUsage> = BTreeMap::new();
+        if let Ok(iter) = self.audit_log.iter() {
+            for entry in iter {
+                if !matches!(
+                    entry.action,
+                    AuditAction::DocumentUploaded | AuditAction::DocumentSigned
+                ) {
+                    continue;
+                }
+                let month = entry.timestamp.year_month();
+                if !in_period(&month) {
+                    continue;
+                }
+                let usage = months
+                    .entry(month.clone())
+                    .or_insert_with(|| MonthlyUsage::empty(month));
+                if entry.action == AuditAction::DocumentUploaded {
+                    usage.documents_sent += 1;
+                } else {
+                    usage.signatures_applied += 1;
+                }
+            }
+        }
+        if let Ok(entries) = self.documents.entries() {
+            for (_, document) in entries {
+                let Some(completed_at) = document.completed_at else {
+                    continue;
+                };
+                let month = completed_at.year_month();
+                if in_period(&month) {
+                    months
+                        .entry(month.clone())
+                        .or_insert_with(|| MonthlyUsage::empty(month))
+                        .documents_completed += 1;
+                }
+            }
         }
-    }
 
-    /// Check if user has given consent for a document (public API)
-    pub fn has_consented(&self, user_id_str: String, document_id: String) -> Result<bool, String> {
-        let user_id = parse_public_key_base58(&user_id_str)?;
-        self.check_consent(&user_id, &document_id)
+        let months: Vec<MonthlyUsage> = months.into_values().collect();
+        Ok(UsageReport {
+            context_id: env::context_id(),
+            period,
+            documents_sent: months.iter().map(|m| m.documents_sent).sum(),
+            documents_completed: months.iter().map(|m| m.documents_completed).sum(),
+            signatures_applied: months.iter().map(|m| m.signatures_applied).sum(),
+            months,
+            generated_at: TimestampNanos::now(),
+        })
     }
 
-    pub fn sign_document(
-        &mut self,
-        document_id: String,
-        pdf_blob_id_str: String,
-        file_size: u64,
-        new_hash: String,
-        signer_id_str: String,
-    ) -> Result<(), String> {
-        let signer_id = parse_public_key_base58(&signer_id_str)?;
-        let has_consent = self.check_consent(&signer_id, &document_id)?;
-        if !has_consent {
-            return Err("User must provide consent before signing this document".to_string());
+    // === RESOURCE USAGE ===
+
+    /// Estimate the serialized size of each state collection against its warning threshold
+    /// (manage capability in shared contexts)
+    pub fn get_resource_report(&self) -> Result<ResourceReport, MeroDocsError> {
+        if !*self.is_private.get() {
+            self.require_capability(roles::MANAGE)?;
         }
 
-        let mut document = match self.documents.get(&document_id) {
-            Ok(Some(doc)) => doc,
-            Ok(None) => return Err("Document not found".to_string()),
-            Err(e) => return Err(format!("Failed to get document: {:?}", e)),
-        };
+        // (entries, bytes) per collection; keys are counted with their values
+        let mut documents = (0u64, 0u64);
+        if let Ok(entries) = self.documents.entries() {
+            for (id, document) in entries {
+                documents.0 += 1;
+                documents.1 += id.len() as u64 + encoded_len(&document);
+            }
+        }
 
-        let pdf_blob_id = parse_blob_id_base58(&pdf_blob_id_str)?;
+        let mut chunks = (0u64, 0u64);
+        if let Ok(entries) = self.document_chunks.entries() {
+            for (id, stored) in entries {
+                chunks.1 += id.len() as u64;
+                if let Ok(iter) = stored.iter() {
+                    for chunk in iter {
+                        chunks.0 += 1;
+                        chunks.1 += encoded_len(&chunk);
+                    }
+                }
+            }
+        }
 
-        // Announce the signed blob to the network for discovery
-        let current_context = env::context_id();
-        if env::blob_announce_to_context(&pdf_blob_id, &current_context) {
-            app::log!(
-                "Successfully announced signed PDF blob {} to network",
-                pdf_blob_id_str
-            );
-        } else {
-            app::log!(
-                "Failed to announce signed PDF blob {} to network",
-                pdf_blob_id_str
-            );
+        let mut document_signatures = (0u64, 0u64);
+        if let Ok(entries) = self.document_signatures.entries() {
+            for (id, stored) in entries {
+                document_signatures.1 += id.len() as u64;
+                if let Ok(iter) = stored.iter() {
+                    for signature in iter {
+                        document_signatures.0 += 1;
+                        document_signatures.1 += encoded_len(&signature);
+                    }
+                }
+            }
         }
 
-        document.pdf_blob_id = pdf_blob_id;
-        document.size = file_size;
-        document.hash = new_hash;
-        document.status = DocumentStatus::PartiallySigned;
+        let mut document_versions = (0u64, 0u64);
+        if let Ok(entries) = self.document_versions.entries() {
+            for (id, stored) in entries {
+                document_versions.1 += id.len() as u64;
+                if let Ok(iter) = stored.iter() {
+                    for version in iter {
+                        document_versions.0 += 1;
+                        document_versions.1 += encoded_len(&version);
+                    }
+                }
+            }
+        }
 
-        self.documents
-            .insert(document_id.clone(), document)
-            .map_err(|e| format!("Failed to update document: {:?}", e))?;
+        let mut document_access_log = (0u64, 0u64);
+        if let Ok(entries) = self.document_access_log.entries() {
+            for (id, stored) in entries {
+                document_access_log.1 += id.len() as u64;
+                if let Ok(iter) = stored.iter() {
+                    for event in iter {
+                        document_access_log.0 += 1;
+                        document_access_log.1 += encoded_len(&event);
+                    }
+                }
+            }
+        }
 
-        let signature = DocumentSignature {
-            signer: signer_id,
-            signed_at: env::time_now(),
-        };
+        let mut keyword_index = (0u64, 0u64);
+        if let Ok(entries) = self.keyword_index.entries() {
+            for (term, postings) in entries {
+                keyword_index.0 += 1;
+                keyword_index.1 += term.len() as u64;
+                if let Ok(postings) = postings.entries() {
+                    for (document_id, _) in postings {
+                        // Document ID plus a u32 term frequency
+                        keyword_index.1 += document_id.len() as u64 + 4;
+                    }
+                }
+            }
+        }
 
-        let mut signatures = self
-            .document_signatures
-            .get(&document_id)
-            .map_err(|e| format!("Failed to get document signatures: {:?}", e))?
-            .unwrap_or_else(Vector::new);
+        let mut audit_log = (0u64, 0u64);
+        if let Ok(iter) = self.audit_log.iter() {
+            for entry in iter {
+                audit_log.0 += 1;
+                audit_log.1 += encoded_len(&entry);
+            }
+        }
 
-        signatures
-            .push(signature)
-            .map_err(|e| format!("Failed to add signature: {:?}", e))?;
+        let mut signatures = (0u64, 0u64);
+        if let Ok(entries) = self.signatures.entries() {
+            for (id, signature) in entries {
+                signatures.0 += 1;
+                signatures.1 += id.len() as u64 + encoded_len(&signature);
+            }
+        }
 
-        self.document_signatures
-            .insert(document_id.clone(), signatures)
-            .map_err(|e| format!("Failed to update document signatures: {:?}", e))?;
+        let mut agreements = (0u64, 0u64);
+        if let Ok(entries) = self.joined_contexts.entries() {
+            for (id, metadata) in entries {
+                agreements.0 += 1;
+                agreements.1 += id.len() as u64 + encoded_len(&metadata);
+            }
+        }
 
-        app::emit!(MeroSignEvent::DocumentSigned {
-            document_id,
-            signer: signer_id,
-        });
+        let measured = [
+            ("documents", documents),
+            ("chunks", chunks),
+            ("document_signatures", document_signatures),
+            ("document_versions", document_versions),
+            ("document_access_log", document_access_log),
+            ("keyword_index", keyword_index),
+            ("audit_log", audit_log),
+            ("signatures", signatures),
+            ("agreements", agreements),
+        ];
+
+        let mut collections = Vec::with_capacity(measured.len());
+        let mut total_bytes = 0u64;
+        for (name, (entries, approx_bytes)) in measured {
+            let warning_bytes = RESOURCE_WARNING_THRESHOLDS
+                .iter()
+                .find(|(collection, _)| *collection == name)
+                .map_or(u64::MAX, |(_, bytes)| *bytes);
+            total_bytes += approx_bytes;
+            collections.push(CollectionUsage {
+                name: name.to_string(),
+                entries,
+                approx_bytes,
+                warning_bytes,
+                exceeded: approx_bytes >= warning_bytes,
+            });
+        }
 
-        Ok(())
+        Ok(ResourceReport {
+            collections,
+            total_bytes,
+            generated_at: TimestampNanos::now(),
+        })
     }
 
-    /// Get signatures for a document
-    pub fn get_document_signatures(
-        &self,
-        document_id: String,
-    ) -> Result<Vec<DocumentSignature>, String> {
-        let mut signatures = Vec::new();
-        if let Ok(Some(sigs)) = self.document_signatures.get(&document_id) {
-            if let Ok(iter) = sigs.iter() {
-                for sig in iter {
-                    signatures.push(sig.clone());
-                }
-            }
+    /// Build the resource report and emit an alert for every collection over its threshold
+    pub fn check_resource_thresholds(&mut self) -> Result<ResourceReport, MeroDocsError> {
+        let report = self.get_resource_report()?;
+        for collection in report.collections.iter().filter(|c| c.exceeded) {
+            app::emit!(MeroSignEvent::ResourceThresholdExceeded {
+                collection: collection.name.clone(),
+                approx_bytes: collection.approx_bytes,
+                warning_bytes: collection.warning_bytes,
+            });
         }
-        Ok(signatures)
+        Ok(report)
     }
 
-    /// Update document status to fully signed
-    pub fn mark_participant_signed(
-        &mut self,
-        document_id: String,
-        user_id_str: String,
-    ) -> Result<(), String> {
-        let user_id = parse_public_key_base58(&user_id_str)?;
-        let has_consent = self.check_consent(&user_id, &document_id)?;
-        if !has_consent {
-            return Err("User must provide consent before being marked as signed".to_string());
-        }
+    // === AUDIT LOG ===
 
-        let mut document = match self.documents.get(&document_id) {
-            Ok(Some(doc)) => doc,
-            Ok(None) => return Err("Document not found".to_string()),
-            Err(e) => return Err(format!("Failed to get document: {:?}", e)),
+    /// Append an entry to the shared context's audit log
+    fn record_audit(
+        &mut self,
+        action: AuditAction,
+        entity_id: &str,
+        details: String,
+    ) -> Result<(), MeroDocsError> {
+        let seq = self.audit_log.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get audit log length: {:?}", e))
+        })? as u64;
+
+        let entry = AuditEntry {
+            seq,
+            timestamp: TimestampNanos::now(),
+            actor: current_caller(),
+            action,
+            entity_id: entity_id.to_string(),
+            details,
         };
 
-        let signatures = self
-            .document_signatures
-            .get(&document_id)
-            .map_err(|e| format!("Failed to get document signatures: {:?}", e))?
-            .unwrap_or_else(Vector::new);
+        self.audit_log
+            .push(entry)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to record audit entry: {:?}", e)))
+    }
 
-        let mut already_signed = false;
-        if let Ok(iter) = signatures.iter() {
-            for sig in iter {
-                if sig.signer == user_id {
-                    already_signed = true;
-                    break;
-                }
+    /// Store an updated document and record the change for delta sync
+    fn save_document(&mut self, document: DocumentInfo) -> Result<(), MeroDocsError> {
+        let document_id = document.id.clone();
+        self.documents
+            .insert(document_id.clone(), document)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to update document: {:?}", e)))?;
+        self.record_change(ChangeEntity::Document, &document_id, ChangeKind::Updated)
+    }
+
+    /// Append an entry to the change log
+    fn record_change(
+        &mut self,
+        entity: ChangeEntity,
+        entity_id: &str,
+        kind: ChangeKind,
+    ) -> Result<(), MeroDocsError> {
+        let seq = self.change_log.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get change log length: {:?}", e))
+        })? as u64;
+        let author = current_caller();
+        let changed_at = env::time_now();
+
+        self.change_log
+            .push(ChangeEntry {
+                id: change_id(changed_at, &author, seq),
+                seq,
+                author,
+                entity,
+                entity_id: entity_id.to_string(),
+                kind,
+                changed_at: TimestampNanos::from_nanos(changed_at),
+            })
+            .map_err(|e| MeroDocsError::storage(format!("Failed to record change: {:?}", e)))
+    }
+
+    /// Get changes recorded after the change with id `since` (from the start when `None`),
+    /// oldest first. Pass the returned `next_cursor` as `since` to fetch the next batch.
+    /// Changes to documents the caller cannot see are left out.
+    pub fn get_change_log(
+        &self,
+        since: Option<String>,
+        limit: Option<u64>,
+    ) -> Result<ChangeLogPage, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Change log is only available in shared context",
+            ));
+        }
+
+        let mut entries = self
+            .change_log
+            .iter()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to read change log: {:?}", e)))?
+            .collect::<Vec<_>>();
+        let total = entries.len() as u64;
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+        if let Some(since) = since {
+            entries.retain(|entry| entry.id > since);
+        }
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).max(1);
+
+        let mut changes = Vec::new();
+        let mut remaining = entries.into_iter().peekable();
+        let mut last_scanned = None;
+        while (changes.len() as u64) < limit {
+            let Some(entry) = remaining.next() else {
+                break;
+            };
+            last_scanned = Some(entry.id.clone());
+
+            let hidden = matches!(
+                entry.entity,
+                ChangeEntity::Document | ChangeEntity::Signature
+            ) && matches!(
+                self.documents.get(&entry.entity_id),
+                Ok(Some(doc)) if !self.can_view_document(&doc)
+            );
+            if !hidden {
+                changes.push(entry);
             }
         }
-        if !already_signed {
-            return Err("User has not signed this document yet".to_string());
+
+        Ok(ChangeLogPage {
+            next_cursor: remaining.peek().and(last_scanned),
+            items: changes,
+            total,
+        })
+    }
+
+    /// Only auditors and holders of the view_audit capability may read the whole audit trail
+    fn can_read_audit_log(&self) -> bool {
+        self.has_capability(&current_caller(), roles::VIEW_AUDIT)
+            || self.caller_oversight_role() == Some(OversightRole::Auditor)
+    }
+
+    /// Get audit entries in sequence order, optionally only those for one document or of
+    /// one action. Pass the returned `next_cursor` as `cursor` to fetch the next page.
+    ///
+    /// Admins and auditors can read the whole log; other participants can read the
+    /// history of a document they can see by passing its `document_id`.
+    pub fn get_audit_log(
+        &self,
+        document_id: Option<String>,
+        action: Option<AuditAction>,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Page<AuditEntry>, MeroDocsError> {
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Audit log is only available in shared context",
+            ));
         }
 
-        let mut all_signed = true;
-        if let Ok(participants_iter) = self.participants.iter() {
-            for participant in participants_iter {
-                let mut signed = false;
-                if let Ok(sig_iter) = signatures.iter() {
-                    for sig in sig_iter {
-                        if sig.signer == participant {
-                            signed = true;
-                            break;
-                        }
-                    }
+        if !self.can_read_audit_log() {
+            match &document_id {
+                Some(id) => {
+                    self.get_visible_document(id)?;
                 }
-                if !signed {
-                    all_signed = false;
-                    break;
+                None => {
+                    return Err(MeroDocsError::unauthorized(
+                        "Audit log access requires the view_audit capability or auditor role",
+                    ))
                 }
             }
         }
 
-        if all_signed {
-            document.status = DocumentStatus::FullySigned;
-            self.documents
-                .insert(document_id, document)
-                .map_err(|e| format!("Failed to update document status: {:?}", e))?;
+        let total = self.audit_log.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get audit log length: {:?}", e))
+        })? as u64;
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).max(1);
+
+        let mut entries = Vec::new();
+        let mut index = cursor.unwrap_or(0);
+        while index < total && (entries.len() as u64) < limit {
+            let entry = match self.audit_log.get(index as usize) {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to read audit entry: {:?}",
+                        e
+                    )))
+                }
+            };
+            index += 1;
+
+            if document_id
+                .as_ref()
+                .is_some_and(|id| entry.entity_id != *id)
+                || action.as_ref().is_some_and(|a| entry.action != *a)
+            {
+                continue;
+            }
+            entries.push(entry);
         }
 
-        Ok(())
+        Ok(Page {
+            next_cursor: (index < total).then_some(index),
+            items: entries,
+            total,
+        })
     }
 
-    /// Register self as participant (for users who joined via open invitation)
-    pub fn register_self_as_participant(&mut self) -> Result<(), String> {
+    /// Export audit entries in `[since, until]` as CSV or JSONL, in sequence order.
+    ///
+    /// Each call returns at most `limit` entries starting at `cursor`; keep calling with
+    /// `next_cursor` until it is `None`.
+    pub fn export_audit_log(
+        &self,
+        format: AuditExportFormat,
+        since: Option<u64>,
+        until: Option<u64>,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<AuditExportChunk, MeroDocsError> {
         if *self.is_private.get() {
-            return Err("Cannot register as participant in private context".to_string());
+            return Err(MeroDocsError::wrong_context(
+                "Audit log can only be exported from shared context",
+            ));
         }
 
-        let executor_id = env::executor_id();
-
-        // Check if already a participant
-        if self.participants.contains(&executor_id).unwrap_or(false) {
-            return Err("Already registered as participant".to_string());
+        if !self.can_read_audit_log() {
+            return Err(MeroDocsError::unauthorized(
+                "Audit log access requires the view_audit capability or auditor role",
+            ));
         }
 
-        // Add as participant with Sign permission
-        self.participants
-            .insert(executor_id)
-            .map_err(|e| format!("Failed to register as participant: {:?}", e))?;
+        let start = cursor.unwrap_or(0) as usize;
+        let limit = limit.unwrap_or(DEFAULT_AUDIT_EXPORT_LIMIT).max(1) as usize;
+        let total = self.audit_log.len().map_err(|e| {
+            MeroDocsError::storage(format!("Failed to get audit log length: {:?}", e))
+        })?;
 
-        self.permissions
-            .insert(executor_id, PermissionLevel::Sign)
-            .map_err(|e| format!("Failed to set permissions: {:?}", e))?;
+        let mut payload = String::new();
+        if format == AuditExportFormat::Csv && start == 0 {
+            payload.push_str("seq,timestamp,actor,action,entity_id,details\n");
+        }
 
-        // Update document statuses since new signer joined
-        let mut docs_to_update = Vec::new();
-        if let Ok(entries) = self.documents.entries() {
-            for (_, document) in entries {
-                if document.status == DocumentStatus::FullySigned {
-                    let mut updated_document = document.clone();
-                    updated_document.status = DocumentStatus::PartiallySigned;
-                    docs_to_update.push(updated_document);
-                }
+        let mut entry_count = 0u64;
+        let mut next_cursor = None;
+        let mut index = start;
+        while index < total {
+            if entry_count as usize == limit {
+                next_cursor = Some(index as u64);
+                break;
             }
-        }
-        for document in docs_to_update {
-            let _ = self.documents.insert(document.id.clone(), document);
-        }
 
-        app::emit!(MeroSignEvent::ParticipantJoined {
-            user_id: executor_id
-        });
+            let entry = match self.audit_log.get(index) {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to read audit entry: {:?}",
+                        e
+                    )))
+                }
+            };
+            index += 1;
 
-        Ok(())
-    }
+            if since.is_some_and(|since| entry.timestamp < TimestampNanos::from_nanos(since))
+                || until.is_some_and(|until| entry.timestamp > TimestampNanos::from_nanos(until))
+            {
+                continue;
+            }
 
-    /// Add participant to shared context (admin only)
-    pub fn add_participant(
-        &mut self,
-        user_id_str: String,
-        permission: PermissionLevel,
-    ) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+            let actor = bs58::encode(&entry.actor).into_string();
+            match format {
+                AuditExportFormat::Csv => payload.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    entry.seq,
+                    entry.timestamp.as_nanos(),
+                    actor,
+                    entry.action.as_str(),
+                    csv_escape(&entry.entity_id),
+                    csv_escape(&entry.details)
+                )),
+                AuditExportFormat::Jsonl => payload.push_str(&format!(
+                    "{{\"seq\":{},\"timestamp\":{},\"actor\":\"{}\",\"action\":\"{}\",\"entity_id\":\"{}\",\"details\":\"{}\"}}\n",
+                    entry.seq,
+                    entry.timestamp.as_nanos(),
+                    actor,
+                    entry.action.as_str(),
+                    json_escape(&entry.entity_id),
+                    json_escape(&entry.details)
+                )),
+            }
+            entry_count += 1;
+        }
 
-        let user_id = parse_public_key_base58(&user_id_str)?;
+        Ok(AuditExportChunk {
+            format,
+            payload,
+            entry_count,
+            next_cursor,
+        })
+    }
 
-        if self.participants.contains(&user_id).unwrap_or(false) {
-            return Err("User is already a participant".to_string());
+    /// Search every visible document for exact keywords, ranked by TF-IDF, with highlighted
+    /// snippets of where the terms occur
+    pub fn search_documents_by_keyword(
+        &self,
+        query: String,
+        limit: Option<u64>,
+    ) -> Result<Vec<KeywordSearchHit>, MeroDocsError> {
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
         }
 
-        self.participants
-            .insert(user_id)
-            .map_err(|e| format!("Failed to add participant: {:?}", e))?;
+        let terms = search::query_terms(&query);
+        if terms.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Search query has no searchable terms",
+            ));
+        }
 
-        self.permissions
-            .insert(user_id, permission.clone())
-            .map_err(|e| format!("Failed to set permissions: {:?}", e))?;
+        let total_documents = self
+            .documents
+            .len()
+            .map_err(|e| MeroDocsError::storage(format!("Failed to count documents: {:?}", e)))?
+            .max(1) as f32;
+
+        // Accumulate TF-IDF per document across the query terms
+        let mut scores: Vec<(String, f32, Vec<String>)> = Vec::new();
+        for term in &terms {
+            let postings = match self.keyword_index.get(term) {
+                Ok(Some(postings)) => postings,
+                Ok(None) => continue,
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to read keyword index: {:?}",
+                        e
+                    )))
+                }
+            };
+            let postings: Vec<(String, u32)> = match postings.entries() {
+                Ok(entries) => entries.map(|(id, tf)| (id, *tf.get())).collect(),
+                Err(e) => {
+                    return Err(MeroDocsError::storage(format!(
+                        "Failed to read keyword index: {:?}",
+                        e
+                    )))
+                }
+            };
+            if postings.is_empty() {
+                continue;
+            }
 
-        if permission == PermissionLevel::Sign {
-            let mut docs_to_update = Vec::new();
-            if let Ok(entries) = self.documents.entries() {
-                for (_, document) in entries {
-                    if document.status == DocumentStatus::FullySigned {
-                        let mut updated_document = document.clone();
-                        updated_document.status = DocumentStatus::PartiallySigned;
-                        docs_to_update.push(updated_document);
+            let idf = (1.0 + total_documents / postings.len() as f32).ln();
+            for (document_id, frequency) in postings {
+                let weight = (1.0 + (frequency.max(1) as f32).ln()) * idf;
+                match scores.iter_mut().find(|(id, _, _)| *id == document_id) {
+                    Some((_, score, matched)) => {
+                        *score += weight;
+                        matched.push(term.clone());
                     }
+                    None => scores.push((document_id, weight, vec![term.clone()])),
                 }
             }
-            for document in docs_to_update {
-                let _ = self.documents.insert(document.id.clone(), document);
-            }
         }
 
-        app::emit!(MeroSignEvent::ParticipantJoined { user_id });
-
-        Ok(())
-    }
-
-    /// Remove participant from shared context
-    pub fn remove_participant(&mut self, user_id_str: String) -> Result<(), String> {
-        self.validate_admin_permissions()?;
+        scores.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
 
-        let user_id = parse_public_key_base58(&user_id_str)?;
+        let limit = limit.unwrap_or(DEFAULT_KEYWORD_SEARCH_LIMIT) as usize;
+        let mut hits = Vec::new();
+        for (document_id, score, matched_terms) in scores {
+            if hits.len() >= limit {
+                break;
+            }
+            let document = match self.documents.get(&document_id) {
+                Ok(Some(doc)) if self.can_view_document(&doc) => doc,
+                _ => continue,
+            };
 
-        if !self.participants.contains(&user_id).unwrap_or(false) {
-            return Err("User is not a participant".to_string());
+            let chunks = match document.extracted_text {
+                Some(_) => Vec::new(),
+                None => self.load_document_chunks(&document_id)?,
+            };
+            let snippets = keyword_text(&document, &chunks)
+                .map(|text| {
+                    search::highlight_snippets(&text, &matched_terms, KEYWORD_SNIPPETS_PER_HIT)
+                })
+                .unwrap_or_default();
+
+            hits.push(KeywordSearchHit {
+                document_id,
+                name: document.name,
+                score,
+                matched_terms,
+                snippets,
+            });
         }
 
-        self.participants
-            .remove(&user_id)
-            .map_err(|e| format!("Failed to remove participant: {:?}", e))?;
+        Ok(hits)
+    }
 
-        self.permissions
-            .remove(&user_id)
-            .map_err(|e| format!("Failed to remove permissions: {:?}", e))?;
+    /// Add a document's terms to the keyword index
+    fn index_document_keywords(
+        &mut self,
+        document: &DocumentInfo,
+        chunks: &[DocumentChunk],
+    ) -> Result<(), MeroDocsError> {
+        let Some(text) = keyword_text(document, chunks) else {
+            return Ok(());
+        };
 
-        app::emit!(MeroSignEvent::ParticipantLeft { user_id });
+        for (term, frequency) in search::term_frequencies(&text) {
+            let mut postings = self
+                .keyword_index
+                .get(&term)
+                .map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to read keyword index: {:?}", e))
+                })?
+                .unwrap_or_else(UnorderedMap::new);
+            postings
+                .insert(document.id.clone(), frequency.into())
+                .map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to update keyword index: {:?}", e))
+                })?;
+            self.keyword_index.insert(term, postings).map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update keyword index: {:?}", e))
+            })?;
+        }
 
         Ok(())
     }
 
-    /// List all participants
-    pub fn list_participants(&self) -> Result<Vec<UserId>, String> {
-        let mut participants = Vec::new();
-        if let Ok(iter) = self.participants.iter() {
-            for participant in iter {
-                participants.push(participant.clone());
+    /// Remove a document's terms from the keyword index
+    fn unindex_document_keywords(
+        &mut self,
+        document: &DocumentInfo,
+        chunks: &[DocumentChunk],
+    ) -> Result<(), MeroDocsError> {
+        let Some(text) = keyword_text(document, chunks) else {
+            return Ok(());
+        };
+
+        for (term, _) in search::term_frequencies(&text) {
+            let Ok(Some(mut postings)) = self.keyword_index.get(&term) else {
+                continue;
+            };
+            let _ = postings.remove(&document.id);
+            if postings.len().unwrap_or(0) == 0 {
+                let _ = self.keyword_index.remove(&term);
+            } else {
+                self.keyword_index.insert(term, postings).map_err(|e| {
+                    MeroDocsError::storage(format!("Failed to update keyword index: {:?}", e))
+                })?;
             }
         }
-        Ok(participants)
+
+        Ok(())
     }
 
-    /// Get user permission level
-    pub fn get_user_permission(&self, user_id_str: String) -> Result<PermissionLevel, String> {
-        let user_id = parse_public_key_base58(&user_id_str)?;
-        match self.permissions.get(&user_id) {
-            Ok(Some(perm)) => Ok(perm.clone()),
-            Ok(None) => Err("User not found".to_string()),
-            Err(e) => Err(format!("Failed to get permission: {:?}", e)),
+    /// Rank chunks from every visible document against a query embedding. Documents being
+    /// re-indexed or embedded with another dimension are skipped, and the scan stops after
+    /// a fixed number of chunks.
+    pub fn search_context_by_embedding(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: Option<u64>,
+    ) -> Result<ContextSearchResult, MeroDocsError> {
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
+        }
+        if query_embedding.is_empty() {
+            return Err(MeroDocsError::invalid_input(
+                "Query embedding cannot be empty",
+            ));
         }
-    }
 
-    /// Get current context ID
-    pub fn get_context_id(&self) -> ContextId {
-        env::context_id()
-    }
+        let top_k = top_k.unwrap_or(10).clamp(1, MAX_CONTEXT_SEARCH_TOP_K) as usize;
+        // Hits carry the index of their chunk so passages are only stitched for the winners
+        let mut hits: Vec<(ChunkSearchHit, Option<usize>)> = Vec::with_capacity(top_k + 1);
+        let mut scanned_chunks = 0u64;
+        let mut truncated = false;
+
+        // Keep `hits` sorted best-first and no longer than top_k
+        let mut offer = |hit: ChunkSearchHit, chunk_index: Option<usize>| {
+            if hits.len() == top_k
+                && hits
+                    .last()
+                    .is_some_and(|(worst, _)| worst.score >= hit.score)
+            {
+                return;
+            }
+            let position = hits
+                .iter()
+                .position(|(h, _)| h.score < hit.score)
+                .unwrap_or(hits.len());
+            hits.insert(position, (hit, chunk_index));
+            hits.truncate(top_k);
+        };
 
-    /// Get identity mapping for a specific context
-    pub fn get_identity_mapping(&self, context_id_str: String) -> Result<IdentityMapping, String> {
-        if !*self.is_private.get() {
-            return Err("Identity mappings can only be accessed in private context".to_string());
-        }
+        if let Ok(entries) = self.documents.entries() {
+            'documents: for (document_id, document) in entries {
+                if !self.can_view_document(&document) || self.is_document_stale(&document_id) {
+                    continue;
+                }
 
-        let context_id = parse_context_id_base58(&context_id_str)?;
-        let context_id_key = encode_context_id_base58(&context_id);
+                let chunks = match self.load_document_chunks(&document_id) {
+                    Ok(chunks) => chunks,
+                    Err(_) => continue,
+                };
 
-        match self.identity_mappings.get(&context_id_key) {
-            Ok(Some(mapping)) => Ok(mapping.clone()),
-            Ok(None) => Err("Identity mapping not found for this context".to_string()),
-            Err(e) => Err(format!("Failed to get identity mapping: {:?}", e)),
-        }
-    }
+                if chunks.is_empty() {
+                    // Whole-document embeddings count as a single chunk
+                    let Some(embedding) = &document.embeddings else {
+                        continue;
+                    };
+                    if scanned_chunks >= MAX_CONTEXT_SEARCH_CHUNKS {
+                        truncated = true;
+                        break;
+                    }
+                    scanned_chunks += 1;
+                    if embedding.len() != query_embedding.len() {
+                        continue;
+                    }
+                    let score = cosine_similarity(&query_embedding, embedding);
+                    if score > 0.1 {
+                        let text = document.extracted_text.as_deref().unwrap_or_default();
+                        let snippet = truncate_snippet(text.trim(), 300);
+                        offer(
+                            ChunkSearchHit {
+                                document_id: document_id.clone(),
+                                document_name: document.name.clone(),
+                                text: snippet.clone(),
+                                passage: snippet,
+                                start_position: 0,
+                                end_position: text.len(),
+                                score,
+                            },
+                            None,
+                        );
+                    }
+                    continue;
+                }
 
-    /// Get shared identity for a specific context
-    pub fn get_shared_identity(&self, context_id_str: String) -> Result<UserId, String> {
-        if !*self.is_private.get() {
-            return Err("Identity resolution can only be done in private context".to_string());
+                for (chunk_index, chunk) in chunks.iter().enumerate() {
+                    if scanned_chunks >= MAX_CONTEXT_SEARCH_CHUNKS {
+                        truncated = true;
+                        break 'documents;
+                    }
+                    scanned_chunks += 1;
+                    if chunk.embedding.len() != query_embedding.len() {
+                        continue;
+                    }
+                    let score = cosine_similarity(&query_embedding, &chunk.embedding);
+                    if score > 0.1 {
+                        offer(
+                            ChunkSearchHit {
+                                document_id: document_id.clone(),
+                                document_name: document.name.clone(),
+                                text: chunk.text.trim().to_string(),
+                                passage: String::new(),
+                                start_position: chunk.start_position,
+                                end_position: chunk.end_position,
+                                score,
+                            },
+                            Some(chunk_index),
+                        );
+                    }
+                }
+            }
         }
 
-        let mapping = self.get_identity_mapping(context_id_str)?;
-        Ok(mapping.shared_identity)
-    }
-
-    /// Resolve private identity from shared identity
-    pub fn resolve_private_identity(
-        &self,
-        shared_identity_str: String,
-    ) -> Result<Option<UserId>, String> {
-        if *self.is_private.get() {
-            let shared_identity = parse_public_key_base58(&shared_identity_str)?;
-            if let Ok(entries) = self.identity_mappings.entries() {
-                for (_, mapping) in entries {
-                    if mapping.shared_identity == shared_identity {
-                        return Ok(Some(mapping.private_identity));
+        let window = *self.chunk_context_window.get() as usize;
+        let mut results = Vec::with_capacity(hits.len());
+        for (mut hit, chunk_index) in hits {
+            if let Some(chunk_index) = chunk_index {
+                if let Ok(chunks) = self.load_document_chunks(&hit.document_id) {
+                    if let Some(passage) =
+                        search::stitch_hits(&chunks, &[(chunk_index, hit.score)], window)
+                            .into_iter()
+                            .next()
+                    {
+                        hit.passage = passage.text;
                     }
                 }
+                if hit.passage.is_empty() {
+                    hit.passage = hit.text.clone();
+                }
             }
-            Ok(None)
-        } else {
-            Err("Cannot resolve private identity from shared context".to_string())
+            results.push(hit);
+        }
+
+        Ok(ContextSearchResult {
+            hits: results,
+            scanned_chunks,
+            truncated,
+        })
+    }
+
+    /// Set how many neighbouring chunks are stitched onto each side of a search hit
+    /// (admin only)
+    pub fn set_chunk_context_window(&mut self, window: u64) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+        self.require_capability(roles::MANAGE)?;
+
+        if window > MAX_CHUNK_CONTEXT_WINDOW {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Chunk context window cannot exceed {} chunks",
+                MAX_CHUNK_CONTEXT_WINDOW
+            )));
         }
+
+        self.chunk_context_window.set(window);
+
+        self.record_audit(
+            AuditAction::ChunkContextWindowChanged,
+            "context",
+            format!("window={}", window),
+        )?;
+
+        Ok(())
+    }
+
+    /// Get how many neighbouring chunks are stitched onto each side of a search hit
+    pub fn get_chunk_context_window(&self) -> u64 {
+        *self.chunk_context_window.get()
     }
 
     pub fn search_document_by_embedding(
         &self,
         query_embedding: Vec<f32>,
         document_id: String,
-    ) -> Result<String, String> {
+    ) -> Result<String, MeroDocsError> {
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
+        }
+
         let document = match self.documents.get(&document_id) {
-            Ok(Some(doc)) => doc,
-            Ok(None) => return Err(format!("Document with ID '{}' not found", document_id)),
-            Err(e) => return Err(format!("Failed to access document: {:?}", e)),
+            Ok(Some(doc)) if self.can_view_document(&doc) => doc,
+            Ok(_) => {
+                return Err(MeroDocsError::not_found(format!(
+                    "Document with ID '{}' not found",
+                    document_id
+                )))
+            }
+            Err(e) => {
+                return Err(MeroDocsError::storage(format!(
+                    "Failed to access document: {:?}",
+                    e
+                )))
+            }
         };
 
-        if let Some(chunks) = &document.chunks {
-            if chunks.is_empty() {
-                return Err("Document has no chunks for semantic search".to_string());
-            }
+        // Stale embeddings give meaningless scores against the new model's queries
+        if self.is_document_stale(&document_id) {
+            let text_snippet = match document.extracted_text {
+                Some(ref full_text) => {
+                    let clean_text = full_text.replace(['\n', '\r'], " ").replace("  ", " ");
+                    truncate_snippet(&clean_text, 250)
+                }
+                None => "No extracted text available".to_string(),
+            };
+            return Ok(format!(
+                "Document: {} (Re-indexing in progress, semantic ranking unavailable)\n{}",
+                document.name, text_snippet
+            ));
+        }
 
+        let chunks = self.load_document_chunks(&document_id)?;
+        if !chunks.is_empty() {
             if chunks[0].embedding.len() != query_embedding.len() {
-                return Err(format!(
-                    "Embedding dimension mismatch: query={}, document chunks={}",
+                return Err(MeroDocsError::invalid_input(format!(
+                    "Embedding dimension mismatch: query={}, document chunks={}{}",
                     query_embedding.len(),
-                    chunks[0].embedding.len()
-                ));
+                    chunks[0].embedding.len(),
+                    self.embedding_model_label()
+                )));
             }
 
-            let mut chunk_similarities: Vec<(&DocumentChunk, f32)> = chunks
+            let mut chunk_similarities: Vec<(usize, f32)> = chunks
                 .iter()
-                .map(|chunk| {
+                .enumerate()
+                .map(|(index, chunk)| {
                     let similarity = cosine_similarity(&query_embedding, &chunk.embedding);
-                    (chunk, similarity)
+                    (index, similarity)
                 })
                 .filter(|(_, similarity)| *similarity > 0.1)
                 .collect();
@@ -1142,17 +11082,14 @@ impl MeroSignState {
             chunk_similarities
                 .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-            let top_chunks: Vec<String> = chunk_similarities
-                .into_iter()
-                .take(3)
-                .map(|(chunk, similarity)| {
-                    let clean_text = chunk
-                        .text
-                        .trim()
-                        .replace('\n', " ")
-                        .replace('\r', " ")
-                        .replace("  ", " ");
+            chunk_similarities.truncate(3);
 
+            // Neighbouring chunks are stitched on so clauses aren't cut mid-sentence
+            let window = *self.chunk_context_window.get() as usize;
+            let top_chunks: Vec<String> = search::stitch_hits(&chunks, &chunk_similarities, window)
+                .into_iter()
+                .map(|passage| {
+                    let similarity = passage.score;
                     let max_chars = if similarity > 0.5 {
                         300
                     } else if similarity > 0.3 {
@@ -1161,11 +11098,8 @@ impl MeroSignState {
                         150
                     };
 
-                    let display_text = if clean_text.len() > max_chars {
-                        format!("{}...", &clean_text[..max_chars])
-                    } else {
-                        clean_text
-                    };
+                    let display_text =
+                        truncate_snippet(&passage.text, max_chars * passage.chunk_count);
 
                     format!("[Relevance: {:.2}] {}", similarity, display_text)
                 })
@@ -1180,15 +11114,20 @@ impl MeroSignState {
 
         let doc_embedding = match &document.embeddings {
             Some(embedding) => embedding,
-            None => return Err("Document has no embeddings for semantic search".to_string()),
+            None => {
+                return Err(MeroDocsError::invalid_state(
+                    "Document has no embeddings for semantic search",
+                ))
+            }
         };
 
         if doc_embedding.len() != query_embedding.len() {
-            return Err(format!(
-                "Embedding dimension mismatch: query={}, document={}",
+            return Err(MeroDocsError::invalid_input(format!(
+                "Embedding dimension mismatch: query={}, document={}{}",
                 query_embedding.len(),
-                doc_embedding.len()
-            ));
+                doc_embedding.len(),
+                self.embedding_model_label()
+            )));
         }
 
         let similarity = cosine_similarity(&query_embedding, doc_embedding);
@@ -1214,11 +11153,7 @@ impl MeroSignState {
                 150
             };
 
-            if clean_text.len() > max_chars {
-                format!("{}...", &clean_text[..max_chars])
-            } else {
-                clean_text
-            }
+            truncate_snippet(&clean_text, max_chars)
         } else {
             format!("Document: {} (No extracted text available)", document.name)
         };
@@ -1228,15 +11163,283 @@ impl MeroSignState {
             document.name, similarity, text_snippet
         ))
     }
+
+    /// Record a question/answer exchange about a document so all participants can see it
+    pub fn record_qa_exchange(
+        &mut self,
+        document_id: String,
+        question: String,
+        answer: String,
+        citations: Vec<QaCitation>,
+    ) -> Result<(), MeroDocsError> {
+        self.ensure_writable()?;
+
+        if *self.is_private.get() {
+            return Err(MeroDocsError::wrong_context(
+                "Q&A history can only be recorded in shared context",
+            ));
+        }
+
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
+        }
+
+        self.get_visible_document(&document_id)?;
+
+        if question.trim().is_empty() {
+            return Err(MeroDocsError::invalid_input("Question cannot be empty"));
+        }
+        if question.chars().count() > MAX_QA_QUESTION_CHARS {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Question cannot exceed {} characters",
+                MAX_QA_QUESTION_CHARS
+            )));
+        }
+        if answer.chars().count() > MAX_QA_ANSWER_CHARS {
+            return Err(MeroDocsError::limit_exceeded(format!(
+                "Answer cannot exceed {} characters",
+                MAX_QA_ANSWER_CHARS
+            )));
+        }
+
+        let asked_by = current_caller();
+        let exchange = QaExchange {
+            question,
+            answer,
+            citations,
+            asked_by,
+            asked_at: TimestampNanos::now(),
+        };
+
+        let mut history = self
+            .qa_history
+            .get(&document_id)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to get Q&A history: {:?}", e)))?
+            .unwrap_or_else(Vector::new);
+
+        history
+            .push(exchange)
+            .map_err(|e| MeroDocsError::storage(format!("Failed to add Q&A exchange: {:?}", e)))?;
+
+        self.qa_history
+            .insert(document_id.clone(), history)
+            .map_err(|e| {
+                MeroDocsError::storage(format!("Failed to update Q&A history: {:?}", e))
+            })?;
+
+        self.record_audit(AuditAction::QaExchangeRecorded, &document_id, String::new())?;
+
+        app::emit!(MeroSignEvent::QaExchangeRecorded {
+            document_id,
+            asked_by,
+        });
+
+        Ok(())
+    }
+
+    /// List the recorded question/answer exchanges for a document
+    pub fn list_qa_history(&self, document_id: String) -> Result<Vec<QaExchange>, MeroDocsError> {
+        if self.caller_oversight_role() == Some(OversightRole::Auditor) {
+            return Err(MeroDocsError::unauthorized(
+                "Auditors cannot access document content",
+            ));
+        }
+
+        self.get_visible_document(&document_id)?;
+
+        let mut exchanges = Vec::new();
+        if let Ok(Some(history)) = self.qa_history.get(&document_id) {
+            if let Ok(iter) = history.iter() {
+                for exchange in iter {
+                    exchanges.push(exchange.clone());
+                }
+            }
+        }
+        Ok(exchanges)
+    }
+}
+
+/// Helper to hide the parts of a document an oversight role must not see
+fn redact_document(mut document: DocumentInfo, role: Option<&OversightRole>) -> DocumentInfo {
+    match role {
+        None => document,
+        Some(OversightRole::Observer) => {
+            document.pdf_blob_id = [0u8; 32];
+            document
+        }
+        Some(OversightRole::Auditor) => {
+            document.pdf_blob_id = [0u8; 32];
+            document.embeddings = None;
+            document.extracted_text = None;
+            document.summary = None;
+            document
+        }
+    }
+}
+
+/// Text indexed for keyword search: the extracted text, or the chunk texts when there is none
+fn keyword_text(document: &DocumentInfo, chunks: &[DocumentChunk]) -> Option<String> {
+    if let Some(text) = &document.extracted_text {
+        return Some(text.clone());
+    }
+    if chunks.is_empty() {
+        return None;
+    }
+    Some(
+        chunks
+            .iter()
+            .map(|chunk| chunk.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Turn a document name into a safe, unique `.pdf` filename within a manifest
+fn manifest_filename(name: &str, existing: &[ManifestEntry]) -> String {
+    let mut stem: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    if stem.to_ascii_lowercase().ends_with(".pdf") {
+        stem.truncate(stem.len() - 4);
+    }
+    if stem.is_empty() {
+        stem = "document".to_string();
+    }
+
+    let taken = |candidate: &str| {
+        existing
+            .iter()
+            .any(|e| e.filename.eq_ignore_ascii_case(candidate))
+    };
+    let mut filename = format!("{}.pdf", stem);
+    let mut copy = 2;
+    while taken(&filename) {
+        filename = format!("{} ({}).pdf", stem, copy);
+        copy += 1;
+    }
+    filename
+}
+
+/// Helper to derive signing turnaround from a document's timestamps
+fn document_signing_metrics(document: &DocumentInfo) -> DocumentSigningMetrics {
+    DocumentSigningMetrics {
+        document_id: document.id.clone(),
+        uploaded_at: document.uploaded_at,
+        time_to_first_signature: document
+            .first_signed_at
+            .map(|t| t.nanos_since(document.uploaded_at)),
+        time_to_completion: document
+            .completed_at
+            .map(|t| t.nanos_since(document.uploaded_at)),
+    }
+}
+
+/// Check a usage report period is `YYYY` or `YYYY-MM`
+fn validate_usage_period(period: &str) -> Result<(), MeroDocsError> {
+    let (year, month) = match period.split_once('-') {
+        Some((year, month)) => (year, Some(month)),
+        None => (period, None),
+    };
+    let valid_year = year.len() == 4 && year.chars().all(|c| c.is_ascii_digit());
+    let valid_month =
+        month.is_none_or(|m| m.len() == 2 && m.parse::<u32>().is_ok_and(|m| (1..=12).contains(&m)));
+    if !valid_year || !valid_month {
+        return Err(MeroDocsError::invalid_input(
+            "Period must be a year (YYYY) or a month (YYYY-MM)",
+        ));
+    }
+    Ok(())
+}
+
+/// Helper to average durations without overflowing
+fn average(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum: u128 = values.iter().map(|&v| v as u128).sum();
+    Some((sum / values.len() as u128) as u64)
 }
 
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if norm_a == 0.0 || norm_b == 0.0 {
-        0.0
+/// Quote a CSV field when it contains separators, quotes or line breaks
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
     } else {
-        dot_product / (norm_a * norm_b)
+        value.to_string()
+    }
+}
+
+/// Escape a string for embedding inside a JSON string literal
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
     }
+    escaped
+}
+
+/// Truncate a snippet to at most `max_chars` characters without splitting a
+/// multi-byte character or detaching combining marks from their base character
+fn truncate_snippet(text: &str, max_chars: usize) -> String {
+    let mut end = match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => idx,
+        None => return text.to_string(),
+    };
+
+    // Back off so the cut doesn't land inside a grapheme cluster
+    while end > 0 {
+        let next = text[end..].chars().next();
+        let prev = text[..end].chars().next_back();
+        if next.is_some_and(is_grapheme_extender) || prev == Some('\u{200D}') {
+            end = text[..end]
+                .char_indices()
+                .next_back()
+                .map(|(idx, _)| idx)
+                .unwrap_or(0);
+        } else {
+            break;
+        }
+    }
+
+    format!("{}...", &text[..end])
+}
+
+/// Characters that attach to the preceding character and must stay with it
+fn is_grapheme_extender(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F // Combining diacritical marks
+            | 0x0483..=0x0489 // Cyrillic combining marks
+            | 0x0591..=0x05BD // Hebrew points
+            | 0x0610..=0x061A // Arabic marks
+            | 0x064B..=0x065F // Arabic vowel marks
+            | 0x0900..=0x0903 // Devanagari signs
+            | 0x093A..=0x094F // Devanagari vowel signs and virama
+            | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E // Thai vowels and tone marks
+            | 0x1AB0..=0x1AFF // Combining diacritical marks extended
+            | 0x1DC0..=0x1DFF // Combining diacritical marks supplement
+            | 0x200C..=0x200D // Zero-width non-joiner / joiner
+            | 0x20D0..=0x20FF // Combining marks for symbols
+            | 0x3099..=0x309A // Japanese voiced sound marks
+            | 0xFE00..=0xFE0F // Variation selectors
+            | 0xFE20..=0xFE2F // Combining half marks
+            | 0x1F3FB..=0x1F3FF // Emoji skin tone modifiers
+            | 0xE0100..=0xE01EF // Variation selectors supplement
+    )
 }
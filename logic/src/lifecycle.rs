@@ -0,0 +1,221 @@
+//! Document status state machine.
+//!
+//! Every status change goes through [`next_status`], so the allowed
+//! transitions and their guards live in one place instead of being repeated
+//! in each method that touches a document.
+
+use crate::{DocumentStatus, MeroDocsError};
+
+/// Something that happened to a document and may change its status
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusTrigger {
+    /// A signature was added to the document
+    Signed,
+    /// The set of required signers or signatures changed; `complete` is true
+    /// when no required signer is outstanding
+    Recalculated { complete: bool },
+    /// The document passed its expiry time
+    Expired,
+    /// A signer formally rejected the document
+    Declined,
+    /// An admin reopened a declined document
+    Reopened,
+    /// The uploader or an admin withdrew the document before completion
+    Voided,
+}
+
+/// Compute the status a document moves to, or the reason the trigger is not allowed.
+/// `has_signatures` is whether at least one signature has been recorded.
+pub fn next_status(
+    current: &DocumentStatus,
+    trigger: StatusTrigger,
+    has_signatures: bool,
+) -> Result<DocumentStatus, MeroDocsError> {
+    use DocumentStatus::*;
+
+    match (current, trigger) {
+        (Expired, StatusTrigger::Signed) => {
+            Err(MeroDocsError::invalid_state("Document has expired"))
+        }
+        (Declined, StatusTrigger::Signed) => Err(MeroDocsError::invalid_state(
+            "Document has been declined and must be reopened first",
+        )),
+        (Voided, StatusTrigger::Signed) => {
+            Err(MeroDocsError::invalid_state("Document has been voided"))
+        }
+        (FullySigned, StatusTrigger::Signed) => Err(MeroDocsError::invalid_state(
+            "Document is already fully signed",
+        )),
+        (Pending | PartiallySigned, StatusTrigger::Signed) => Ok(PartiallySigned),
+
+        // Closed documents keep their status when signers change
+        (Expired | Declined | Voided, StatusTrigger::Recalculated { .. }) => Ok(current.clone()),
+        (_, StatusTrigger::Recalculated { complete }) => {
+            Ok(signing_progress(complete, has_signatures))
+        }
+
+        (Pending | PartiallySigned, StatusTrigger::Expired) => Ok(Expired),
+        (_, StatusTrigger::Expired) => Err(MeroDocsError::invalid_state(
+            "Only unsigned or partially signed documents can expire",
+        )),
+
+        (Pending | PartiallySigned, StatusTrigger::Declined) => Ok(Declined),
+        (FullySigned, StatusTrigger::Declined) => Err(MeroDocsError::invalid_state(
+            "Cannot decline a fully signed document",
+        )),
+        (Expired, StatusTrigger::Declined) => Err(MeroDocsError::invalid_state(
+            "Cannot decline an expired document",
+        )),
+        (Declined, StatusTrigger::Declined) => Err(MeroDocsError::invalid_state(
+            "Document has already been declined",
+        )),
+        (Voided, StatusTrigger::Declined) => Err(MeroDocsError::invalid_state(
+            "Cannot decline a voided document",
+        )),
+
+        (Declined, StatusTrigger::Reopened) => Ok(signing_progress(false, has_signatures)),
+        (_, StatusTrigger::Reopened) => {
+            Err(MeroDocsError::invalid_state("Document is not declined"))
+        }
+
+        (Pending | PartiallySigned, StatusTrigger::Voided) => Ok(Voided),
+        (Voided, StatusTrigger::Voided) => Err(MeroDocsError::invalid_state(
+            "Document has already been voided",
+        )),
+        (_, StatusTrigger::Voided) => Err(MeroDocsError::invalid_state(
+            "Only unsigned or partially signed documents can be voided",
+        )),
+    }
+}
+
+/// Status of an open document given how far signing has progressed
+fn signing_progress(complete: bool, has_signatures: bool) -> DocumentStatus {
+    match (complete, has_signatures) {
+        (true, true) => DocumentStatus::FullySigned,
+        (_, true) => DocumentStatus::PartiallySigned,
+        (_, false) => DocumentStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::DocumentStatus::*;
+
+    fn any_status() -> impl Strategy<Value = DocumentStatus> {
+        prop_oneof![
+            Just(Pending),
+            Just(PartiallySigned),
+            Just(FullySigned),
+            Just(Expired),
+            Just(Declined),
+            Just(Voided),
+        ]
+    }
+
+    fn any_trigger() -> impl Strategy<Value = StatusTrigger> {
+        prop_oneof![
+            Just(StatusTrigger::Signed),
+            any::<bool>().prop_map(|complete| StatusTrigger::Recalculated { complete }),
+            Just(StatusTrigger::Expired),
+            Just(StatusTrigger::Declined),
+            Just(StatusTrigger::Reopened),
+            Just(StatusTrigger::Voided),
+        ]
+    }
+
+    /// Apply a trigger the way `transition_document` does: a rejected trigger leaves
+    /// the status alone, and a signature is only recorded when the trigger is allowed
+    fn apply(status: &mut DocumentStatus, has_signatures: &mut bool, trigger: StatusTrigger) {
+        let signs = trigger == StatusTrigger::Signed;
+        if let Ok(next) = next_status(status, trigger, *has_signatures || signs) {
+            *status = next;
+            *has_signatures |= signs;
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn signing_only_moves_open_documents_to_partially_signed(
+            status in any_status(),
+            has_signatures in any::<bool>(),
+        ) {
+            let open = matches!(status, Pending | PartiallySigned);
+            match next_status(&status, StatusTrigger::Signed, has_signatures) {
+                Ok(next) => {
+                    prop_assert!(open);
+                    prop_assert_eq!(next, PartiallySigned);
+                }
+                Err(_) => prop_assert!(!open),
+            }
+        }
+
+        #[test]
+        fn recalculation_rolls_up_signing_progress(
+            status in any_status(),
+            complete in any::<bool>(),
+            has_signatures in any::<bool>(),
+        ) {
+            let next = next_status(
+                &status,
+                StatusTrigger::Recalculated { complete },
+                has_signatures,
+            )
+            .unwrap();
+
+            if matches!(status, Expired | Declined | Voided) {
+                prop_assert_eq!(next, status);
+            } else if !has_signatures {
+                prop_assert_eq!(next, Pending);
+            } else if complete {
+                prop_assert_eq!(next, FullySigned);
+            } else {
+                prop_assert_eq!(next, PartiallySigned);
+            }
+        }
+
+        #[test]
+        fn expired_and_voided_documents_stay_closed(
+            status in prop_oneof![Just(Expired), Just(Voided)],
+            triggers in prop::collection::vec(any_trigger(), 0..32),
+        ) {
+            let closed = status.clone();
+            let mut status = status;
+            let mut has_signatures = false;
+            for trigger in triggers {
+                apply(&mut status, &mut has_signatures, trigger);
+                prop_assert_eq!(&status, &closed);
+            }
+        }
+
+        #[test]
+        fn any_trigger_sequence_keeps_status_consistent_with_signatures(
+            triggers in prop::collection::vec(any_trigger(), 0..64),
+        ) {
+            let mut status = Pending;
+            let mut has_signatures = false;
+            for trigger in triggers {
+                apply(&mut status, &mut has_signatures, trigger);
+                match status {
+                    Pending => prop_assert!(!has_signatures),
+                    PartiallySigned | FullySigned => prop_assert!(has_signatures),
+                    Expired | Declined | Voided => {}
+                }
+            }
+        }
+
+        #[test]
+        fn only_declined_documents_can_be_reopened(
+            status in any_status(),
+            has_signatures in any::<bool>(),
+        ) {
+            let reopened = next_status(&status, StatusTrigger::Reopened, has_signatures);
+            prop_assert_eq!(reopened.is_ok(), status == Declined);
+            if let Ok(next) = reopened {
+                prop_assert!(matches!(next, Pending | PartiallySigned));
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Migration from the original state layout.
+//!
+//! Contexts created before chunks moved to their own store kept them inline on
+//! each document, stored embeddings as bare `f32` arrays and recorded only the
+//! signer and time of a signature. Borsh has no field tags, so those records
+//! cannot be read with the current types. `migrate_from_v0` reads the old root
+//! with the legacy types below and rewrites every record in the current layout.
+//! Fields that did not exist yet get the same defaults a new upload would.
+
+use std::fmt::Debug;
+
+use calimero_sdk::app;
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::state::read_raw;
+use calimero_storage::collections::{LwwRegister, Mergeable, UnorderedMap, UnorderedSet, Vector};
+
+use crate::{
+    BlobId, ContextMetadata, DocumentChunk, DocumentInfo, DocumentSignature, DocumentStatus,
+    Embedding, IdentityMapping, MeroSignState, PermissionLevel, SignatureCapacity, SignatureKind,
+    SignatureRecord, TimestampNanos, UserId,
+};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct LegacySignatureRecord {
+    id: u64,
+    name: String,
+    blob_id: BlobId,
+    size: u64,
+    created_at: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct LegacyDocumentChunk {
+    text: String,
+    embedding: Vec<f32>,
+    start_position: usize,
+    end_position: usize,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct LegacyDocumentInfo {
+    id: String,
+    name: String,
+    hash: String,
+    uploaded_by: UserId,
+    uploaded_at: u64,
+    /// The first three `DocumentStatus` variants, which keep their encoding
+    status: DocumentStatus,
+    pdf_blob_id: BlobId,
+    size: u64,
+    embeddings: Option<Vec<f32>>,
+    extracted_text: Option<String>,
+    chunks: Option<Vec<LegacyDocumentChunk>>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct LegacyDocumentSignature {
+    signer: UserId,
+    signed_at: u64,
+}
+
+impl Mergeable for LegacySignatureRecord {
+    fn merge(
+        &mut self,
+        _other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Legacy records are only read once, during migration
+        Ok(())
+    }
+}
+
+impl Mergeable for LegacyDocumentInfo {
+    fn merge(
+        &mut self,
+        _other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Legacy records are only read once, during migration
+        Ok(())
+    }
+}
+
+impl Mergeable for LegacyDocumentSignature {
+    fn merge(
+        &mut self,
+        _other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // Legacy records are only read once, during migration
+        Ok(())
+    }
+}
+
+#[derive(BorshDeserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+struct LegacyMeroSignState {
+    is_private: LwwRegister<bool>,
+    owner: LwwRegister<UserId>,
+    context_name: LwwRegister<String>,
+    signatures: UnorderedMap<String, LegacySignatureRecord>,
+    joined_contexts: UnorderedMap<String, ContextMetadata>,
+    identity_mappings: UnorderedMap<String, IdentityMapping>,
+    signature_count: LwwRegister<u64>,
+    participants: UnorderedSet<UserId>,
+    documents: UnorderedMap<String, LegacyDocumentInfo>,
+    document_signatures: UnorderedMap<String, Vector<LegacyDocumentSignature>>,
+    permissions: UnorderedMap<UserId, PermissionLevel>,
+    consents: UnorderedMap<String, LwwRegister<bool>>,
+}
+
+/// Abort the migration; the node keeps the old state and application
+fn fail(what: &str, error: impl Debug) -> ! {
+    panic!("Migration failed: {}: {:?}", what, error)
+}
+
+/// Rewrite a context created with the original layout
+#[app::migrate]
+pub fn migrate_from_v0() -> MeroSignState {
+    let bytes = read_raw().unwrap_or_else(|| fail("no existing state", ()));
+    let mut legacy = LegacyMeroSignState::deserialize(&mut &bytes[..])
+        .unwrap_or_else(|e| fail("state is not in the original layout", e));
+
+    let mut state = MeroSignState::empty(
+        *legacy.is_private.get(),
+        legacy.context_name.get().clone(),
+        *legacy.owner.get(),
+    );
+
+    // Collections whose records kept their layout move over as they are
+    state.joined_contexts = legacy.joined_contexts;
+    state.identity_mappings = legacy.identity_mappings;
+    state.signature_count = legacy.signature_count;
+    state.participants = legacy.participants;
+    state.permissions = legacy.permissions;
+    state.consents = legacy.consents;
+
+    let signatures = legacy
+        .signatures
+        .entries()
+        .unwrap_or_else(|e| fail("could not read signatures", e));
+    for (key, record) in signatures {
+        let signature = SignatureRecord {
+            id: record.id,
+            name: record.name,
+            blob_id: record.blob_id,
+            size: record.size,
+            format: None,
+            // The original app only stored signatures drawn on the signature pad
+            kind: SignatureKind::Drawn,
+            created_at: TimestampNanos(record.created_at),
+        };
+        state
+            .signatures
+            .insert(key, signature)
+            .unwrap_or_else(|e| fail("could not store signature", e));
+    }
+    let _ = legacy.signatures.clear();
+
+    let documents = legacy
+        .documents
+        .entries()
+        .unwrap_or_else(|e| fail("could not read documents", e));
+    for (id, document) in documents {
+        let mut signed_at = Vec::new();
+        if let Ok(Some(mut legacy_signatures)) = legacy.document_signatures.get(&id) {
+            let mut signatures = Vector::new();
+            if let Ok(iter) = legacy_signatures.iter() {
+                for signature in iter {
+                    signed_at.push(signature.signed_at);
+                    signatures
+                        .push(DocumentSignature {
+                            signer: signature.signer,
+                            signed_at: TimestampNanos(signature.signed_at),
+                            signed_by: signature.signer,
+                            on_behalf_of: None,
+                            // Signed hashes were not recorded; without a signature payload
+                            // nothing is verified against it
+                            signed_hash: String::new(),
+                            signature: None,
+                            organization_id: None,
+                            capacity: SignatureCapacity::Party,
+                        })
+                        .unwrap_or_else(|e| fail("could not store document signature", e));
+                }
+            }
+            state
+                .document_signatures
+                .insert(id.clone(), signatures)
+                .unwrap_or_else(|e| fail("could not store document signatures", e));
+            let _ = legacy_signatures.clear();
+        }
+
+        let chunks: Vec<DocumentChunk> = document
+            .chunks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|chunk| DocumentChunk {
+                text: chunk.text,
+                embedding: Embedding::Float(chunk.embedding),
+                start_position: chunk.start_position,
+                end_position: chunk.end_position,
+            })
+            .collect();
+
+        let migrated = DocumentInfo {
+            id: id.clone(),
+            name: document.name,
+            hash: document.hash,
+            uploaded_by: document.uploaded_by,
+            uploaded_at: TimestampNanos(document.uploaded_at),
+            completed_at: (document.status == DocumentStatus::FullySigned)
+                .then(|| signed_at.iter().max().copied())
+                .flatten()
+                .map(TimestampNanos),
+            status: document.status,
+            pdf_blob_id: document.pdf_blob_id,
+            size: document.size,
+            embeddings: document.embeddings.map(Embedding::Float),
+            extracted_text: document.extracted_text,
+            summary: None,
+            first_signed_at: signed_at.iter().min().copied().map(TimestampNanos),
+            document_number: None,
+            requires_fully_signed: None,
+            signing_order: None,
+            required_signers: None,
+            expires_at: None,
+            signature_fields: Vec::new(),
+            tags: Vec::new(),
+            folder_id: None,
+            signer_due_dates: Vec::new(),
+            access_list: Vec::new(),
+            page_count: None,
+            page_sizes: Vec::new(),
+            organization_id: None,
+            key_epoch: state.current_key_epoch(),
+            template: None,
+            required_witnesses: 0,
+        };
+
+        state
+            .index_document_keywords(&migrated, &chunks)
+            .unwrap_or_else(|e| fail("could not index document keywords", e));
+        state
+            .store_document_chunks(&id, chunks)
+            .unwrap_or_else(|e| fail("could not store document chunks", e));
+        state
+            .documents
+            .insert(id, migrated)
+            .unwrap_or_else(|e| fail("could not store document", e));
+    }
+    let _ = legacy.documents.clear();
+    let _ = legacy.document_signatures.clear();
+
+    state
+}
@@ -0,0 +1,99 @@
+//! Internet Computer principals.
+//!
+//! A participant proves control of a principal by signing the link payload
+//! with the ed25519 key the principal was derived from. The principal of an
+//! ed25519 key is self-authenticating: the SHA-224 of the key's DER encoding
+//! followed by the `0x02` tag. Its text form is the lowercase, unpadded
+//! base32 of a big-endian CRC-32 checksum followed by those bytes, split into
+//! groups of five characters with `-`.
+
+use sha2::{Digest, Sha224};
+
+/// DER prefix of an ed25519 `SubjectPublicKeyInfo`; the raw 32-byte key follows it
+const ED25519_DER_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Tag byte that ends a self-authenticating principal
+const SELF_AUTHENTICATING_TAG: u8 = 0x02;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Text form of the self-authenticating principal of an ed25519 public key
+pub fn self_authenticating(public_key: &[u8; 32]) -> String {
+    let mut der = ED25519_DER_PREFIX.to_vec();
+    der.extend_from_slice(public_key);
+
+    let mut bytes = Sha224::digest(&der).to_vec();
+    bytes.push(SELF_AUTHENTICATING_TAG);
+    to_text(&bytes)
+}
+
+/// Text form of raw principal bytes
+pub fn to_text(bytes: &[u8]) -> String {
+    let mut checked = crc32(bytes).to_be_bytes().to_vec();
+    checked.extend_from_slice(bytes);
+
+    let encoded = base32(&checked);
+    let groups: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(5)
+        .map(|group| std::str::from_utf8(group).unwrap_or_default())
+        .collect();
+    groups.join("-")
+}
+
+/// Lowercase RFC 4648 base32 without padding
+fn base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// CRC-32 (IEEE 802.3), as used by principal checksums
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_principals() {
+        assert_eq!(to_text(&[]), "aaaaa-aa");
+        assert_eq!(to_text(&[0x04]), "2vxsx-fae");
+    }
+
+    #[test]
+    fn self_authenticating_principal_of_an_ed25519_key() {
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(
+            self_authenticating(&key),
+            "7gheb-jchfc-rcrvr-e6jtu-yplhf-2463c-shoi4-2zgcr-mxtd4-e4v72-6qe"
+        );
+    }
+}
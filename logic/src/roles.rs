@@ -0,0 +1,205 @@
+//! Custom roles and the capabilities they grant.
+//!
+//! Each participant's `PermissionLevel` maps to a default set of capabilities.
+//! Admins can define named roles with any combination of capabilities and
+//! assign them to participants. An assigned role replaces the defaults of the
+//! participant's permission level.
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::serde::{Deserialize, Serialize};
+use calimero_storage::collections::Mergeable;
+
+use crate::{MeroDocsError, PermissionLevel, TimestampNanos, UserId};
+
+/// Bitset of capabilities
+pub type Capabilities = u32;
+
+/// Upload documents
+pub const UPLOAD: Capabilities = 1 << 0;
+/// Delete documents
+pub const DELETE: Capabilities = 1 << 1;
+/// Add and remove participants
+pub const INVITE: Capabilities = 1 << 2;
+/// Sign documents
+pub const SIGN: Capabilities = 1 << 3;
+/// Manage the DAO linked to the context; checked by the DAO canister, not here
+pub const MANAGE_DAO: Capabilities = 1 << 4;
+/// Read and export the audit log
+pub const VIEW_AUDIT: Capabilities = 1 << 5;
+/// Change context settings, document metadata, access lists and roles
+pub const MANAGE: Capabilities = 1 << 6;
+
+const ALL: Capabilities = UPLOAD | DELETE | INVITE | SIGN | MANAGE_DAO | VIEW_AUDIT | MANAGE;
+
+/// Capability names as used in the API
+const NAMES: &[(&str, Capabilities)] = &[
+    ("upload", UPLOAD),
+    ("delete", DELETE),
+    ("invite", INVITE),
+    ("sign", SIGN),
+    ("manage_dao", MANAGE_DAO),
+    ("view_audit", VIEW_AUDIT),
+    ("manage", MANAGE),
+];
+
+/// Roles backing the permission levels; their names cannot be used for custom roles
+pub const BUILT_IN_ROLES: &[(&str, PermissionLevel)] = &[
+    ("read", PermissionLevel::Read),
+    ("sign", PermissionLevel::Sign),
+    ("admin", PermissionLevel::Admin),
+];
+
+/// Longest allowed role name, in characters
+const MAX_ROLE_NAME_CHARS: usize = 32;
+
+/// Capabilities of a participant without an assigned role. Read participants may sign
+/// documents that have no access list, so they keep the sign capability; Sign participants
+/// have always been able to upload their own documents.
+pub fn default_capabilities(level: &PermissionLevel) -> Capabilities {
+    match level {
+        PermissionLevel::Read => SIGN,
+        PermissionLevel::Sign => SIGN | UPLOAD,
+        PermissionLevel::Admin => ALL,
+    }
+}
+
+/// Convert capability names to a bitset
+pub fn parse_capabilities(names: &[String]) -> Result<Capabilities, MeroDocsError> {
+    let mut capabilities = 0;
+    for name in names {
+        let name = name.trim().to_ascii_lowercase();
+        let Some((_, bit)) = NAMES.iter().find(|(n, _)| *n == name) else {
+            return Err(MeroDocsError::invalid_input(format!(
+                "Unknown capability: {}",
+                name
+            )));
+        };
+        capabilities |= bit;
+    }
+    Ok(capabilities)
+}
+
+/// Names of the capabilities in a bitset
+pub fn capability_names(capabilities: Capabilities) -> Vec<String> {
+    NAMES
+        .iter()
+        .filter(|(_, bit)| capabilities & bit != 0)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Lowercase a role name and check it only uses letters, digits, `-` and `_`
+pub fn normalize_role_name(name: &str) -> Result<String, MeroDocsError> {
+    let name = name.trim().to_ascii_lowercase();
+    if name.is_empty() || name.chars().count() > MAX_ROLE_NAME_CHARS {
+        return Err(MeroDocsError::invalid_input(format!(
+            "Role name must be 1 to {} characters",
+            MAX_ROLE_NAME_CHARS
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(MeroDocsError::invalid_input(
+            "Role name can only contain letters, digits, '-' and '_'",
+        ));
+    }
+    Ok(name)
+}
+
+/// Custom role defined in a shared context - uses LWW based on updated_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct Role {
+    pub name: String,
+    pub capabilities: Capabilities,
+    pub updated_by: UserId,
+    pub updated_at: TimestampNanos,
+}
+
+impl Mergeable for Role {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.updated_at > self.updated_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Custom role assigned to a participant - uses LWW based on assigned_at
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct RoleAssignment {
+    pub role: String,
+    pub assigned_by: UserId,
+    pub assigned_at: TimestampNanos,
+}
+
+impl Mergeable for RoleAssignment {
+    fn merge(
+        &mut self,
+        other: &Self,
+    ) -> Result<(), calimero_storage::collections::crdt_meta::MergeError> {
+        // LWW based on timestamp - newer wins
+        if other.assigned_at > self.assigned_at {
+            *self = other.clone();
+        }
+        Ok(())
+    }
+}
+
+/// A role with its capabilities spelled out, for listing
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "calimero_sdk::serde")]
+pub struct RoleInfo {
+    pub name: String,
+    pub capabilities: Vec<String>,
+    pub built_in: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn capability_names_round_trip(capabilities in 0..=ALL) {
+            let names = capability_names(capabilities);
+            prop_assert_eq!(parse_capabilities(&names).unwrap(), capabilities);
+        }
+
+        #[test]
+        fn capability_names_ignore_case_and_whitespace(
+            capabilities in 0..=ALL,
+            upper in any::<bool>(),
+        ) {
+            let names: Vec<String> = capability_names(capabilities)
+                .into_iter()
+                .map(|name| if upper { format!(" {} ", name.to_uppercase()) } else { name })
+                .collect();
+            prop_assert_eq!(parse_capabilities(&names).unwrap(), capabilities);
+        }
+
+        #[test]
+        fn unknown_capabilities_are_rejected(name in "[a-z_]{1,12}") {
+            let known = NAMES.iter().any(|(n, _)| *n == name);
+            prop_assert_eq!(parse_capabilities(&[name]).is_ok(), known);
+        }
+    }
+
+    #[test]
+    fn default_capabilities_by_level() {
+        assert_eq!(default_capabilities(&PermissionLevel::Admin), ALL);
+        assert_eq!(default_capabilities(&PermissionLevel::Sign), SIGN | UPLOAD);
+        assert_eq!(default_capabilities(&PermissionLevel::Read), SIGN);
+    }
+}
@@ -0,0 +1,323 @@
+//! Keyword tokenization and snippet highlighting for full-text search.
+//!
+//! Uploaded documents are tokenized into an inverted index (term -> document
+//! -> term frequency). Queries go through the same tokenizer, so exact clause
+//! numbers such as `4.2` and names match even when embedding search misses them.
+//!
+//! A token is a run of letters and digits. Single `.`, `-`, `_` or `'` between
+//! two word characters are kept inside the token. Tokens are lowercased.
+//!
+//! Semantic search results are widened with the chunks around each hit and
+//! stitched into one passage, so a clause cut at a chunk boundary still reads
+//! as a whole.
+
+use crate::DocumentChunk;
+
+/// Longest token kept in the index, in characters
+const MAX_TOKEN_CHARS: usize = 64;
+
+/// Characters of context shown on each side of a match in a snippet
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+/// Marker wrapped around matched terms in snippets
+const HIGHLIGHT: &str = "**";
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '.' | '-' | '_' | '\'')
+}
+
+/// Single letters are noise; single digits are kept for clause and section numbers
+fn is_indexable(term: &str) -> bool {
+    let chars = term.chars().count();
+    chars <= MAX_TOKEN_CHARS && (chars >= 2 || term.chars().all(|c| c.is_numeric()))
+}
+
+/// Split text into lowercased tokens with their byte ranges in the original text
+pub fn tokenize(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if !c.is_alphanumeric() {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some(&(idx, next)) = chars.peek() {
+            if next.is_alphanumeric() {
+                end = idx + next.len_utf8();
+            } else if is_joiner(next) {
+                // Only keep the joiner when a word character follows it
+                let mut lookahead = text[idx + next.len_utf8()..].chars();
+                if !lookahead.next().is_some_and(|c| c.is_alphanumeric()) {
+                    break;
+                }
+            } else {
+                break;
+            }
+            chars.next();
+        }
+
+        let term = text[start..end].to_lowercase();
+        if is_indexable(&term) {
+            tokens.push((start, end, term));
+        }
+    }
+
+    tokens
+}
+
+/// Count how often each term occurs, sorted by term
+pub fn term_frequencies(text: &str) -> Vec<(String, u32)> {
+    let mut terms: Vec<String> = tokenize(text).into_iter().map(|(_, _, t)| t).collect();
+    terms.sort_unstable();
+
+    let mut frequencies: Vec<(String, u32)> = Vec::new();
+    for term in terms {
+        match frequencies.last_mut() {
+            Some((last, count)) if *last == term => *count += 1,
+            _ => frequencies.push((term, 1)),
+        }
+    }
+    frequencies
+}
+
+/// Distinct terms of a search query, in the order they appear
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for (_, _, term) in tokenize(query) {
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Extract up to `max_snippets` passages around matches of `terms`, with every
+/// matched term in a passage wrapped in `**`
+pub fn highlight_snippets(text: &str, terms: &[String], max_snippets: usize) -> Vec<String> {
+    let matches: Vec<(usize, usize)> = tokenize(text)
+        .into_iter()
+        .filter(|(_, _, term)| terms.contains(term))
+        .map(|(start, end, _)| (start, end))
+        .collect();
+
+    let mut snippets = Vec::new();
+    let mut covered_until = 0;
+    for &(start, end) in &matches {
+        if snippets.len() >= max_snippets {
+            break;
+        }
+        if start < covered_until {
+            continue;
+        }
+
+        let window_start = chars_before(text, start, SNIPPET_CONTEXT_CHARS);
+        let window_end = chars_after(text, end, SNIPPET_CONTEXT_CHARS);
+
+        let mut snippet = String::new();
+        if window_start > 0 {
+            snippet.push('…');
+        }
+        let mut cursor = window_start;
+        for &(match_start, match_end) in &matches {
+            if match_start < cursor || match_end > window_end {
+                continue;
+            }
+            snippet.push_str(&text[cursor..match_start]);
+            snippet.push_str(HIGHLIGHT);
+            snippet.push_str(&text[match_start..match_end]);
+            snippet.push_str(HIGHLIGHT);
+            cursor = match_end;
+        }
+        snippet.push_str(&text[cursor..window_end]);
+        if window_end < text.len() {
+            snippet.push('…');
+        }
+
+        snippets.push(snippet.split_whitespace().collect::<Vec<_>>().join(" "));
+        covered_until = window_end;
+    }
+
+    snippets
+}
+
+/// Byte offset `count` characters before `pos`, or the start of the text
+fn chars_before(text: &str, pos: usize, count: usize) -> usize {
+    if count == 0 {
+        return pos;
+    }
+    text[..pos]
+        .char_indices()
+        .rev()
+        .nth(count - 1)
+        .map(|(idx, _)| idx)
+        .unwrap_or(0)
+}
+
+/// Byte offset `count` characters after `pos`, or the end of the text
+fn chars_after(text: &str, pos: usize, count: usize) -> usize {
+    text[pos..]
+        .char_indices()
+        .nth(count)
+        .map(|(idx, _)| pos + idx)
+        .unwrap_or(text.len())
+}
+
+/// Consecutive chunks around one or more search hits, joined into a single passage
+#[derive(Debug, Clone)]
+pub struct Passage {
+    pub text: String,
+    pub score: f32,
+    pub chunk_count: usize,
+}
+
+/// Widen each hit to `window` chunks on either side (by position), merge windows that
+/// touch or overlap, and stitch each into a passage. `hits` are chunk indices with their
+/// scores, best first; passages keep the order of their best hit.
+pub fn stitch_hits(chunks: &[DocumentChunk], hits: &[(usize, f32)], window: usize) -> Vec<Passage> {
+    if chunks.is_empty() {
+        return Vec::new();
+    }
+
+    let mut order: Vec<usize> = (0..chunks.len()).collect();
+    order.sort_by_key(|&i| (chunks[i].start_position, chunks[i].end_position));
+    let mut rank = vec![0; chunks.len()];
+    for (position, &index) in order.iter().enumerate() {
+        rank[index] = position;
+    }
+
+    // (first rank, last rank, best score), merged as hits are added
+    let mut ranges: Vec<(usize, usize, f32)> = Vec::new();
+    for &(index, score) in hits {
+        let Some(&r) = rank.get(index) else {
+            continue;
+        };
+        let mut range = (
+            r.saturating_sub(window),
+            (r + window).min(chunks.len() - 1),
+            score,
+        );
+
+        // Absorb every existing range this one touches; the earlier range keeps its place
+        let mut slot = None;
+        let mut i = 0;
+        while i < ranges.len() {
+            let (lo, hi, best) = ranges[i];
+            if range.0 <= hi + 1 && lo <= range.1 + 1 {
+                range = (range.0.min(lo), range.1.max(hi), range.2.max(best));
+                if slot.is_none() {
+                    slot = Some(i);
+                    i += 1;
+                } else {
+                    ranges.remove(i);
+                }
+            } else {
+                i += 1;
+            }
+        }
+        match slot {
+            Some(i) => ranges[i] = range,
+            None => ranges.push(range),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(lo, hi, score)| {
+            let span: Vec<&DocumentChunk> = order[lo..=hi].iter().map(|&i| &chunks[i]).collect();
+            Passage {
+                text: stitch_chunks(&span),
+                score,
+                chunk_count: span.len(),
+            }
+        })
+        .collect()
+}
+
+/// Join chunks ordered by position, dropping text repeated where neighbours overlap
+fn stitch_chunks(chunks: &[&DocumentChunk]) -> String {
+    let mut passage = String::new();
+    let mut covered_until: Option<usize> = None;
+
+    for chunk in chunks {
+        let text = match covered_until {
+            Some(end) => skip_overlap(
+                &passage,
+                &chunk.text,
+                end.saturating_sub(chunk.start_position),
+            ),
+            None => chunk.text.as_str(),
+        };
+        if !passage.is_empty()
+            && !passage.ends_with(char::is_whitespace)
+            && !text.starts_with(char::is_whitespace)
+        {
+            passage.push(' ');
+        }
+        passage.push_str(text);
+        covered_until =
+            Some(covered_until.map_or(chunk.end_position, |e| e.max(chunk.end_position)));
+    }
+
+    passage.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The part of `next` that does not repeat the end of `passage`. `overlap` comes from the
+/// stored positions and is only trusted when the text actually matches.
+fn skip_overlap<'a>(passage: &str, next: &'a str, overlap: usize) -> &'a str {
+    if overlap == 0 {
+        return next;
+    }
+    let split = next
+        .char_indices()
+        .nth(overlap)
+        .map_or(next.len(), |(idx, _)| idx);
+    let (repeated, rest) = next.split_at(split);
+    if !repeated.trim().is_empty() && passage.trim_end().ends_with(repeated.trim_end()) {
+        rest
+    } else {
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn tokens_are_ordered_slices_of_the_text(text in "\\PC{0,200}") {
+            let mut previous_end = 0;
+            for (start, end, term) in tokenize(&text) {
+                prop_assert!(start >= previous_end && start < end);
+                prop_assert_eq!(text[start..end].to_lowercase(), term);
+                previous_end = end;
+            }
+        }
+
+        #[test]
+        fn query_terms_are_distinct(query in "\\PC{0,100}") {
+            let terms = query_terms(&query);
+            for (i, term) in terms.iter().enumerate() {
+                prop_assert!(!terms[i + 1..].contains(term));
+            }
+        }
+
+        #[test]
+        fn snippets_respect_the_limit_and_highlight_a_term(
+            text in "\\PC{0,400}",
+            query in "\\PC{0,20}",
+            max_snippets in 0usize..5,
+        ) {
+            let terms = query_terms(&query);
+            let snippets = highlight_snippets(&text, &terms, max_snippets);
+            prop_assert!(snippets.len() <= max_snippets);
+            for snippet in snippets {
+                prop_assert!(snippet.contains(HIGHLIGHT));
+            }
+        }
+    }
+}
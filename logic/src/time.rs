@@ -0,0 +1,252 @@
+//! Timestamps.
+//!
+//! `env::time_now()` returns nanoseconds since the Unix epoch. Clients work in
+//! milliseconds, and mixing the two up shows dates a million times off.
+//! Stored timestamps use `TimestampNanos` so the unit is part of the type.
+//! It serializes as a plain integer of nanoseconds, so the JSON and Borsh
+//! encodings are the same as a bare `u64`.
+
+use calimero_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use calimero_sdk::env;
+use calimero_sdk::serde::{Deserialize, Serialize};
+
+pub const NANOS_PER_MILLI: u64 = 1_000_000;
+pub const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Point in time, in nanoseconds since the Unix epoch
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[borsh(crate = "calimero_sdk::borsh")]
+#[serde(crate = "calimero_sdk::serde", transparent)]
+pub struct TimestampNanos(pub u64);
+
+impl TimestampNanos {
+    /// Current block time
+    pub fn now() -> Self {
+        TimestampNanos(env::time_now())
+    }
+
+    pub fn from_nanos(nanos: u64) -> Self {
+        TimestampNanos(nanos)
+    }
+
+    pub fn from_millis(millis: u64) -> Self {
+        TimestampNanos(millis.saturating_mul(NANOS_PER_MILLI))
+    }
+
+    pub fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Whole milliseconds, as JavaScript `Date` expects
+    pub fn as_millis(self) -> u64 {
+        self.0 / NANOS_PER_MILLI
+    }
+
+    pub fn as_secs(self) -> u64 {
+        self.0 / NANOS_PER_SECOND
+    }
+
+    /// Nanoseconds from `earlier` to `self`, or zero if `earlier` is later
+    pub fn nanos_since(self, earlier: TimestampNanos) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// This timestamp moved `nanos` later, saturating at the end of time
+    pub fn plus_nanos(self, nanos: u64) -> Self {
+        TimestampNanos(self.0.saturating_add(nanos))
+    }
+
+    /// Whether this point in time has been reached at `now`
+    pub fn has_passed(self, now: TimestampNanos) -> bool {
+        now >= self
+    }
+
+    /// UTC calendar month as `YYYY-MM`
+    pub fn year_month(self) -> String {
+        let (year, month, _) = civil_from_days(self.as_secs() / SECONDS_PER_DAY);
+        format!("{:04}-{:02}", year, month)
+    }
+
+    /// UTC calendar date as `YYYY-MM-DD`
+    pub fn date(self) -> String {
+        let (year, month, day) = civil_from_days(self.as_secs() / SECONDS_PER_DAY);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    /// Same time of day `months` calendar months later, clamped to the last day of a
+    /// shorter month (Jan 31 + 1 month is Feb 28 or 29)
+    pub fn plus_months(self, months: u64) -> Self {
+        let days = self.as_secs() / SECONDS_PER_DAY;
+        let time_of_day = self.0 - days * SECONDS_PER_DAY * NANOS_PER_SECOND;
+        let (year, month, day) = civil_from_days(days);
+
+        let month_index = year * 12 + (month - 1) + months;
+        let (year, month) = (month_index / 12, month_index % 12 + 1);
+        let day = day.min(days_in_month(year, month));
+
+        TimestampNanos(
+            days_from_civil(year, month, day)
+                .saturating_mul(SECONDS_PER_DAY * NANOS_PER_SECOND)
+                .saturating_add(time_of_day),
+        )
+    }
+}
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days from the Unix epoch to a date in the proleptic Gregorian calendar (Howard
+/// Hinnant's `days_from_civil`); only valid from 1970 on
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Year, month and day of a day counted from the Unix epoch, in the proleptic Gregorian
+/// calendar (Howard Hinnant's `civil_from_days`)
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+impl From<u64> for TimestampNanos {
+    fn from(nanos: u64) -> Self {
+        TimestampNanos(nanos)
+    }
+}
+
+impl From<TimestampNanos> for u64 {
+    fn from(timestamp: TimestampNanos) -> Self {
+        timestamp.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const NANOS_PER_DAY: u64 = SECONDS_PER_DAY * NANOS_PER_SECOND;
+
+    /// Days from the Unix epoch to the end of 2399
+    const LAST_DAY: u64 = 157_054;
+
+    fn civil(timestamp: TimestampNanos) -> (u64, u64, u64) {
+        civil_from_days(timestamp.as_secs() / SECONDS_PER_DAY)
+    }
+
+    #[test]
+    fn known_dates() {
+        assert_eq!(TimestampNanos(0).date(), "1970-01-01");
+        // 2024-02-29 00:00:00 UTC
+        assert_eq!(
+            TimestampNanos(1_709_164_800 * NANOS_PER_SECOND).date(),
+            "2024-02-29"
+        );
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+    }
+
+    #[test]
+    fn month_end_clamps_without_drifting() {
+        // 2023-01-31 12:00:00 UTC
+        let start = TimestampNanos(1_675_166_400 * NANOS_PER_SECOND);
+        assert_eq!(start.plus_months(1).date(), "2023-02-28");
+        assert_eq!(start.plus_months(2).date(), "2023-03-31");
+        assert_eq!(start.plus_months(13).date(), "2024-02-29");
+    }
+
+    proptest! {
+        #[test]
+        fn civil_days_round_trip(days in 0..=LAST_DAY) {
+            let (year, month, day) = civil_from_days(days);
+            prop_assert!((1..=12).contains(&month));
+            prop_assert!(day >= 1 && day <= days_in_month(year, month));
+            prop_assert_eq!(days_from_civil(year, month, day), days);
+        }
+
+        #[test]
+        fn plus_months_keeps_the_day_and_time_of_day(
+            nanos in 0..=LAST_DAY * NANOS_PER_DAY,
+            months in 0u64..1200,
+        ) {
+            let start = TimestampNanos(nanos);
+            let later = start.plus_months(months);
+            let (year, month, day) = civil(start);
+            let (later_year, later_month, later_day) = civil(later);
+
+            let month_index = year * 12 + (month - 1) + months;
+            prop_assert_eq!(later_year, month_index / 12);
+            prop_assert_eq!(later_month, month_index % 12 + 1);
+            prop_assert_eq!(later_day, day.min(days_in_month(later_year, later_month)));
+            prop_assert_eq!(later.0 % NANOS_PER_DAY, start.0 % NANOS_PER_DAY);
+        }
+
+        #[test]
+        fn plus_months_is_monotonic(
+            nanos in 0..=LAST_DAY * NANOS_PER_DAY,
+            months in 0u64..1200,
+        ) {
+            let start = TimestampNanos(nanos);
+            prop_assert_eq!(start.plus_months(0), start);
+            prop_assert!(start.plus_months(months + 1) > start.plus_months(months));
+        }
+
+        #[test]
+        fn months_from_the_anchor_return_to_the_anchor_day(
+            nanos in 0..=LAST_DAY * NANOS_PER_DAY,
+            months in 0u64..1200,
+        ) {
+            // A schedule computes each run from its first one, so a long month after a
+            // short one gets the original day back
+            let start = TimestampNanos(nanos);
+            let (_, _, day) = civil(start);
+            let (year, month, later_day) = civil(start.plus_months(months));
+            if days_in_month(year, month) >= day {
+                prop_assert_eq!(later_day, day);
+            }
+        }
+    }
+}